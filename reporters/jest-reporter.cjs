@@ -0,0 +1,227 @@
+const path = require("path");
+
+class LensReporter {
+  constructor(globalConfig) {
+    this._globalConfig = globalConfig;
+    this._startedFiles = new Set();
+    // Files whose test cases were already reported through onTestCaseResult
+    // (Jest >= 29). Older versions only report them in onTestFileResult.
+    this._caseResultFiles = new Set();
+    this._snapshotFiles = [];
+  }
+
+  onRunStart(aggregatedResults) {
+    this._startTime = Date.now();
+    this._startedFiles.clear();
+    this._caseResultFiles.clear();
+    this._snapshotFiles = [];
+    this._emit({
+      type: "run-started",
+      total: aggregatedResults.numTotalTestSuites,
+    });
+  }
+
+  // Jest >= 28 calls onTestFileStart; older versions only call onTestStart.
+  onTestFileStart(test) {
+    this._fileStarted(test.path);
+  }
+
+  onTestStart(test) {
+    this._fileStarted(test.path);
+  }
+
+  onTestCaseStart(test, info) {
+    this._emit({
+      type: "test-started",
+      file: test.path,
+      name: this._fullName(info),
+    });
+  }
+
+  onTestCaseResult(test, testCaseResult) {
+    this._caseResultFiles.add(test.path);
+    this._emitCaseResult(test.path, testCaseResult);
+  }
+
+  onTestFileResult(test, testResult) {
+    const file = test.path;
+
+    this._emit({
+      type: "tests-collected",
+      file,
+      count: testResult.testResults.length,
+    });
+
+    if (!this._caseResultFiles.has(file)) {
+      for (const testCaseResult of testResult.testResults) {
+        this._emitCaseResult(file, testCaseResult);
+      }
+    }
+
+    // Jest buffers console output per file when custom reporters are used.
+    for (const entry of testResult.console ?? []) {
+      const content = String(entry.message ?? "").trim();
+      if (content) {
+        const stream = ["error", "warn", "trace"].includes(entry.type)
+          ? "stderr"
+          : "stdout";
+        this._emit({ type: "console-log", file, stream, content });
+      }
+    }
+
+    // The file failed before any test could run (syntax error, bad import…).
+    if (testResult.testExecError) {
+      const err = testResult.testExecError;
+      this._emit({
+        type: "test-finished",
+        file,
+        name: "(suite failed to run)",
+        state: "failed",
+        duration: 0,
+        errors: [{ message: err.message ?? String(err), stack: err.stack }],
+      });
+    }
+
+    const snapshot = testResult.snapshot;
+    if (snapshot && (snapshot.added || snapshot.updated || snapshot.fileDeleted)) {
+      this._snapshotFiles.push(this._snapshotPath(file));
+    }
+
+    this._emit({ type: "file-finished", file });
+    this._startedFiles.delete(file);
+  }
+
+  onRunComplete(_contexts, results) {
+    if (this._snapshotFiles.length > 0) {
+      this._emit({ type: "snapshots-written", files: this._snapshotFiles });
+    }
+
+    if (results.coverageMap) {
+      this._emit({ type: "coverage", files: coverageFiles(results.coverageMap) });
+    }
+
+    const snapshot = results.snapshot ?? {};
+    this._emit({
+      type: "run-finished",
+      total: results.numTotalTests,
+      passed: results.numPassedTests,
+      failed: results.numFailedTests,
+      skipped: results.numPendingTests + results.numTodoTests,
+      duration: Date.now() - (this._startTime || Date.now()),
+      snapshots: {
+        written: snapshot.added ?? 0,
+        updated: snapshot.updated ?? 0,
+        obsolete: snapshot.unchecked ?? 0,
+      },
+    });
+  }
+
+  getLastError() {
+    return undefined;
+  }
+
+  // Where Jest's default snapshot resolver keeps a test file's snapshots. Custom
+  // resolvers aren't visible to reporters.
+  _snapshotPath(file) {
+    return path.join(
+      path.dirname(file),
+      "__snapshots__",
+      path.basename(file) + ".snap",
+    );
+  }
+
+  _fileStarted(file) {
+    if (this._startedFiles.has(file)) return;
+    this._startedFiles.add(file);
+    this._emit({ type: "file-started", file });
+  }
+
+  _emitCaseResult(file, testCaseResult) {
+    const loc = testCaseResult.location;
+    const event = {
+      type: "test-finished",
+      file,
+      name: this._fullName(testCaseResult),
+      state: this._state(testCaseResult.status),
+      duration: testCaseResult.duration ?? undefined,
+      location: loc ? { line: loc.line, column: loc.column + 1 } : undefined,
+    };
+
+    if (testCaseResult.status === "failed") {
+      event.errors = this._errors(testCaseResult);
+    }
+
+    this._emit(event);
+  }
+
+  // One entry per failure message; `failureDetails` lines up with
+  // `failureMessages` when available.
+  _errors(testCaseResult) {
+    const messages = testCaseResult.failureMessages ?? [];
+    const details = testCaseResult.failureDetails ?? [];
+    const count = Math.max(messages.length, details.length, 1);
+    const errors = [];
+    for (let i = 0; i < count; i++) {
+      errors.push(this._error(details[i], messages[i] ?? ""));
+    }
+    return errors;
+  }
+
+  _error(detail, raw) {
+    const matcher = detail?.matcherResult;
+
+    const error = {
+      message: detail?.message ?? raw.split("\n")[0],
+      stack: detail?.stack ?? raw,
+    };
+    if (matcher && "expected" in matcher) {
+      error.expected = this._format(matcher.expected);
+    }
+    if (matcher && "actual" in matcher) {
+      error.actual = this._format(matcher.actual);
+    }
+    return error;
+  }
+
+  // Map Jest's case statuses onto the vocabulary lens understands.
+  _state(status) {
+    if (status === "passed" || status === "failed") return status;
+    return "skipped";
+  }
+
+  _fullName(entry) {
+    return [...(entry.ancestorTitles ?? []), entry.title].join(" > ");
+  }
+
+  _format(value) {
+    if (value === undefined) return "undefined";
+    if (typeof value === "string") return JSON.stringify(value);
+    try {
+      return JSON.stringify(value, null, 2) ?? String(value);
+    } catch {
+      return String(value);
+    }
+  }
+
+  _emit(event) {
+    process.stdout.write(JSON.stringify(event) + "\n");
+  }
+}
+
+// Per-file summary of an istanbul coverage map.
+function coverageFiles(map) {
+  const metric = (m) => ({ covered: m?.covered ?? 0, total: m?.total ?? 0 });
+  return map.files().map((file) => {
+    const fileCoverage = map.fileCoverageFor(file);
+    const summary = fileCoverage.toSummary();
+    return {
+      file,
+      lines: metric(summary.lines),
+      branches: metric(summary.branches),
+      functions: metric(summary.functions),
+      uncovered_lines: fileCoverage.getUncoveredLines().map(Number),
+    };
+  });
+}
+
+module.exports = LensReporter;
@@ -0,0 +1,207 @@
+// A `node --test` reporter: node passes every test event through this async
+// generator, and each line it yields is a lens NDJSON event.
+
+export default async function* lensReporter(source) {
+  const state = newRun();
+
+  for await (const { type, data } of source) {
+    // Summaries trail a run; a new one starts with its first file's events.
+    if (!state.started && data?.file) {
+      state.started = true;
+      state.startTime = Date.now();
+      yield emit({ type: "run-started", total: 0 });
+    }
+    if (data?.file && !state.files.has(data.file)) {
+      state.files.add(data.file);
+      yield emit({ type: "file-started", file: data.file });
+    }
+
+    switch (type) {
+      case "test:enqueue":
+        // Newer versions of node say whether it's a test or a suite.
+        if (data.details?.type === "test" || data.type === "test") {
+          yield emit({ type: "tests-collected", file: data.file, count: 1 });
+        }
+        break;
+
+      // Node reports in declaration order once tests are done, so a start is
+      // right before its result and isn't forwarded; it's still where a test
+      // turns out to be a suite, when its first child starts.
+      case "test:start":
+        if (isFileWrapper(data)) break;
+        state.stack.length = data.nesting;
+        state.stack.push({ name: data.name, hasChildren: false });
+        if (data.nesting > 0 && state.stack[data.nesting - 1]) {
+          state.stack[data.nesting - 1].hasChildren = true;
+        }
+        break;
+
+      case "test:pass":
+      case "test:fail":
+        yield* finished(state, type === "test:pass", data);
+        break;
+
+      case "test:stdout":
+      case "test:stderr": {
+        const stream = type === "test:stdout" ? "stdout" : "stderr";
+        const content = String(data.message ?? "").replace(/\n$/, "");
+        if (data.file) {
+          if (stream === "stderr") {
+            const previous = state.stderr.get(data.file) ?? "";
+            state.stderr.set(data.file, previous + data.message);
+          }
+          if (content) {
+            yield emit({ type: "console-log", file: data.file, stream, content });
+          }
+        } else if (content) {
+          yield emit({ type: "output", line: content });
+        }
+        break;
+      }
+
+      case "test:coverage":
+        yield emit({ type: "coverage", files: coverageFiles(data.summary) });
+        break;
+
+      case "test:watch:drained":
+        // Watch mode never ends the stream; each re-run ends here instead.
+        yield* runFinished(state);
+        Object.assign(state, newRun());
+        break;
+    }
+  }
+
+  if (state.started) yield* runFinished(state);
+}
+
+function newRun() {
+  return {
+    started: false,
+    startTime: 0,
+    files: new Set(),
+    // Names of the tests enclosing the current one, by nesting level.
+    stack: [],
+    // What each file wrote to stderr, the only trace of a file failing to load.
+    stderr: new Map(),
+    counts: { passed: 0, failed: 0, skipped: 0 },
+  };
+}
+
+function* finished(state, passed, data) {
+  const details = data.details ?? {};
+  const error = details.error;
+  // Failures that only say a subtest failed are already shown on the subtest.
+  const ownFailure = !passed && error?.failureType !== "subtestsFailed";
+
+  if (isFileWrapper(data)) {
+    if (ownFailure) {
+      const stderr = state.stderr.get(data.file) ?? "";
+      const thrown = stderr.split("\n").find((line) => /^\w*Error\b/.test(line));
+      state.counts.failed += 1;
+      yield emit({
+        type: "test-finished",
+        file: data.file,
+        name: "(file failed to run)",
+        state: "failed",
+        duration: details.duration_ms,
+        errors: [
+          { message: thrown ?? error?.message ?? "test file failed", stack: stderr },
+        ],
+      });
+    }
+    yield emit({ type: "file-finished", file: data.file });
+    state.files.delete(data.file);
+    return;
+  }
+
+  const name = fullName(state, data);
+  const entry = state.stack[data.nesting];
+  const location = { line: data.line, column: data.column };
+  if (details.type === "suite" || (entry?.hasChildren && !ownFailure)) {
+    yield emit({ type: "suite-location", file: data.file, name, location });
+    return;
+  }
+
+  const skipped = data.skip !== undefined || data.todo !== undefined;
+  const result = skipped ? "skipped" : passed ? "passed" : "failed";
+  state.counts[result] += 1;
+  const event = {
+    type: "test-finished",
+    file: data.file,
+    name,
+    state: result,
+    duration: details.duration_ms,
+    location,
+  };
+  if (result === "failed") {
+    event.errors = [toError(error)];
+  }
+  yield emit(event);
+}
+
+function* runFinished(state) {
+  for (const file of state.files) {
+    yield emit({ type: "file-finished", file });
+  }
+  const { passed, failed, skipped } = state.counts;
+  yield emit({
+    type: "run-finished",
+    total: passed + failed + skipped,
+    passed,
+    failed,
+    skipped,
+    duration: Date.now() - state.startTime,
+  });
+}
+
+// With process isolation each file runs as a top-level test named after it.
+function isFileWrapper(data) {
+  return data.nesting === 0 && data.name === data.file;
+}
+
+function fullName(state, data) {
+  const ancestors = state.stack.slice(0, data.nesting).map((entry) => entry.name);
+  return [...ancestors, data.name].join(" > ");
+}
+
+// The runner wraps what the test threw; `cause` is the original error, with
+// `expected`/`actual` when it came from `node:assert`.
+function toError(error) {
+  if (!error) return { message: "test failed" };
+  const cause = error.cause?.message !== undefined ? error.cause : error;
+  const message = String(cause.message ?? error.message ?? "test failed");
+  const result = { message, stack: cause.stack };
+  if (cause.code === "ERR_ASSERTION" && "expected" in cause && "actual" in cause) {
+    result.message = message.split("\n")[0];
+    result.expected = format(cause.expected);
+    result.actual = format(cause.actual);
+  }
+  return result;
+}
+
+function format(value) {
+  if (value === undefined) return "undefined";
+  if (typeof value === "string") return JSON.stringify(value);
+  try {
+    return JSON.stringify(value, null, 2) ?? String(value);
+  } catch {
+    return String(value);
+  }
+}
+
+// Per-file summary of node's coverage report.
+function coverageFiles(summary) {
+  return (summary?.files ?? []).map((file) => ({
+    file: file.path,
+    lines: { covered: file.coveredLineCount, total: file.totalLineCount },
+    branches: { covered: file.coveredBranchCount, total: file.totalBranchCount },
+    functions: { covered: file.coveredFunctionCount, total: file.totalFunctionCount },
+    uncovered_lines: (file.lines ?? [])
+      .filter((line) => line.count === 0)
+      .map((line) => line.line),
+  }));
+}
+
+function emit(event) {
+  return JSON.stringify(event) + "\n";
+}
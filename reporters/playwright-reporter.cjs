@@ -0,0 +1,174 @@
+class LensReporter {
+  constructor() {
+    // Tests still to finish per file, counted over every project, so a file
+    // is only finished once all its browsers are done with it.
+    this._remaining = new Map();
+    this._startedFiles = new Set();
+    this._counts = { passed: 0, failed: 0, skipped: 0 };
+  }
+
+  // Keeps Playwright from adding its own terminal reporter.
+  printsToStdio() {
+    return true;
+  }
+
+  onBegin(_config, suite) {
+    this._remaining.clear();
+    this._startedFiles.clear();
+    this._counts = { passed: 0, failed: 0, skipped: 0 };
+
+    const tests = suite.allTests();
+    this._emit({ type: "run-started", total: tests.length });
+    for (const test of tests) {
+      const file = test.location.file;
+      this._remaining.set(file, (this._remaining.get(file) ?? 0) + 1);
+    }
+    for (const [file, count] of this._remaining) {
+      this._emit({ type: "tests-collected", file, count });
+    }
+    this._suiteLocations(suite);
+  }
+
+  onTestBegin(test) {
+    const file = test.location.file;
+    if (!this._startedFiles.has(file)) {
+      this._startedFiles.add(file);
+      this._emit({ type: "file-started", file });
+    }
+    this._emit({ type: "test-started", file, name: this._fullName(test) });
+  }
+
+  onTestEnd(test, result) {
+    // A failed attempt with retries left is followed by another one.
+    const outcome = test.outcome();
+    if (outcome === "unexpected" && result.retry < test.retries) return;
+
+    const file = test.location.file;
+    const state = this._state(outcome);
+    this._counts[state] += 1;
+
+    const event = {
+      type: "test-finished",
+      file,
+      name: this._fullName(test),
+      state,
+      duration: result.duration,
+      location: { line: test.location.line, column: test.location.column },
+    };
+    if (state === "failed") {
+      event.errors = result.errors.map((error) => this._error(error));
+      if (event.errors.length === 0) {
+        event.errors.push({ message: `Test ${result.status}` });
+      }
+      event.attachments = result.attachments
+        .filter((attachment) => attachment.path)
+        .map((attachment) => ({
+          name: attachment.name,
+          content_type: attachment.contentType,
+          path: attachment.path,
+        }));
+    }
+    this._emit(event);
+
+    const remaining = (this._remaining.get(file) ?? 1) - 1;
+    this._remaining.set(file, remaining);
+    if (remaining === 0) {
+      this._emit({ type: "file-finished", file });
+    }
+  }
+
+  onStdOut(chunk, test) {
+    this._console(chunk, test, "stdout");
+  }
+
+  onStdErr(chunk, test) {
+    this._console(chunk, test, "stderr");
+  }
+
+  onError(error) {
+    this._emit({
+      type: "error",
+      message: stripAnsi(error.stack ?? error.message ?? String(error.value)),
+    });
+  }
+
+  onEnd(result) {
+    const { passed, failed, skipped } = this._counts;
+    this._emit({
+      type: "run-finished",
+      total: passed + failed + skipped,
+      passed,
+      failed,
+      skipped,
+      duration: Math.round(result.duration ?? 0),
+    });
+  }
+
+  // Describe blocks, named like the tests inside them so each browser project
+  // gets its own.
+  _suiteLocations(suite) {
+    for (const child of suite.suites) {
+      if (child.type === "describe" && child.location) {
+        this._emit({
+          type: "suite-location",
+          file: child.location.file,
+          name: this._fullName(child),
+          location: { line: child.location.line, column: child.location.column },
+        });
+      }
+      this._suiteLocations(child);
+    }
+  }
+
+  _console(chunk, test, stream) {
+    const content = String(chunk).replace(/\n$/, "");
+    if (!content) return;
+    if (test) {
+      const file = test.location.file;
+      this._emit({ type: "console-log", file, test: this._fullName(test), stream, content });
+    } else {
+      // Global setup and the like, which belong to no test file.
+      for (const line of content.split("\n")) {
+        this._emit({ type: "output", line });
+      }
+    }
+  }
+
+  // `titlePath()` is root, project, file, describes…, title. The project is
+  // kept as the top level under the file; the file is already the tree's.
+  _fullName(entry) {
+    const [, project, , ...titles] = entry.titlePath();
+    return (project ? [project, ...titles] : titles).join(" > ");
+  }
+
+  // Playwright's outcomes, mapped onto the vocabulary lens understands. Flaky
+  // tests passed in the end; expected failures (`test.fail()`) count as passes.
+  _state(outcome) {
+    if (outcome === "skipped") return "skipped";
+    if (outcome === "unexpected") return "failed";
+    return "passed";
+  }
+
+  // `expect` messages carry the values as `Expected: …` / `Received: …` lines.
+  _error(error) {
+    const message = stripAnsi(error.message ?? String(error.value ?? "unknown error"));
+    const result = { message, stack: error.stack ? stripAnsi(error.stack) : undefined };
+    const expected = /^Expected(?: [\w ]+)?: (.*)$/m.exec(message);
+    const received = /^Received(?: [\w ]+)?: (.*)$/m.exec(message);
+    if (expected && received) {
+      result.expected = expected[1];
+      result.actual = received[1];
+    }
+    return result;
+  }
+
+  _emit(event) {
+    process.stdout.write(JSON.stringify(event) + "\n");
+  }
+}
+
+function stripAnsi(text) {
+  return text.replace(/\u001b\[[0-9;]*[A-Za-z]/g, "");
+}
+
+module.exports = LensReporter;
@@ -0,0 +1,326 @@
+# A pytest plugin, loaded with `-p lens_pytest_plugin`: each line it writes to
+# stdout is a lens NDJSON event. Pytest's own terminal output moves to stderr
+# so the two never share a line.
+
+import json
+import os
+import sys
+import time
+
+import pytest
+
+# Full names to run, joined with " > ", when lens runs only some tests.
+NAMES_ENV = "LENS_PYTEST_NAMES"
+UPDATE_SNAPSHOTS_ENV = "LENS_PYTEST_UPDATE_SNAPSHOTS"
+COVERAGE_ENV = "LENS_PYTEST_COVERAGE"
+
+
+class LensPlugin:
+    def __init__(self, config):
+        self.config = config
+        # Captured before the first test runs, while pytest isn't capturing.
+        self.out = os.fdopen(os.dup(1), "w", buffering=1, encoding="utf-8")
+        self.items = {}
+        # Tests still to finish in each file, to know when a file is done.
+        self.remaining = {}
+        self.started_files = set()
+        self.reports = {}
+        # Operands of the failing `==` each test's assertion rewrote.
+        self.comparisons = {}
+        self.current = None
+        self.counts = {"passed": 0, "failed": 0, "skipped": 0}
+        self.start_time = time.time()
+        self.coverage = None
+
+    def emit(self, event):
+        self.out.write(json.dumps(event) + "\n")
+
+    def pytest_sessionstart(self, session):
+        self.emit({"type": "run-started", "total": 0})
+        if os.environ.get(COVERAGE_ENV):
+            self.start_coverage()
+
+    def pytest_collection_modifyitems(self, session, config, items):
+        wanted = os.environ.get(NAMES_ENV)
+        if not wanted:
+            return
+        names = json.loads(wanted)
+        selected, deselected = [], []
+        for item in items:
+            name = test_name(item)
+            # A name can also be a suite's, which runs everything inside it.
+            if any(name == n or name.startswith(n + " > ") for n in names):
+                selected.append(item)
+            else:
+                deselected.append(item)
+        if deselected:
+            config.hook.pytest_deselected(items=deselected)
+            items[:] = selected
+
+    def pytest_collectreport(self, report):
+        # Errors outside any file, like a missing path, are pytest's to print.
+        if not report.failed or not report.nodeid:
+            return
+        # A file that can't be imported has no tests, only this report.
+        file = self.absolute(report.nodeid.split("::")[0])
+        text = report.longreprtext
+        thrown = [line[1:].strip() for line in text.splitlines() if line.startswith("E ")]
+        self.counts["failed"] += 1
+        self.emit({"type": "file-started", "file": file})
+        self.emit(
+            {
+                "type": "test-finished",
+                "file": file,
+                "name": "(file failed to run)",
+                "state": "failed",
+                "errors": [
+                    {
+                        "message": thrown[-1] if thrown else "test file failed",
+                        "stack": text,
+                    }
+                ],
+            }
+        )
+        self.emit({"type": "file-finished", "file": file})
+
+    def pytest_collection_finish(self, session):
+        located = set()
+        for item in session.items:
+            file = item_file(item)
+            self.items[item.nodeid] = item
+            self.remaining[file] = self.remaining.get(file, 0) + 1
+            for node, name in suites(item):
+                if (file, name) not in located:
+                    located.add((file, name))
+                    location = node_location(node)
+                    if location:
+                        self.emit(
+                            {
+                                "type": "suite-location",
+                                "file": file,
+                                "name": name,
+                                "location": location,
+                            }
+                        )
+        for file, count in self.remaining.items():
+            self.emit({"type": "tests-collected", "file": file, "count": count})
+
+    def pytest_runtest_logstart(self, nodeid, location):
+        item = self.items.get(nodeid)
+        if item is None:
+            return
+        file = item_file(item)
+        if file not in self.started_files:
+            self.started_files.add(file)
+            self.emit({"type": "file-started", "file": file})
+        self.current = nodeid
+        self.emit({"type": "test-started", "file": file, "name": test_name(item)})
+
+    def pytest_assertrepr_compare(self, config, op, left, right):
+        # Called by rewritten asserts when a comparison fails. Returning None
+        # leaves pytest's own explanation in place.
+        if op == "==" and self.current is not None:
+            self.comparisons[self.current] = (left, right)
+        return None
+
+    def pytest_runtest_logreport(self, report):
+        if report.nodeid in self.items:
+            self.reports.setdefault(report.nodeid, []).append(report)
+
+    def pytest_runtest_logfinish(self, nodeid, location):
+        item = self.items.get(nodeid)
+        if item is None:
+            return
+        self.current = None
+        file = item_file(item)
+        name = test_name(item)
+        reports = self.reports.pop(nodeid, [])
+        state = outcome(reports)
+        self.counts[state] += 1
+
+        event = {
+            "type": "test-finished",
+            "file": file,
+            "name": name,
+            "state": state,
+            "duration": sum(r.duration for r in reports) * 1000,
+        }
+        location = node_location(item)
+        if location:
+            event["location"] = location
+        if state == "failed":
+            event["errors"] = [
+                self.to_error(nodeid, r) for r in reports if r.failed
+            ]
+        self.comparisons.pop(nodeid, None)
+
+        # Each phase's report carries everything captured up to it.
+        last = reports[-1] if reports else None
+        for stream, content in (
+            ("stdout", last.capstdout if last else ""),
+            ("stderr", last.capstderr if last else ""),
+        ):
+            content = content.rstrip("\n")
+            if content:
+                self.emit(
+                    {
+                        "type": "console-log",
+                        "file": file,
+                        "test": name,
+                        "stream": stream,
+                        "content": content,
+                    }
+                )
+        self.emit(event)
+
+        self.remaining[file] -= 1
+        if self.remaining[file] == 0:
+            self.emit({"type": "file-finished", "file": file})
+
+    def pytest_sessionfinish(self, session, exitstatus):
+        for file, count in self.remaining.items():
+            if count > 0 and file in self.started_files:
+                self.emit({"type": "file-finished", "file": file})
+        if self.coverage is not None:
+            self.emit({"type": "coverage", "files": self.stop_coverage()})
+        counts = self.counts
+        self.emit(
+            {
+                "type": "run-finished",
+                "total": counts["passed"] + counts["failed"] + counts["skipped"],
+                "passed": counts["passed"],
+                "failed": counts["failed"],
+                "skipped": counts["skipped"],
+                "duration": int((time.time() - self.start_time) * 1000),
+            }
+        )
+        self.out.flush()
+
+    def to_error(self, nodeid, report):
+        longrepr = report.longrepr
+        crash = getattr(longrepr, "reprcrash", None)
+        message = crash.message if crash is not None else str(longrepr)
+        lines = message.splitlines() or ["test failed"]
+        error = {"message": lines[0], "stack": report.longreprtext}
+        if crash is not None and crash.path:
+            error["stack"] = "at {}:{}\n{}".format(
+                self.absolute(crash.path), crash.lineno, report.longreprtext
+            )
+        # Only the call phase runs the test's own asserts.
+        if report.when == "call" and nodeid in self.comparisons:
+            actual, expected = self.comparisons[nodeid]
+            error["expected"] = format_value(expected)
+            error["actual"] = format_value(actual)
+        elif len(lines) > 1:
+            error["diff"] = "\n".join(lines[1:])
+        return error
+
+    def absolute(self, path):
+        return os.path.normpath(os.path.join(str(self.config.rootpath), str(path)))
+
+    def start_coverage(self):
+        try:
+            import coverage
+        except ImportError:
+            self.emit({"type": "output", "line": "Coverage needs the coverage package."})
+            return
+        self.coverage = coverage.Coverage()
+        self.coverage.start()
+
+    def stop_coverage(self):
+        self.coverage.stop()
+        files = []
+        for path in sorted(self.coverage.get_data().measured_files()):
+            try:
+                _, statements, _, missing, _ = self.coverage.analysis2(path)
+            except Exception:
+                continue
+            files.append(
+                {
+                    "file": path,
+                    "lines": {
+                        "covered": len(statements) - len(missing),
+                        "total": len(statements),
+                    },
+                    # Line coverage is all coverage.py measures by default.
+                    "branches": {"covered": 0, "total": 0},
+                    "functions": {"covered": 0, "total": 0},
+                    "uncovered_lines": sorted(missing),
+                }
+            )
+        return files
+
+def item_file(item):
+    return os.path.normpath(str(item.path))
+
+
+def test_name(item):
+    # The node id after the file: classes, then the function and its params.
+    return " > ".join(item.nodeid.split("::")[1:])
+
+
+def suites(item):
+    """The classes around `item`, outermost first, with their full names."""
+    names = item.nodeid.split("::")[1:-1]
+    nodes = [n for n in item.listchain() if isinstance(n, pytest.Class)]
+    return [
+        (node, " > ".join(names[: i + 1])) for i, node in enumerate(nodes[: len(names)])
+    ]
+
+
+def node_location(node):
+    try:
+        _, line, _ = node.reportinfo()
+    except Exception:
+        return None
+    if line is None:
+        return None
+    # Pytest counts lines from 0.
+    return {"line": line + 1, "column": 1}
+
+
+def outcome(reports):
+    if any(r.failed for r in reports):
+        return "failed"
+    if any(r.skipped for r in reports):
+        return "skipped"
+    return "passed"
+
+
+def format_value(value):
+    if isinstance(value, str):
+        return json.dumps(value)
+    if isinstance(value, (dict, list, tuple)):
+        try:
+            return json.dumps(value, indent=2)
+        except (TypeError, ValueError):
+            pass
+    return repr(value)
+
+
+@pytest.hookimpl(hookwrapper=True)
+def pytest_configure(config):
+    # Under pytest-xdist, say from `addopts = "-n auto"`, tests would run in
+    # worker processes this plugin never sees; run them in this one instead.
+    # Blocking the plugin outright would reject the `-n` in addopts.
+    if getattr(config.option, "dist", "no") != "no":
+        config.option.dist = "no"
+        config.option.numprocesses = None
+
+    if os.environ.get(UPDATE_SNAPSHOTS_ENV):
+        # syrupy's `--snapshot-update`; pytest has no snapshots of its own.
+        if hasattr(config.option, "update_snapshots"):
+            config.option.update_snapshots = True
+        else:
+            sys.stderr.write("Updating snapshots needs syrupy.\n")
+
+    # Output pytest writes for a person goes where lens shows the runner log.
+    # The terminal reporter writes to whatever `sys.stdout` is when it's
+    # configured, which happens inside this wrapper.
+    stdout, sys.stdout = sys.stdout, sys.stderr
+    try:
+        yield
+    finally:
+        sys.stdout = stdout
+
+    config.pluginmanager.register(LensPlugin(config), "lens-reporter")
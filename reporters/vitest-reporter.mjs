@@ -17,6 +17,7 @@ export default class LensReporter {
           JSON.stringify({
             type: "console-log",
             file: this._currentFile,
+            stream: "stdout",
             content: trimmed,
           }) + "\n";
         return this._originalWrite(event, encoding, callback);
@@ -35,33 +36,71 @@ export default class LensReporter {
     // Prevent the stdin listener from keeping the process alive in non-watch (vitest run) mode.
     process.stdin.unref();
 
-    process.stdin.on("data", async (data) => {
+    // Commands can arrive while vitest is still initializing; hold them until the
+    // watcher reports it is idle. The timeout covers versions that never call
+    // onWatcherStart in standalone mode.
+    this._ready = new Promise((resolve) => {
+      this._markReady = resolve;
+      setTimeout(resolve, 5000).unref();
+    });
+
+    // As a lens server, only run on command. The watcher still invalidates changed
+    // modules, so the next command picks up edits.
+    if (process.env.LENS_SERVER) {
+      vitest.onFilterWatchedSpecification?.(() => false);
+    }
+
+    // Record each file's snapshot result so the run can report what was written.
+    this._snapshotResults = [];
+    const snapshot = vitest.snapshot;
+    if (typeof snapshot?.add === "function") {
+      const add = snapshot.add.bind(snapshot);
+      snapshot.add = (result) => {
+        this._snapshotResults.push(result);
+        return add(result);
+      };
+    }
+
+    // Serialize commands so a second run never starts while one is in flight.
+    this._queue = Promise.resolve();
+
+    process.stdin.on("data", (data) => {
       const lines = data.toString().split("\n");
       for (const line of lines) {
         const trimmed = line.trim();
         if (trimmed.startsWith("LENS_RUN:")) {
-          try {
-            const cmd = JSON.parse(trimmed.substring(9));
-            this._emit({
-              type: "output",
-              line: "[REPORTER] Command: " + JSON.stringify(cmd),
-            });
-            await this._handleCommand(cmd);
-          } catch (e) {
-            this._emit({
-              type: "error",
-              message:
-                "Reporter failed to handle command: " +
-                e.message +
-                "\n" +
-                e.stack,
-            });
-          }
+          this._queue = this._queue.then(() => this._runCommand(trimmed));
         }
       }
     });
   }
 
+  onWatcherStart() {
+    if (!this._serverReady) {
+      this._serverReady = true;
+      this._emit({ type: "server-ready" });
+    }
+    this._markReady?.();
+  }
+
+  async _runCommand(line) {
+    await this._ready;
+    try {
+      const cmd = JSON.parse(line.substring(9));
+      this._emit({
+        type: "output",
+        line: "[REPORTER] Command: " + JSON.stringify(cmd),
+      });
+      await this._handleCommand(cmd);
+    } catch (e) {
+      this._emit({
+        type: "error",
+        message:
+          "Reporter failed to handle command: " + e.message + "\n" + e.stack,
+      });
+    }
+  }
+
   async _handleCommand(cmd) {
     if (!this.ctx) {
       this._emit({ type: "error", message: "Reporter context not available" });
@@ -72,12 +111,20 @@ export default class LensReporter {
     this.ctx.config.testNamePattern = undefined;
 
     if (cmd.type === "run-all") {
-      await this.ctx.rerunFiles();
+      await this.ctx.rerunFiles(await this._allTestFiles());
+    } else if (cmd.type === "update-snapshots") {
+      if (cmd.name) {
+        this.ctx.config.testNamePattern = new RegExp(this._escape(cmd.name));
+      }
+      await this._updateSnapshots(cmd.files);
+    } else if (cmd.type === "run-files") {
+      await this.ctx.rerunFiles(cmd.files);
+    } else if (cmd.type === "run-tests") {
+      this.ctx.config.testNamePattern = new RegExp(cmd.pattern);
+      await this.ctx.rerunFiles(cmd.files);
     } else if (cmd.type === "run-file" || cmd.type === "run-test") {
       if (cmd.type === "run-test") {
-        // Escape special regex characters in the test name
-        const escapedName = cmd.name.replace(/[.*+?^${}()|[\]\\]/g, "\\$&");
-        this.ctx.config.testNamePattern = new RegExp(escapedName);
+        this.ctx.config.testNamePattern = new RegExp(this._escape(cmd.name));
       }
 
       // Try to find the file in the projects to ensure it's a known test file.
@@ -109,11 +156,48 @@ export default class LensReporter {
 
       this._emit({ type: "output", line: `[REPORTER] Rerunning: ${files[0]}` });
       await this.ctx.rerunFiles(files);
+    } else {
+      this._emit({ type: "error", message: `Unknown command: ${cmd.type}` });
+    }
+  }
+
+  // Rerun `files` with snapshot updating enabled, restoring the previous mode
+  // afterwards so later runs don't silently rewrite snapshots.
+  async _updateSnapshots(files) {
+    if (typeof this.ctx.updateSnapshot === "function") {
+      await this.ctx.updateSnapshot(files);
+      return;
+    }
+    const options = this.ctx.config.snapshotOptions;
+    const previous = options.updateSnapshot;
+    options.updateSnapshot = "all";
+    try {
+      await this.ctx.rerunFiles(files);
+    } finally {
+      options.updateSnapshot = previous;
     }
   }
 
+  // Escape special regex characters in a full test name. Vitest matches the
+  // names joined with spaces where lens joins them with " > ".
+  _escape(name) {
+    return name.split(" > ").join(" ").replace(/[.*+?^${}()|[\]\\]/g, "\\$&");
+  }
+
+  // Every test file vitest knows about. `rerunFiles()` without arguments only
+  // reruns files that already ran, which is nothing in a fresh standalone server.
+  async _allTestFiles() {
+    const glob =
+      this.ctx.globTestSpecifications?.bind(this.ctx) ??
+      this.ctx.globTestFiles?.bind(this.ctx);
+    if (!glob) return undefined;
+    const specs = await glob();
+    return [...new Set(specs.map((s) => s.moduleId ?? s[1]))];
+  }
+
   onTestRunStart(specifications) {
     this._startTime = Date.now();
+    this._snapshotResults = [];
     this._emit({ type: "run-started", total: specifications.length });
   }
 
@@ -164,20 +248,49 @@ export default class LensReporter {
       location: loc ? { line: loc.line, column: loc.column } : undefined,
     };
 
+    // Every error, not just the first: `expect.soft` can record several.
     if (result.state === "failed" && result.errors.length > 0) {
-      const err = result.errors[0];
-      event.error = {
+      event.errors = result.errors.map((err) => ({
         message: err.message ?? "",
         expected: err.expected,
         actual: err.actual,
         diff: err.diff,
         stack: err.stack,
-      };
+      }));
     }
 
     this._emit(event);
   }
 
+  // Vitest intercepts console calls in the workers and reports each one here,
+  // with the id of the test (or suite, or file) that was running.
+  onUserConsoleLog(log) {
+    const content = String(log.content ?? "").replace(/\n$/, "");
+    if (!content.trim()) return;
+    const task = log.taskId ? this.ctx?.state?.idMap?.get(log.taskId) : undefined;
+    const file = task?.file?.filepath ?? this._currentFile;
+    if (!file) return;
+    this._emit({
+      type: "console-log",
+      file,
+      test: task?.type === "test" ? this._fullName(task) : undefined,
+      stream: log.type === "stderr" ? "stderr" : "stdout",
+      content,
+    });
+  }
+
+  // The name lens keys tests by, as `TestCase.fullName` spells it: suite and
+  // test names joined with " > ".
+  _fullName(task) {
+    const reported = this.ctx.state.getReportedEntity?.(task);
+    if (reported?.fullName) return reported.fullName;
+    const names = [];
+    for (let t = task; t && t !== task.file; t = t.suite) {
+      names.unshift(t.name);
+    }
+    return names.join(" > ");
+  }
+
   onTestModuleEnd(module) {
     this._emit({ type: "file-finished", file: module.moduleId });
     this._currentFile = null;
@@ -203,7 +316,7 @@ export default class LensReporter {
         name: "(unhandled error)",
         state: "fail",
         duration: 0,
-        error: { message, stack },
+        errors: [{ message, stack }],
       });
     }
 
@@ -222,14 +335,66 @@ export default class LensReporter {
       }
     }
 
-    this._emit({
+    const snapshots = { written: 0, updated: 0, obsolete: 0 };
+    const written = [];
+    for (const result of this._snapshotResults ?? []) {
+      snapshots.written += result.added ?? 0;
+      snapshots.updated += result.updated ?? 0;
+      snapshots.obsolete += result.unchecked ?? 0;
+      if (result.added || result.updated || result.fileDeleted) {
+        written.push(result.filepath);
+      }
+    }
+    if (written.length > 0) {
+      this._emit({ type: "snapshots-written", files: written });
+    }
+
+    const event = {
       type: "run-finished",
       total,
       passed,
       failed,
       skipped,
       duration: Date.now() - (this._startTime || Date.now()),
-    });
+      snapshots,
+    };
+
+    // Coverage is reported after the run ends, and lens treats run-finished as
+    // the end of a run, so hold it back until onCoverage. The timer covers runs
+    // where coverage fails and onCoverage never fires.
+    if (this.ctx?.config?.coverage?.enabled) {
+      this._pendingRunFinished = event;
+      this._pendingTimer = setTimeout(() => this._flushRunFinished(), 30000);
+    } else {
+      this._emit(event);
+    }
+  }
+
+  onCoverage(coverage) {
+    if (coverage && typeof coverage.files === "function") {
+      const metric = (m) => ({ covered: m?.covered ?? 0, total: m?.total ?? 0 });
+      const files = coverage.files().map((file) => {
+        const fileCoverage = coverage.fileCoverageFor(file);
+        const summary = fileCoverage.toSummary();
+        return {
+          file,
+          lines: metric(summary.lines),
+          branches: metric(summary.branches),
+          functions: metric(summary.functions),
+          uncovered_lines: fileCoverage.getUncoveredLines().map(Number),
+        };
+      });
+      this._emit({ type: "coverage", files });
+    }
+    this._flushRunFinished();
+  }
+
+  _flushRunFinished() {
+    clearTimeout(this._pendingTimer);
+    if (this._pendingRunFinished) {
+      this._emit(this._pendingRunFinished);
+      this._pendingRunFinished = null;
+    }
   }
 
   _emit(event) {
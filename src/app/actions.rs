@@ -4,11 +4,17 @@ use arboard::Clipboard;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
 use crate::{
-    app::{App, Panel, PendingRun},
-    models::{NodeKind, TestStatus},
+    app::{
+        App, Panel, PendingRun, SnapshotUpdate, events::common_directory_prefix, search::Reveal,
+        source,
+    },
+    models::{NodeKind, Query, TestStatus, query::path_term},
 };
 
-#[derive(Debug)]
+/// Lines the runner log scrolls by a page.
+const RUNNER_LOG_PAGE: isize = 10;
+
+#[derive(Debug, Clone, PartialEq)]
 pub enum Action {
     Quit,
     FocusNext,
@@ -38,11 +44,35 @@ pub enum Action {
     FilterKey(KeyEvent),
     FilterExit,
     FilterApply,
+    OutputSearchKey(KeyEvent),
+    OutputSearchExit,
+    OutputSearchApply,
+    SearchNext,
+    SearchPrevious,
+    ToggleFlatResults,
     OpenInEditor,
+    OpenAttachment,
     YankPath,
     YankFailureLocation,
     YankOutput,
+    ToggleFileConsole,
     ToggleHelp,
+    ToggleFlaky,
+    ToggleRunnerLog,
+    RunnerLogScroll(isize),
+    FlakyUp,
+    FlakyDown,
+    FlakyReveal,
+    UpdateSnapshots,
+    ConfirmUpdateSnapshots,
+    CancelUpdateSnapshots,
+    ToggleCoverage,
+    GrowTests,
+    ShrinkTests,
+    GrowTree,
+    ShrinkTree,
+    ToggleZoom,
+    ToggleStacking,
 }
 
 /// Process a keyboard action.
@@ -343,6 +373,18 @@ pub fn handle_action(app: &mut App, action: Action) {
         }
 
         Action::RunFiltered => {
+            let query = Query::parse(app.filter.value());
+            if !query.is_empty() {
+                let runs = filtered_runs(app, &query);
+                if runs.is_empty() {
+                    app.notifier.info("No tests match the filter", 3);
+                    return;
+                }
+                app.pending_runs.extend(runs);
+                app.running = true;
+                app.progress_done = 0;
+                return;
+            }
             let visible = app.visible_tree_nodes();
             let file_ids: Vec<usize> = visible
                 .into_iter()
@@ -386,15 +428,21 @@ pub fn handle_action(app: &mut App, action: Action) {
             app.watch_mode = !app.watch_mode;
         }
 
+        // In the output panel `/` searches its text instead of filtering the tree.
+        Action::FilterEnter if app.active_panel == Panel::Output => {
+            app.output_search.active = true;
+        }
+
         Action::FilterEnter => {
             app.filter_active = true;
         }
 
         Action::FilterByFile => {
             if let Some(file_id) = find_file_node_for_selection(app)
-                && let Some(node) = app.tree.get(file_id)
+                && let Some(path) = app.tree.get(file_id).and_then(|n| n.path.as_deref())
             {
-                app.filter = tui_input::Input::from(node.name.clone());
+                let term = path_term(&path.to_string_lossy());
+                app.filter = tui_input::Input::from(term);
                 app.filter_active = false;
                 reposition_cursor(app, Some(file_id));
             }
@@ -408,7 +456,7 @@ pub fn handle_action(app: &mut App, action: Action) {
             let dir = if let Some(node_id) = selected_id {
                 let node = app.tree.get(node_id);
                 match node.map(|n| n.kind) {
-                    Some(NodeKind::Project) => node.map(|n| n.name.clone()).unwrap_or_default(),
+                    Some(NodeKind::Project) => project_path_term(app, node_id),
                     Some(NodeKind::Workspace) => String::new(),
                     _ => {
                         // File / Test / Suite: walk up to nearest Project ancestor.
@@ -418,7 +466,7 @@ pub fn handle_action(app: &mut App, action: Action) {
                         while let Some(pid) = cur {
                             if let Some(p) = app.tree.get(pid) {
                                 if p.kind == NodeKind::Project {
-                                    found = p.name.clone();
+                                    found = project_path_term(app, pid);
                                     break;
                                 }
                                 cur = p.parent;
@@ -461,6 +509,51 @@ pub fn handle_action(app: &mut App, action: Action) {
             app.filter_active = false;
         }
 
+        Action::OutputSearchKey(key) => {
+            use tui_input::backend::crossterm::EventHandler;
+            app.output_search
+                .input
+                .handle_event(&crossterm::event::Event::Key(key));
+            app.output_search.reveal = Some(Reveal::FromTop);
+        }
+
+        Action::OutputSearchExit => app.output_search.clear(),
+
+        Action::OutputSearchApply => {
+            app.output_search.active = false;
+        }
+
+        Action::SearchNext => app.output_search.step(true),
+        Action::SearchPrevious => app.output_search.step(false),
+
+        Action::GrowTests => app.layout.resize_tests(true),
+        Action::ShrinkTests => app.layout.resize_tests(false),
+        Action::GrowTree => app.layout.resize_tree(true),
+        Action::ShrinkTree => app.layout.resize_tree(false),
+
+        Action::ToggleZoom => {
+            app.layout.zoomed = !app.layout.zoomed;
+            if app.layout.zoomed {
+                app.active_panel = Panel::Output;
+            }
+        }
+
+        Action::ToggleStacking => app.layout.toggle_stacking(app.areas.screen.width),
+
+        Action::ToggleFileConsole => {
+            app.show_file_console = !app.show_file_console;
+            app.output_scroll_offset = 0;
+        }
+
+        Action::ToggleFlatResults => {
+            let current_id = app
+                .visible_tree_nodes()
+                .get(app.selected_tree_index)
+                .map(|&(id, _)| id);
+            app.filter_flat = !app.filter_flat;
+            reposition_cursor(app, current_id);
+        }
+
         Action::OpenInEditor => {
             if let Some(node_id) = app.selected_node_id() {
                 let node = app.tree.get(node_id);
@@ -468,7 +561,7 @@ pub fn handle_action(app: &mut App, action: Action) {
                 // For failed tests, open at failure location; otherwise at definition
                 let (line, col) = node
                     .and_then(|n| n.result.as_ref())
-                    .and_then(|r| r.failure.as_ref())
+                    .and_then(|r| r.failure())
                     .and_then(|f| f.stack_trace.as_ref())
                     .and_then(|st| parse_line_col_from_stack(st))
                     .or_else(|| node.and_then(|n| n.location.map(|(l, c)| (Some(l), Some(c)))))
@@ -491,6 +584,43 @@ pub fn handle_action(app: &mut App, action: Action) {
             }
         }
 
+        Action::OpenAttachment => {
+            let Some(node_id) = app.selected_node_id() else {
+                return;
+            };
+            let attachments = app
+                .tree
+                .get(node_id)
+                .and_then(|n| n.result.as_ref())
+                .and_then(|r| r.failure())
+                .map(|f| f.attachments.as_slice())
+                .unwrap_or_default();
+            if attachments.is_empty() {
+                app.notifier.info("No attachments for this test", 2);
+                return;
+            }
+            let index = match app.last_attachment {
+                Some((id, last)) if id == node_id => (last + 1) % attachments.len(),
+                _ => 0,
+            };
+            let attachment = &attachments[index];
+            match crate::editor::open_attachment(attachment, &app.workspace) {
+                Ok(()) => app.notifier.info(
+                    format!(
+                        "Opened {} ({}/{})",
+                        attachment.name,
+                        index + 1,
+                        attachments.len()
+                    ),
+                    2,
+                ),
+                Err(e) => app
+                    .notifier
+                    .error(format!("Could not open {}: {e}", attachment.name)),
+            }
+            app.last_attachment = Some((node_id, index));
+        }
+
         Action::YankPath => {
             if let Some(node_id) = app.selected_node_id() {
                 // Capture the selected node's kind and location before walking up.
@@ -541,6 +671,79 @@ pub fn handle_action(app: &mut App, action: Action) {
             app.show_help = !app.show_help;
         }
 
+        Action::ToggleFlaky => {
+            app.show_flaky = !app.show_flaky;
+            app.flaky_selected = 0;
+        }
+
+        Action::ToggleRunnerLog => {
+            app.show_runner_log = !app.show_runner_log;
+            app.runner_log_scroll = 0;
+        }
+
+        // Positive scrolls back towards older lines; the offset counts lines up
+        // from the newest, which stays in view as lines arrive while it's 0.
+        Action::RunnerLogScroll(lines) => {
+            let max = app.runner_log.len().saturating_sub(1);
+            app.runner_log_scroll = app.runner_log_scroll.saturating_add_signed(lines).min(max);
+        }
+
+        Action::FlakyUp => {
+            app.flaky_selected = app.flaky_selected.saturating_sub(1);
+        }
+
+        Action::FlakyDown => {
+            let count = app.history.flaky_tests().len();
+            if app.flaky_selected + 1 < count {
+                app.flaky_selected += 1;
+            }
+        }
+
+        Action::FlakyReveal => {
+            let key = app
+                .history
+                .flaky_tests()
+                .get(app.flaky_selected)
+                .map(|(key, _)| key.to_string());
+            app.show_flaky = false;
+            match key.and_then(|key| find_node_by_test_key(&app.tree, &key)) {
+                Some(id) => reveal_node(app, id),
+                None => app.notifier.info("Test is not in the current tree", 2),
+            }
+        }
+
+        Action::UpdateSnapshots => {
+            app.confirm_snapshot_update = app
+                .selected_node_id()
+                .and_then(|id| snapshot_update(app, id));
+        }
+
+        Action::ConfirmUpdateSnapshots => {
+            if let Some(update) = app.confirm_snapshot_update.take() {
+                set_running_status(app, update.node_id);
+                app.pending_runs.push(PendingRun::UpdateSnapshots {
+                    files: update.files,
+                    name: update.name,
+                });
+                app.running = true;
+                app.progress_done = 0;
+            }
+        }
+
+        Action::CancelUpdateSnapshots => {
+            app.confirm_snapshot_update = None;
+        }
+
+        Action::ToggleCoverage => {
+            app.coverage_enabled = !app.coverage_enabled;
+            if app.coverage_enabled {
+                app.notifier
+                    .info("Coverage on — collected from the next run", 2);
+            } else {
+                app.notifier.info("Coverage off", 1);
+            }
+        }
+
         Action::YankFailureLocation => {
             if let Some(node_id) = app.selected_node_id() {
                 let node = app.tree.get(node_id);
@@ -548,7 +751,7 @@ pub fn handle_action(app: &mut App, action: Action) {
                 // Prefer the failure stack-trace location; fall back to the test definition.
                 let (line, col) = node
                     .and_then(|n| n.result.as_ref())
-                    .and_then(|r| r.failure.as_ref())
+                    .and_then(|r| r.failure())
                     .and_then(|f| f.stack_trace.as_ref())
                     .and_then(|st| parse_line_col_from_stack(st))
                     .or_else(|| node.and_then(|n| n.location.map(|(l, c)| (Some(l), Some(c)))))
@@ -578,67 +781,68 @@ pub fn handle_action(app: &mut App, action: Action) {
     }
 }
 
-pub fn trigger_action(key: KeyEvent, filter_active: bool, show_help: bool) -> Option<Action> {
+/// Map a key press to an action, given which overlay or input has the keyboard.
+pub fn trigger_action(app: &mut App, key: KeyEvent) -> Option<Action> {
     // Any key while the help overlay is open just closes it.
-    if show_help {
+    if app.show_help {
         return Some(Action::ToggleHelp);
     }
 
-    if filter_active {
+    if app.confirm_snapshot_update.is_some() {
+        return match key.code {
+            KeyCode::Char('y') | KeyCode::Enter => Some(Action::ConfirmUpdateSnapshots),
+            _ => Some(Action::CancelUpdateSnapshots),
+        };
+    }
+
+    if app.show_flaky {
+        return match key.code {
+            KeyCode::Up | KeyCode::Char('k') => Some(Action::FlakyUp),
+            KeyCode::Down | KeyCode::Char('j') => Some(Action::FlakyDown),
+            KeyCode::Enter => Some(Action::FlakyReveal),
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('!') => Some(Action::ToggleFlaky),
+            _ => None,
+        };
+    }
+
+    if app.show_runner_log {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        return match key.code {
+            KeyCode::Up | KeyCode::Char('k') => Some(Action::RunnerLogScroll(1)),
+            KeyCode::Down | KeyCode::Char('j') => Some(Action::RunnerLogScroll(-1)),
+            KeyCode::PageUp => Some(Action::RunnerLogScroll(RUNNER_LOG_PAGE)),
+            KeyCode::PageDown => Some(Action::RunnerLogScroll(-RUNNER_LOG_PAGE)),
+            KeyCode::Char('u') if ctrl => Some(Action::RunnerLogScroll(RUNNER_LOG_PAGE)),
+            KeyCode::Char('d') if ctrl => Some(Action::RunnerLogScroll(-RUNNER_LOG_PAGE)),
+            KeyCode::Home | KeyCode::Char('g') => Some(Action::RunnerLogScroll(isize::MAX)),
+            KeyCode::End | KeyCode::Char('G') => Some(Action::RunnerLogScroll(isize::MIN)),
+            KeyCode::Esc | KeyCode::Char('q') => Some(Action::ToggleRunnerLog),
+            _ => match app.keymap.resolve(key) {
+                Some(Action::ToggleRunnerLog) => Some(Action::ToggleRunnerLog),
+                _ => None,
+            },
+        };
+    }
+
+    if app.output_search.active {
+        return match key.code {
+            KeyCode::Esc => Some(Action::OutputSearchExit),
+            KeyCode::Enter => Some(Action::OutputSearchApply),
+            _ => Some(Action::OutputSearchKey(key)),
+        };
+    }
+
+    if app.filter_active {
         match key.code {
             KeyCode::Esc => Some(Action::FilterExit),
             KeyCode::Enter => Some(Action::FilterApply),
+            KeyCode::Tab => Some(Action::ToggleFlatResults),
             KeyCode::Up => Some(Action::NavigateUp),
             KeyCode::Down => Some(Action::NavigateDown),
             _ => Some(Action::FilterKey(key)),
         }
     } else {
-        map_key(key)
-    }
-}
-
-fn map_key(key: KeyEvent) -> Option<Action> {
-    if key.modifiers.contains(KeyModifiers::CONTROL) {
-        return match key.code {
-            KeyCode::Char('c') => Some(Action::Quit),
-            KeyCode::Char('u') => Some(Action::ScrollUp),
-            KeyCode::Char('d') => Some(Action::ScrollDown),
-            _ => None,
-        };
-    }
-
-    match key.code {
-        KeyCode::Char('q') => Some(Action::Quit),
-        KeyCode::Tab => Some(Action::FocusNext),
-        KeyCode::BackTab => Some(Action::FocusPrevious),
-        KeyCode::Up | KeyCode::Char('k') => Some(Action::NavigateUp),
-        KeyCode::Down | KeyCode::Char('j') => Some(Action::NavigateDown),
-        KeyCode::Right | KeyCode::Char('l') => Some(Action::Expand),
-        KeyCode::Char('L') => Some(Action::ExpandAll),
-        KeyCode::Left | KeyCode::Char('h') => Some(Action::Collapse),
-        KeyCode::Char('H') => Some(Action::CollapseAll),
-        KeyCode::Char('g') | KeyCode::Home => Some(Action::JumpToStart),
-        KeyCode::Char('G') | KeyCode::End => Some(Action::JumpToEnd),
-        KeyCode::Char('{') => Some(Action::JumpToPrevFile),
-        KeyCode::Char('}') => Some(Action::JumpToNextFile),
-        KeyCode::Char('[') => Some(Action::JumpToPrevError),
-        KeyCode::Char(']') => Some(Action::JumpToNextError),
-        KeyCode::Enter => Some(Action::Select),
-        KeyCode::Char('a') => Some(Action::RunFiltered),
-        KeyCode::Char('A') => Some(Action::RunAll),
-        KeyCode::Char('r') => Some(Action::RerunFailed),
-        KeyCode::Char('w') => Some(Action::ToggleWatch),
-        KeyCode::Char('/') => Some(Action::FilterEnter),
-        KeyCode::Char('f') => Some(Action::FilterByFile),
-        KeyCode::Char('F') => Some(Action::FilterByDir),
-        KeyCode::Char('e') => Some(Action::OpenInEditor),
-        KeyCode::PageUp => Some(Action::ScrollUp),
-        KeyCode::PageDown => Some(Action::ScrollDown),
-        KeyCode::Char('y') => Some(Action::YankPath),
-        KeyCode::Char('Y') => Some(Action::YankFailureLocation),
-        KeyCode::Char('c') => Some(Action::YankOutput),
-        KeyCode::Char('?') => Some(Action::ToggleHelp),
-        _ => None,
+        app.keymap.resolve(key)
     }
 }
 
@@ -681,27 +885,10 @@ fn build_failure_yank_string(file_name: &str, line: Option<u32>, col: Option<u32
     }
 }
 
-/// Extract line and column from the first frame of a stack trace.
+/// Extract line and column from the first user frame of a stack trace.
 /// Matches patterns like `(file.ts:123:45)` or `file.ts:123:45` or `file.ts:123`.
 fn parse_line_col_from_stack(stack: &str) -> Option<(Option<u32>, Option<u32>)> {
-    for segment in stack.split_whitespace() {
-        let s = segment.trim_matches(|c| c == '(' || c == ')');
-        let parts: Vec<&str> = s.rsplitn(3, ':').collect();
-
-        if parts.len() == 3 {
-            let col = parts[0].parse::<u32>().ok();
-            let line = parts[1].parse::<u32>().ok();
-            if line.is_some() {
-                return Some((line, col));
-            }
-        } else if parts.len() == 2 {
-            let line = parts[0].parse::<u32>().ok();
-            if line.is_some() {
-                return Some((line, None));
-            }
-        }
-    }
-    None
+    source::first_user_frame(stack).map(|frame| (Some(frame.line), frame.column))
 }
 
 /// Build a plain-text copy of the output panel content for the selected node.
@@ -726,55 +913,51 @@ fn build_output_plain_text(app: &App) -> Option<String> {
     out.push_str(&crumbs.join(" > "));
 
     if node.kind == NodeKind::Test {
-        if let Some(ref result) = node.result
-            && let Some(ref failure) = result.failure
-        {
-            out.push_str("\n\n");
-            out.push_str(&failure.message);
-            if let (Some(e), Some(a)) = (&failure.expected, &failure.actual) {
-                out.push_str(&format!("\n\nExpected: {}\nActual:   {}", e, a));
-            }
-            if let Some(ref diff) = failure.diff {
-                out.push('\n');
-                out.push_str(diff);
-            }
-            if let Some(ref stack) = failure.stack_trace {
-                out.push('\n');
-                out.push_str(stack);
+        if let Some(ref result) = node.result {
+            for failure in &result.failures {
+                out.push_str("\n\n");
+                out.push_str(&failure.message);
+                if let (Some(e), Some(a)) = (&failure.expected, &failure.actual) {
+                    out.push_str(&format!("\n\nExpected: {}\nActual:   {}", e, a));
+                }
+                if let Some(ref diff) = failure.diff {
+                    out.push('\n');
+                    out.push_str(diff);
+                }
+                if let Some(ref stack) = failure.stack_trace {
+                    out.push('\n');
+                    out.push_str(stack);
+                }
             }
         }
     } else {
         for fid in output_failed_descendants(&app.tree, node_id) {
             if let Some(fnode) = app.tree.get(fid)
                 && let Some(ref result) = fnode.result
-                && let Some(ref failure) = result.failure
+                && !result.failures.is_empty()
             {
-                out.push_str(&format!("\n\n✗ {}\n", fnode.name));
-                out.push_str(&failure.message);
-                if let (Some(e), Some(a)) = (&failure.expected, &failure.actual) {
-                    out.push_str(&format!("\nExpected: {}\nActual:   {}", e, a));
-                }
-                if let Some(ref stack) = failure.stack_trace {
+                out.push_str(&format!("\n\n✗ {}", fnode.name));
+                for failure in &result.failures {
                     out.push('\n');
-                    out.push_str(stack);
+                    out.push_str(&failure.message);
+                    if let (Some(e), Some(a)) = (&failure.expected, &failure.actual) {
+                        out.push_str(&format!("\nExpected: {}\nActual:   {}", e, a));
+                    }
+                    if let Some(ref stack) = failure.stack_trace {
+                        out.push('\n');
+                        out.push_str(stack);
+                    }
                 }
             }
         }
     }
 
-    // Console output — walk up to the file node
-    let mut cur = Some(node_id);
-    while let Some(id) = cur {
-        if let Some(n) = app.tree.get(id) {
-            if n.kind == NodeKind::File && !n.console_output.is_empty() {
-                out.push_str("\n\n━━ Console Output ━━\n");
-                out.push_str(&n.console_output.join("\n"));
-                break;
-            }
-            cur = n.parent;
-        } else {
-            break;
-        }
+    // Console output, as the output panel shows it
+    let console_output = app.tree.console_output(node_id, app.show_file_console);
+    if !console_output.is_empty() {
+        out.push_str("\n\n━━ Console Output ━━\n");
+        let text: Vec<&str> = console_output.iter().map(|l| l.text.as_str()).collect();
+        out.push_str(&text.join("\n"));
     }
 
     if out.trim().is_empty() {
@@ -814,6 +997,32 @@ fn reposition_cursor(app: &mut App, preferred_id: Option<usize>) {
     app.adjust_tree_scroll();
 }
 
+/// Resolve a [`history::test_key`](super::history::test_key) to its test node.
+fn find_node_by_test_key(tree: &crate::models::TestTree, key: &str) -> Option<usize> {
+    let (file, name) = key.split_once("::")?;
+    let mut id = tree.find_file_by_path(std::path::Path::new(file))?;
+    for part in name.split(" > ") {
+        id = tree.find_child_by_name(id, part)?;
+    }
+    Some(id)
+}
+
+/// Clear the filter, expand every ancestor of `id` and move the tree cursor to it.
+fn reveal_node(app: &mut App, id: usize) {
+    let mut parent = app.tree.get(id).and_then(|n| n.parent);
+    while let Some(pid) = parent {
+        let Some(node) = app.tree.get_mut(pid) else {
+            break;
+        };
+        node.expanded = true;
+        parent = node.parent;
+    }
+    app.filter.reset();
+    app.filter_active = false;
+    app.active_panel = Panel::TestTree;
+    reposition_cursor(app, Some(id));
+}
+
 /// Collect all File-kind descendants of `id` into `out`.
 fn collect_file_descendants(tree: &crate::models::TestTree, id: usize, out: &mut Vec<usize>) {
     if let Some(node) = tree.get(id) {
@@ -829,6 +1038,128 @@ fn collect_file_descendants(tree: &crate::models::TestTree, id: usize, out: &mut
     }
 }
 
+fn collect_test_descendants(tree: &crate::models::TestTree, id: usize, out: &mut Vec<usize>) {
+    if let Some(node) = tree.get(id) {
+        for &child_id in &node.children {
+            match tree.get(child_id) {
+                Some(child) if child.deleted => {}
+                Some(child) if child.kind == NodeKind::Test => out.push(child_id),
+                Some(_) => collect_test_descendants(tree, child_id, out),
+                None => {}
+            }
+        }
+    }
+}
+
+/// The runs covering exactly the tests matched by `query`, marking them running.
+///
+/// Files whose every test matched run whole; otherwise each file's matched tests
+/// run by name, in one run per set of names so a name only runs in the files it
+/// matched in. Files that haven't run yet have no known tests, so they run whole.
+fn filtered_runs(app: &mut App, query: &Query) -> Vec<PendingRun> {
+    let matches = app.tree.query_matches(query);
+    let mut file_ids = Vec::new();
+    for &root_id in app.tree.root_ids() {
+        if app
+            .tree
+            .get(root_id)
+            .is_some_and(|n| n.kind == NodeKind::File)
+        {
+            file_ids.push(root_id);
+        } else {
+            collect_file_descendants(&app.tree, root_id, &mut file_ids);
+        }
+    }
+
+    let mut whole = Vec::new();
+    let mut partial: Vec<(Vec<PathBuf>, Vec<String>)> = Vec::new();
+    for file_id in file_ids {
+        if matches.contains(&file_id) {
+            set_running_status(app, file_id);
+            whole.push(resolve_file_path(app, file_id));
+            continue;
+        }
+        let mut tests = Vec::new();
+        collect_test_descendants(&app.tree, file_id, &mut tests);
+        let matched: Vec<usize> = tests
+            .iter()
+            .copied()
+            .filter(|id| matches.contains(id))
+            .collect();
+        if matched.is_empty() {
+            continue;
+        }
+        for &id in &matched {
+            set_running_status(app, id);
+        }
+        let file = resolve_file_path(app, file_id);
+        if matched.len() == tests.len() {
+            whole.push(file);
+            continue;
+        }
+        let names: Vec<String> = matched
+            .iter()
+            .filter_map(|&id| app.tree.file_and_full_name(id).map(|(_, name)| name))
+            .collect();
+        match partial.iter_mut().find(|(_, n)| *n == names) {
+            Some((files, _)) => files.push(file),
+            None => partial.push((vec![file], names)),
+        }
+    }
+
+    let mut runs: Vec<PendingRun> = partial
+        .into_iter()
+        .map(|(files, names)| PendingRun::Tests { files, names })
+        .collect();
+    if !whole.is_empty() {
+        runs.push(PendingRun::Files(whole));
+    }
+    runs
+}
+
+/// Describe a snapshot update for `node_id`: the whole file for file nodes, just
+/// the test or suite for test nodes, and every file below a project or workspace.
+fn snapshot_update(app: &App, node_id: usize) -> Option<SnapshotUpdate> {
+    let node = app.tree.get(node_id)?;
+    let (label, files, name) = match node.kind {
+        NodeKind::File => (
+            node.path
+                .as_ref()
+                .map(|p| p.to_string_lossy().to_string())
+                .unwrap_or_else(|| node.name.clone()),
+            vec![resolve_file_path(app, node_id)],
+            None,
+        ),
+        NodeKind::Test | NodeKind::Suite => {
+            let (file, name) = resolve_test_path(app, node_id);
+            (name.clone(), vec![file], Some(name))
+        }
+        NodeKind::Project | NodeKind::Workspace => {
+            let mut file_ids = Vec::new();
+            collect_file_descendants(&app.tree, node_id, &mut file_ids);
+            if file_ids.is_empty() {
+                return None;
+            }
+            let files = file_ids
+                .iter()
+                .map(|&id| resolve_file_path(app, id))
+                .collect();
+            let noun = if file_ids.len() == 1 { "file" } else { "files" };
+            (
+                format!("{} ({} {noun})", node.name, file_ids.len()),
+                files,
+                None,
+            )
+        }
+    };
+    Some(SnapshotUpdate {
+        node_id,
+        label,
+        files,
+        name,
+    })
+}
+
 /// Walk up from the currently selected node to its ancestor file node.
 fn find_file_node_for_selection(app: &App) -> Option<usize> {
     let node_id = app.selected_node_id()?;
@@ -843,6 +1174,21 @@ fn find_file_node_for_selection(app: &App) -> Option<usize> {
     None
 }
 
+/// A `path:` filter for the directory a project node stands for: the deepest
+/// directory holding all of its files.
+fn project_path_term(app: &App, project_id: usize) -> String {
+    let files: Vec<String> = app
+        .tree
+        .subtree_file_paths(project_id)
+        .iter()
+        .map(|p| p.to_string_lossy().into_owned())
+        .collect();
+    match common_directory_prefix(&files) {
+        dir if dir.is_empty() => String::new(),
+        dir => path_term(&dir),
+    }
+}
+
 /// Resolve a file node's path to an absolute path.
 fn resolve_file_path(app: &App, node_id: usize) -> PathBuf {
     if let Some(node) = app.tree.get(node_id) {
@@ -857,12 +1203,13 @@ fn resolve_file_path(app: &App, node_id: usize) -> PathBuf {
     app.workspace.clone()
 }
 
-/// Walk up from a test/suite node to find the file path and the node's own name.
+/// Walk up from a test/suite node to find the file path and the node's full
+/// name below it, which is what the runners filter on.
 fn resolve_test_path(app: &App, node_id: usize) -> (PathBuf, String) {
     let test_name = app
         .tree
-        .get(node_id)
-        .map(|n| n.name.clone())
+        .file_and_full_name(node_id)
+        .map(|(_, name)| name)
         .unwrap_or_default();
 
     let mut current = Some(node_id);
@@ -889,6 +1236,7 @@ fn resolve_test_path(app: &App, node_id: usize) -> (PathBuf, String) {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::path::Path;
 
     #[test]
     fn test_parse_line_col_standard_frame() {
@@ -1023,4 +1371,317 @@ mod tests {
             "src/foo.test.ts"
         );
     }
+
+    // --- snapshot updates ---
+
+    fn app_with_file_selected() -> App {
+        let (mut app, _rx) = App::new(PathBuf::from("/ws"));
+        crate::app::handle_test_event(
+            &mut app,
+            crate::app::TestEvent::DiscoveryComplete {
+                files: vec!["src/a.test.ts".into(), "src/b.test.ts".into()],
+                projects: Vec::new(),
+            },
+        );
+        app.tree.expand_all();
+        let file_id = app
+            .tree
+            .find_file_by_path(Path::new("src/a.test.ts"))
+            .unwrap();
+        app.selected_tree_index = app
+            .visible_tree_nodes()
+            .iter()
+            .position(|&(id, _)| id == file_id)
+            .unwrap();
+        app
+    }
+
+    #[test]
+    fn update_snapshots_waits_for_confirmation() {
+        let mut app = app_with_file_selected();
+        handle_action(&mut app, Action::UpdateSnapshots);
+        assert!(app.pending_runs.is_empty());
+        assert_eq!(
+            app.confirm_snapshot_update
+                .as_ref()
+                .map(|u| u.label.as_str()),
+            Some("src/a.test.ts")
+        );
+
+        handle_action(&mut app, Action::ConfirmUpdateSnapshots);
+        assert!(app.confirm_snapshot_update.is_none());
+        assert!(matches!(
+            app.pending_runs.as_slice(),
+            [PendingRun::UpdateSnapshots { files, name: None }]
+                if files == &[PathBuf::from("/ws/src/a.test.ts")]
+        ));
+    }
+
+    #[test]
+    fn cancelled_snapshot_update_runs_nothing() {
+        let mut app = app_with_file_selected();
+        handle_action(&mut app, Action::UpdateSnapshots);
+        handle_action(&mut app, Action::CancelUpdateSnapshots);
+        assert!(app.confirm_snapshot_update.is_none());
+        assert!(app.pending_runs.is_empty());
+    }
+
+    #[test]
+    fn snapshot_update_for_a_project_covers_every_file() {
+        let mut app = app_with_file_selected();
+        app.selected_tree_index = 0;
+        handle_action(&mut app, Action::UpdateSnapshots);
+        let update = app.confirm_snapshot_update.unwrap();
+        assert_eq!(update.files.len(), 2);
+        assert_eq!(update.name, None);
+    }
+
+    #[test]
+    fn slash_searches_the_output_when_it_is_focused() {
+        let (mut app, _rx) = App::new(PathBuf::from("/ws"));
+        app.active_panel = Panel::Output;
+        handle_action(&mut app, Action::FilterEnter);
+        assert!(app.output_search.active);
+        assert!(!app.filter_active);
+
+        let key = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE);
+        let action = trigger_action(&mut app, key('n'));
+        assert_eq!(action, Some(Action::OutputSearchKey(key('n'))));
+        handle_action(&mut app, action.unwrap());
+        handle_action(&mut app, Action::OutputSearchApply);
+        assert_eq!(app.output_search.query(), "n");
+        assert!(!app.output_search.active);
+
+        handle_action(&mut app, Action::OutputSearchExit);
+        assert_eq!(app.output_search.query(), "");
+    }
+
+    #[test]
+    fn confirmation_prompt_accepts_only_y_or_enter() {
+        let key = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE);
+        let (mut app, _rx) = App::new(PathBuf::from("/ws"));
+        app.confirm_snapshot_update = Some(SnapshotUpdate {
+            node_id: 0,
+            label: "a.test.ts".into(),
+            files: vec![],
+            name: None,
+        });
+        assert!(matches!(
+            trigger_action(&mut app, key('y')),
+            Some(Action::ConfirmUpdateSnapshots)
+        ));
+        assert!(matches!(
+            trigger_action(&mut app, key('q')),
+            Some(Action::CancelUpdateSnapshots)
+        ));
+    }
+
+    fn app_with_results() -> App {
+        let mut app = app_with_file_selected();
+        for (name, status) in [
+            ("math > adds", TestStatus::Failed),
+            ("math > subtracts", TestStatus::Passed),
+        ] {
+            crate::app::handle_test_event(
+                &mut app,
+                crate::app::TestEvent::TestFinished {
+                    file: "/ws/src/a.test.ts".into(),
+                    name: name.into(),
+                    result: Box::new(crate::models::TestResult {
+                        status,
+                        duration_ms: Some(1),
+                        failures: vec![],
+                    }),
+                    location: None,
+                },
+            );
+        }
+        app
+    }
+
+    #[test]
+    fn selecting_a_nested_test_runs_it_by_its_full_name() {
+        let (mut app, _rx) = App::new(PathBuf::from("/ws"));
+        crate::app::handle_test_event(
+            &mut app,
+            crate::app::TestEvent::DiscoveryComplete {
+                files: vec!["e2e/login.spec.ts".into()],
+                projects: Vec::new(),
+            },
+        );
+        crate::app::handle_test_event(
+            &mut app,
+            crate::app::TestEvent::TestFinished {
+                file: "/ws/e2e/login.spec.ts".into(),
+                name: "chromium > login > signs in".into(),
+                result: Box::new(crate::models::TestResult {
+                    status: TestStatus::Passed,
+                    duration_ms: Some(1),
+                    failures: vec![],
+                }),
+                location: None,
+            },
+        );
+        app.tree.expand_all();
+        app.selected_tree_index = app
+            .visible_tree_nodes()
+            .iter()
+            .position(|&(id, _)| app.tree.get(id).is_some_and(|n| n.name == "signs in"))
+            .unwrap();
+
+        handle_action(&mut app, Action::Select);
+        assert!(matches!(
+            app.pending_runs.as_slice(),
+            [PendingRun::Test { file, name }]
+                if file == Path::new("/ws/e2e/login.spec.ts")
+                    && name == "chromium > login > signs in"
+        ));
+    }
+
+    #[test]
+    fn filter_by_file_and_dir_isolate_similarly_named_files() {
+        let (mut app, _rx) = App::new(PathBuf::from("/ws"));
+        crate::app::handle_test_event(
+            &mut app,
+            crate::app::TestEvent::DiscoveryComplete {
+                files: vec![
+                    "src/a.test.ts".into(),
+                    "src/auth/a.test.ts".into(),
+                    "src/auth/login.test.ts".into(),
+                    "src/authz/a.test.ts".into(),
+                ],
+                projects: Vec::new(),
+            },
+        );
+        app.tree.expand_all();
+        let select = |app: &mut App, path: &str| {
+            let id = app.tree.find_file_by_path(Path::new(path)).unwrap();
+            app.selected_tree_index = app
+                .visible_tree_nodes()
+                .iter()
+                .position(|&(node, _)| node == id)
+                .unwrap();
+        };
+        let visible_files = |app: &App| -> Vec<String> {
+            app.visible_tree_nodes()
+                .iter()
+                .filter_map(|&(id, _)| app.tree.get(id)?.path.as_ref())
+                .map(|p| p.to_string_lossy().into_owned())
+                .collect()
+        };
+
+        select(&mut app, "src/a.test.ts");
+        handle_action(&mut app, Action::FilterByFile);
+        assert_eq!(app.filter.value(), "path:src/a.test.ts");
+        assert_eq!(visible_files(&app), ["src/a.test.ts"]);
+
+        app.filter.reset();
+        select(&mut app, "src/auth/login.test.ts");
+        handle_action(&mut app, Action::FilterByDir);
+        assert_eq!(app.filter.value(), "path:src/auth");
+        assert_eq!(
+            visible_files(&app),
+            ["src/auth/a.test.ts", "src/auth/login.test.ts"]
+        );
+    }
+
+    #[test]
+    fn run_filtered_runs_only_matched_tests() {
+        let mut app = app_with_results();
+        app.filter = tui_input::Input::from("status:failed".to_string());
+        handle_action(&mut app, Action::RunFiltered);
+        match app.pending_runs.as_slice() {
+            [PendingRun::Tests { files, names }] => {
+                assert_eq!(files, &[PathBuf::from("/ws/src/a.test.ts")]);
+                assert_eq!(names, &["math > adds".to_string()]);
+            }
+            other => panic!("unexpected runs: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn run_filtered_runs_a_shared_name_only_where_it_matched() {
+        let mut app = app_with_results();
+        for (name, status) in [
+            ("math > adds", TestStatus::Passed),
+            ("math > divides", TestStatus::Failed),
+        ] {
+            crate::app::handle_test_event(
+                &mut app,
+                crate::app::TestEvent::TestFinished {
+                    file: "/ws/src/b.test.ts".into(),
+                    name: name.into(),
+                    result: Box::new(crate::models::TestResult {
+                        status,
+                        duration_ms: Some(1),
+                        failures: vec![],
+                    }),
+                    location: None,
+                },
+            );
+        }
+        app.filter = tui_input::Input::from("status:failed".to_string());
+        handle_action(&mut app, Action::RunFiltered);
+        match app.pending_runs.as_slice() {
+            [
+                PendingRun::Tests {
+                    files: a_files,
+                    names: a_names,
+                },
+                PendingRun::Tests {
+                    files: b_files,
+                    names: b_names,
+                },
+            ] => {
+                assert_eq!(a_files, &[PathBuf::from("/ws/src/a.test.ts")]);
+                assert_eq!(a_names, &["math > adds".to_string()]);
+                assert_eq!(b_files, &[PathBuf::from("/ws/src/b.test.ts")]);
+                assert_eq!(b_names, &["math > divides".to_string()]);
+            }
+            other => panic!("unexpected runs: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn run_filtered_runs_whole_files_when_every_test_matches() {
+        let mut app = app_with_results();
+        app.filter = tui_input::Input::from("math".to_string());
+        handle_action(&mut app, Action::RunFiltered);
+        assert!(matches!(
+            app.pending_runs.as_slice(),
+            [PendingRun::Files(files)] if files == &[PathBuf::from("/ws/src/a.test.ts")]
+        ));
+
+        app.pending_runs.clear();
+        app.filter = tui_input::Input::from("-math".to_string());
+        handle_action(&mut app, Action::RunFiltered);
+        assert!(matches!(
+            app.pending_runs.as_slice(),
+            [PendingRun::Files(files)] if files == &[PathBuf::from("/ws/src/b.test.ts")]
+        ));
+    }
+
+    #[test]
+    fn run_filtered_with_no_matches_runs_nothing() {
+        let mut app = app_with_results();
+        app.filter = tui_input::Input::from("nothing-matches".to_string());
+        handle_action(&mut app, Action::RunFiltered);
+        assert!(app.pending_runs.is_empty());
+        assert!(!app.running);
+    }
+
+    #[test]
+    fn flat_results_list_matches_without_ancestors() {
+        let mut app = app_with_results();
+        app.filter = tui_input::Input::from("adds".to_string());
+        let tree_len = app.visible_tree_nodes().len();
+        handle_action(&mut app, Action::ToggleFlatResults);
+        let flat = app.visible_tree_nodes();
+        assert!(flat.len() < tree_len);
+        assert!(flat.iter().all(|&(_, depth)| depth == 0));
+        assert_eq!(
+            app.tree.file_and_full_name(flat[0].0).unwrap().1,
+            "math > adds"
+        );
+    }
 }
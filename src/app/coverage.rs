@@ -0,0 +1,215 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+/// Covered and total counts for one coverage metric.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+pub struct Metric {
+    pub covered: u32,
+    pub total: u32,
+}
+
+impl Metric {
+    /// Percentage covered. An empty metric (nothing to cover) counts as fully covered.
+    pub fn pct(&self) -> f64 {
+        if self.total == 0 {
+            100.0
+        } else {
+            self.covered as f64 * 100.0 / self.total as f64
+        }
+    }
+
+    fn add(&mut self, other: Metric) {
+        self.covered += other.covered;
+        self.total += other.total;
+    }
+}
+
+/// Coverage of one source file.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FileCoverage {
+    pub lines: Metric,
+    pub branches: Metric,
+    pub functions: Metric,
+    /// Uncovered lines collapsed into inclusive `(first, last)` ranges.
+    pub uncovered: Vec<(u32, u32)>,
+}
+
+/// Per-source-file coverage from the most recent coverage run, keyed by
+/// workspace-relative path.
+#[derive(Debug, Default)]
+pub struct Coverage {
+    files: HashMap<PathBuf, FileCoverage>,
+    /// Each file's directory and stem, for [`Coverage::source_for_test`].
+    /// When several extensions share them the lowest path wins.
+    by_stem: HashMap<(PathBuf, String), PathBuf>,
+}
+
+impl Coverage {
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// Replace the coverage of every file in `files`; files the run didn't
+    /// report keep their previous numbers.
+    pub fn merge(&mut self, files: Vec<(PathBuf, FileCoverage)>) {
+        for (path, coverage) in files {
+            if let Some(stem) = path.file_stem() {
+                let dir = path.parent().unwrap_or(Path::new("")).to_path_buf();
+                self.by_stem
+                    .entry((dir, stem.to_string_lossy().into_owned()))
+                    .and_modify(|lowest| {
+                        if path < *lowest {
+                            lowest.clone_from(&path);
+                        }
+                    })
+                    .or_insert_with(|| path.clone());
+            }
+            self.files.insert(path, coverage);
+        }
+    }
+
+    /// The source file a test file covers, found by dropping the `.test` /
+    /// `.spec` infix (`a.test.ts` → `a.ts`). Any extension matches, and tests
+    /// in a `__tests__` directory also look one level up.
+    pub fn source_for_test(&self, test_file: &Path) -> Option<(&Path, &FileCoverage)> {
+        let name = test_file.file_name()?.to_string_lossy();
+        let stem = [".test.", ".spec."]
+            .iter()
+            .find_map(|infix| name.split_once(infix).map(|(stem, _)| stem))?;
+
+        let mut dirs = vec![test_file.parent().unwrap_or(Path::new(""))];
+        if dirs[0].file_name().is_some_and(|d| d == "__tests__") {
+            dirs.push(dirs[0].parent().unwrap_or(Path::new("")));
+        }
+
+        dirs.iter().find_map(|dir| {
+            let path = self.by_stem.get(&(dir.to_path_buf(), stem.to_string()))?;
+            self.files
+                .get_key_value(path)
+                .map(|(path, coverage)| (path.as_path(), coverage))
+        })
+    }
+
+    /// Line coverage summed over the sources of `test_files`, each counted once.
+    pub fn lines_for_tests<'a>(
+        &self,
+        test_files: impl Iterator<Item = &'a Path>,
+    ) -> Option<Metric> {
+        let mut seen = Vec::new();
+        let mut total = Metric::default();
+        for test_file in test_files {
+            if let Some((source, coverage)) = self.source_for_test(test_file)
+                && !seen.contains(&source)
+            {
+                seen.push(source);
+                total.add(coverage.lines);
+            }
+        }
+        (!seen.is_empty()).then_some(total)
+    }
+}
+
+/// Collapse sorted or unsorted line numbers into inclusive ranges.
+pub fn line_ranges(lines: &[u32]) -> Vec<(u32, u32)> {
+    let mut lines = lines.to_vec();
+    lines.sort_unstable();
+    lines.dedup();
+    let mut ranges: Vec<(u32, u32)> = Vec::new();
+    for line in lines {
+        match ranges.last_mut() {
+            Some((_, last)) if *last + 1 == line => *last = line,
+            _ => ranges.push((line, line)),
+        }
+    }
+    ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn covered(lines: u32, total: u32) -> FileCoverage {
+        FileCoverage {
+            lines: Metric {
+                covered: lines,
+                total,
+            },
+            ..FileCoverage::default()
+        }
+    }
+
+    fn coverage(files: &[(&str, FileCoverage)]) -> Coverage {
+        let mut coverage = Coverage::default();
+        coverage.merge(
+            files
+                .iter()
+                .map(|(p, c)| (PathBuf::from(p), c.clone()))
+                .collect(),
+        );
+        coverage
+    }
+
+    #[test]
+    fn line_ranges_collapse_consecutive_lines() {
+        assert_eq!(line_ranges(&[7, 3, 4, 5, 10, 9]), [(3, 5), (7, 7), (9, 10)]);
+        assert!(line_ranges(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_file_maps_to_sibling_source() {
+        let coverage = coverage(&[
+            ("src/math.ts", covered(1, 2)),
+            ("src/other.ts", covered(0, 1)),
+        ]);
+        let (source, _) = coverage
+            .source_for_test(Path::new("src/math.test.ts"))
+            .unwrap();
+        assert_eq!(source, Path::new("src/math.ts"));
+    }
+
+    #[test]
+    fn tests_dir_maps_to_parent_source() {
+        let coverage = coverage(&[("src/math.tsx", covered(1, 2))]);
+        let (source, _) = coverage
+            .source_for_test(Path::new("src/__tests__/math.spec.ts"))
+            .unwrap();
+        assert_eq!(source, Path::new("src/math.tsx"));
+    }
+
+    #[test]
+    fn lowest_path_wins_across_merges() {
+        let mut coverage = coverage(&[("src/math.tsx", covered(1, 2))]);
+        coverage.merge(vec![("src/math.ts".into(), covered(2, 2))]);
+        coverage.merge(vec![("src/math.tsx".into(), covered(0, 2))]);
+        let (source, _) = coverage
+            .source_for_test(Path::new("src/math.test.ts"))
+            .unwrap();
+        assert_eq!(source, Path::new("src/math.ts"));
+    }
+
+    #[test]
+    fn non_test_file_has_no_source() {
+        let coverage = coverage(&[("src/math.ts", covered(1, 2))]);
+        assert!(coverage.source_for_test(Path::new("src/math.ts")).is_none());
+    }
+
+    #[test]
+    fn lines_for_tests_counts_each_source_once() {
+        let coverage = coverage(&[("a.ts", covered(1, 2)), ("b.ts", covered(3, 4))]);
+        let files = [
+            Path::new("a.test.ts"),
+            Path::new("a.spec.ts"),
+            Path::new("b.test.ts"),
+        ];
+        let metric = coverage.lines_for_tests(files.into_iter()).unwrap();
+        assert_eq!(
+            metric,
+            Metric {
+                covered: 4,
+                total: 6
+            }
+        );
+    }
+}
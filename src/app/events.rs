@@ -1,8 +1,9 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
+use super::{coverage::FileCoverage, history, runner_log::LogKind, source};
 use crate::{
-    app::{App, WatchScope},
-    models::{NodeKind, RunSummary, TestResult, TestStatus},
+    app::{Action, App, WatchScope},
+    models::{ConsoleLine, ConsoleStream, NodeKind, RunSummary, TestResult, TestStatus},
 };
 
 /// Events streamed from test runner adapters into the app.
@@ -31,16 +32,45 @@ pub enum TestEvent {
     RunFinished {
         summary: RunSummary,
     },
+    /// The code version of a run, computed off the UI thread once it started.
+    CodeVersion {
+        run: u64,
+        version: Option<String>,
+    },
+    /// Snapshot files created, rewritten or deleted during the run.
+    SnapshotsWritten {
+        files: Vec<String>,
+    },
+    /// Coverage of each source file the run loaded.
+    Coverage {
+        files: Vec<(String, FileCoverage)>,
+    },
+    /// A runner process was spawned with this command line.
+    CommandStarted {
+        command: String,
+        cwd: PathBuf,
+    },
     Output {
         line: String,
     },
+    /// Code frames for a finished test's errors, by index, read off the UI
+    /// thread. `file` is relative to the workspace.
+    SourceSnippets {
+        file: String,
+        name: String,
+        snippets: Vec<(usize, String)>,
+    },
     SuiteLocation {
         file: String,
         name: String,
         location: (u32, u32),
     },
+    /// Console output from a test file, attributed to the test (by full
+    /// name) that was running when it was logged, if the runner knows it.
     ConsoleLog {
         file: String,
+        test: Option<String>,
+        stream: ConsoleStream,
         content: String,
     },
     Error {
@@ -51,6 +81,10 @@ pub enum TestEvent {
     /// Test file discovery completed.
     DiscoveryComplete {
         files: Vec<String>,
+        /// Named projects as `(directory, name)`, directories relative to the
+        /// workspace. Files under one are listed under its name instead of
+        /// the directories leading to it.
+        projects: Vec<(String, String)>,
     },
     /// Test file discovery failed (e.g. glob error, Nx project not found).
     DiscoveryFailed {
@@ -58,17 +92,36 @@ pub enum TestEvent {
     },
 }
 
+fn history_failed(app: &mut App, e: std::io::Error) {
+    app.notifier
+        .error(format!("Run history disabled: could not write: {e}"));
+}
+
 /// Process a test event from a runner.
 pub fn handle_test_event(app: &mut App, event: TestEvent) {
     match event {
         TestEvent::RunStarted => {
             if app.full_run {
                 app.tree.reset();
-                app.output_lines.clear();
             }
             app.progress_total = 0;
             app.progress_done = 0;
             app.running = true;
+            app.summary_restored = false;
+            match app.history.begin_run() {
+                Ok(Some(request)) => {
+                    let tx = app.event_tx.clone();
+                    tokio::task::spawn_blocking(move || {
+                        let version = history::code_version(&request.workspace);
+                        let _ = tx.send(TestEvent::CodeVersion {
+                            run: request.run,
+                            version,
+                        });
+                    });
+                }
+                Ok(None) => {}
+                Err(e) => history_failed(app, e),
+            }
             // For manual runs run_start is set in main before the runner spawns,
             // preserving warmup time. For watch re-runs it won't be set, so we fall back here.
             app.run_start.get_or_insert_with(std::time::Instant::now);
@@ -79,17 +132,15 @@ pub fn handle_test_event(app: &mut App, event: TestEvent) {
         }
 
         TestEvent::FileStarted { path } => {
-            let file_name = file_display_name(app, &path);
-            let file_id = find_or_create_file_node(app, &file_name, &path);
-            if let Some(node) = app.tree.get_mut(file_id) {
-                node.console_output.clear();
-            }
+            let relative = file_display_name(app, &path);
+            let file_id = find_or_create_file_node(app, &relative);
+            app.tree.clear_console(file_id);
             app.tree.mark_children_stale(file_id);
         }
 
         TestEvent::TestStarted { file, name } => {
-            let file_name = file_display_name(app, &file);
-            let file_id = find_or_create_file_node(app, &file_name, &file);
+            let relative = file_display_name(app, &file);
+            let file_id = find_or_create_file_node(app, &relative);
             let test_id = find_or_create_test_node(app, file_id, &name);
             if let Some(node) = app.tree.get_mut(test_id) {
                 node.status = TestStatus::Running;
@@ -103,8 +154,8 @@ pub fn handle_test_event(app: &mut App, event: TestEvent) {
             location,
         } => {
             app.progress_done += 1;
-            let file_name = file_display_name(app, &file);
-            let file_id = find_or_create_file_node(app, &file_name, &file);
+            let relative = file_display_name(app, &file);
+            let file_id = find_or_create_file_node(app, &relative);
             let test_id = find_or_create_test_node(app, file_id, &name);
             // Don't overwrite a real result with "skipped" (happens with -t filtering)
             let dominated = result.status == TestStatus::Skipped
@@ -113,7 +164,28 @@ pub fn handle_test_event(app: &mut App, event: TestEvent) {
                     .get(test_id)
                     .is_some_and(|n| n.status.is_terminal());
             if !dominated {
-                app.tree.update_result(test_id, *result);
+                let result = *result;
+                let frames = source::snippet_frames(&app.workspace, &result);
+                if !frames.is_empty() {
+                    let tx = app.event_tx.clone();
+                    let (file, name) = (relative.clone(), name.clone());
+                    tokio::task::spawn_blocking(move || {
+                        let snippets = source::read_snippets(&frames);
+                        let _ = tx.send(TestEvent::SourceSnippets {
+                            file,
+                            name,
+                            snippets,
+                        });
+                    });
+                }
+                if let Err(e) = app.history.record(
+                    history::test_key(&relative, &name),
+                    result.status,
+                    result.duration_ms,
+                ) {
+                    history_failed(app, e);
+                }
+                app.tree.update_result(test_id, result);
             }
             if let Some(loc) = location
                 && let Some(node) = app.tree.get_mut(test_id)
@@ -122,13 +194,38 @@ pub fn handle_test_event(app: &mut App, event: TestEvent) {
             }
         }
 
+        TestEvent::SourceSnippets {
+            file,
+            name,
+            snippets,
+        } => {
+            let test_id = app
+                .tree
+                .find_file_by_path(Path::new(&file))
+                .and_then(|file_id| {
+                    name.split(" > ").try_fold(file_id, |parent, part| {
+                        app.tree.find_child_by_name(parent, part)
+                    })
+                });
+            if let Some(result) = test_id
+                .and_then(|id| app.tree.get_mut(id))
+                .and_then(|node| node.result.as_mut())
+            {
+                for (i, snippet) in snippets {
+                    if let Some(failure) = result.failures.get_mut(i) {
+                        failure.source_snippet.get_or_insert(snippet);
+                    }
+                }
+            }
+        }
+
         TestEvent::SuiteLocation {
             file,
             name,
             location,
         } => {
-            let file_name = file_display_name(app, &file);
-            let file_id = find_or_create_file_node(app, &file_name, &file);
+            let relative = file_display_name(app, &file);
+            let file_id = find_or_create_file_node(app, &relative);
             let suite_id = find_or_create_test_node(app, file_id, &name);
             if let Some(node) = app.tree.get_mut(suite_id) {
                 node.location = Some(location);
@@ -136,9 +233,8 @@ pub fn handle_test_event(app: &mut App, event: TestEvent) {
         }
 
         TestEvent::FileFinished { path } => {
-            let display = file_display_name(app, &path);
-            let filename = basename(&display).to_string();
-            if let Some(file_id) = app.tree.find_file_by_filename(&filename) {
+            let relative = file_display_name(app, &path);
+            if let Some(file_id) = app.tree.find_file_by_path(Path::new(&relative)) {
                 app.tree.purge_stale_children(file_id);
             }
         }
@@ -155,22 +251,80 @@ pub fn handle_test_event(app: &mut App, event: TestEvent) {
             app.summary = Some(summary);
             // Tree may have gained new nodes during the run; recompute watched set.
             app.watched_ids_stale = true;
+            if let Err(e) = app.history.end_run() {
+                history_failed(app, e);
+            }
+        }
+
+        TestEvent::CodeVersion { run, version } => {
+            if let Err(e) = app.history.set_code_version(run, version) {
+                history_failed(app, e);
+            }
+        }
+
+        TestEvent::SnapshotsWritten { files } => {
+            for file in &files {
+                let file = file_display_name(app, file);
+                app.runner_log
+                    .push(LogKind::Info, format!("Wrote snapshot {file}"));
+            }
+            let noun = if files.len() == 1 { "file" } else { "files" };
+            app.notifier
+                .info(format!("Wrote {} snapshot {noun}", files.len()), 3);
         }
 
-        TestEvent::ConsoleLog { file, content } => {
-            let file_name = file_display_name(app, &file);
-            let file_id = find_or_create_file_node(app, &file_name, &file);
+        TestEvent::Coverage { files } => {
+            let files = files
+                .into_iter()
+                .map(|(file, coverage)| (PathBuf::from(file_display_name(app, &file)), coverage))
+                .collect();
+            app.coverage.merge(files);
+        }
+
+        TestEvent::ConsoleLog {
+            file,
+            test,
+            stream,
+            content,
+        } => {
+            let relative = file_display_name(app, &file);
+            let file_id = find_or_create_file_node(app, &relative);
+            let line = ConsoleLine {
+                stream,
+                text: content,
+            };
+            // The file keeps every line, in order, for its whole-file view.
+            if let Some(name) = test {
+                let test_id = find_or_create_test_node(app, file_id, &name);
+                if let Some(node) = app.tree.get_mut(test_id) {
+                    node.console_output.push(line.clone());
+                }
+            }
             if let Some(node) = app.tree.get_mut(file_id) {
-                node.console_output.push(content);
+                node.console_output.push(line);
             }
         }
 
+        TestEvent::CommandStarted { command, cwd } => {
+            app.runner_log.push(
+                LogKind::Command,
+                format!("{command}  (in {})", cwd.display()),
+            );
+        }
+
         TestEvent::Output { line } => {
-            app.output_lines.push(line);
+            app.runner_log.push(LogKind::Output, line);
         }
 
         TestEvent::Error { message } => {
-            app.output_lines.push(format!("[ERROR] {}", message));
+            let first = message.lines().next().unwrap_or_default();
+            let hint = app
+                .keymap
+                .key_for(&Action::ToggleRunnerLog)
+                .map(|key| format!(" ({key}: runner log)"))
+                .unwrap_or_default();
+            app.notifier.error(format!("{first}{hint}"));
+            app.runner_log.push(LogKind::Error, message);
         }
 
         TestEvent::WatchStopped => {
@@ -181,9 +335,14 @@ pub fn handle_test_event(app: &mut App, event: TestEvent) {
             app.watched_ids_stale = true;
         }
 
-        TestEvent::DiscoveryComplete { files } => {
+        TestEvent::DiscoveryComplete { files, projects } => {
             if !files.is_empty() {
-                let prefix = common_directory_prefix(&files);
+                let unprojected: Vec<String> = files
+                    .iter()
+                    .filter(|path| project_of(&projects, path).is_none())
+                    .cloned()
+                    .collect();
+                let prefix = common_directory_prefix(&unprojected);
 
                 // Find or create the workspace root node
                 let workspace_id = if let Some(id) = app.tree.find_root_by_name(&prefix) {
@@ -193,21 +352,27 @@ pub fn handle_test_event(app: &mut App, event: TestEvent) {
                 };
 
                 for path in &files {
-                    let filename = basename(path).to_string();
-                    if app.tree.find_file_by_filename(&filename).is_some() {
+                    if app.tree.find_file_by_path(Path::new(path)).is_some() {
                         continue;
                     }
-                    // relative path from workspace prefix (e.g. "todos/todos.service.spec.ts")
-                    let relative = if prefix.is_empty() {
-                        path.as_str()
-                    } else {
-                        path.strip_prefix(&format!("{prefix}/")).unwrap_or(path)
+                    let filename = basename(path).to_string();
+                    let mut dir_parts: Vec<&str> = match project_of(&projects, path) {
+                        // relative path from the project (e.g. "src/lib.rs")
+                        Some((name, relative)) => {
+                            std::iter::once(name).chain(relative.split('/')).collect()
+                        }
+                        // relative path from workspace prefix (e.g. "todos/todos.service.spec.ts")
+                        None if prefix.is_empty() => path.split('/').collect(),
+                        None => path
+                            .strip_prefix(&format!("{prefix}/"))
+                            .unwrap_or(path)
+                            .split('/')
+                            .collect(),
                     };
-                    let parts: Vec<&str> = relative.split('/').collect();
-                    let dir_parts = &parts[..parts.len().saturating_sub(1)];
+                    dir_parts.pop();
 
                     let mut parent_id = workspace_id;
-                    for &dir in dir_parts {
+                    for dir in dir_parts {
                         if let Some(id) = app.tree.find_child_by_name(parent_id, dir) {
                             parent_id = id;
                         } else {
@@ -237,21 +402,37 @@ pub fn handle_test_event(app: &mut App, event: TestEvent) {
     }
 }
 
-/// Find or create a file node anywhere in the tree, using just the filename (basename).
+/// Find or create a file node anywhere in the tree by its workspace-relative path.
 /// Falls back to creating a root-level File node if not found (e.g. new file in watch mode).
-fn find_or_create_file_node(app: &mut App, display_name: &str, path: &str) -> usize {
-    let filename = basename(display_name);
-    if let Some(id) = app.tree.find_file_by_filename(filename) {
+fn find_or_create_file_node(app: &mut App, relative: &str) -> usize {
+    if let Some(id) = app.tree.find_file_by_path(Path::new(relative)) {
         return id;
     }
     // Not found — create as a root fallback (watch mode new file)
     app.tree.add_root(
         NodeKind::File,
-        filename.to_string(),
-        Some(PathBuf::from(path)),
+        basename(relative).to_string(),
+        Some(PathBuf::from(relative)),
     )
 }
 
+/// The name of the project `path` is in, the innermost when they nest, and
+/// `path` relative to its directory.
+fn project_of<'a>(projects: &'a [(String, String)], path: &'a str) -> Option<(&'a str, &'a str)> {
+    projects
+        .iter()
+        .filter_map(|(dir, name)| {
+            let relative = if dir.is_empty() {
+                path
+            } else {
+                path.strip_prefix(dir.as_str())?.strip_prefix('/')?
+            };
+            Some((dir.len(), name.as_str(), relative))
+        })
+        .max_by_key(|(len, ..)| *len)
+        .map(|(_, name, relative)| (name, relative))
+}
+
 fn basename(path: &str) -> &str {
     path.rsplit('/').next().unwrap_or(path)
 }
@@ -259,7 +440,7 @@ fn basename(path: &str) -> &str {
 /// Compute the common parent directory shared by all file paths.
 /// E.g. ["apps/todos/src/app/app.spec.ts", "apps/todos/src/app/todos/foo.ts"]
 ///      → "apps/todos/src/app"
-fn common_directory_prefix(paths: &[String]) -> String {
+pub(super) fn common_directory_prefix(paths: &[String]) -> String {
     if paths.is_empty() {
         return String::new();
     }
@@ -285,7 +466,7 @@ fn common_directory_prefix(paths: &[String]) -> String {
 }
 
 /// Find or create a test node under a file. Handles suite nesting via ` > ` separator.
-fn find_or_create_test_node(app: &mut App, file_id: usize, full_name: &str) -> usize {
+pub(super) fn find_or_create_test_node(app: &mut App, file_id: usize, full_name: &str) -> usize {
     // Vitest uses " > " to separate suite/test hierarchy in fullName
     let parts: Vec<&str> = full_name.split(" > ").collect();
     let mut parent_id = file_id;
@@ -321,3 +502,276 @@ fn file_display_name(app: &App, path: &str) -> String {
         .trim_start_matches('/');
     stripped.to_string()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn app_with_files(files: &[&str]) -> App {
+        let (mut app, _rx) = App::new(PathBuf::from("/ws"));
+        handle_test_event(
+            &mut app,
+            TestEvent::DiscoveryComplete {
+                files: files.iter().map(|f| f.to_string()).collect(),
+                projects: Vec::new(),
+            },
+        );
+        app
+    }
+
+    fn finish(app: &mut App, file: &str, name: &str, status: TestStatus) {
+        handle_test_event(
+            app,
+            TestEvent::TestFinished {
+                file: file.to_string(),
+                name: name.to_string(),
+                result: Box::new(TestResult {
+                    status,
+                    duration_ms: None,
+                    failures: Vec::new(),
+                }),
+                location: None,
+            },
+        );
+    }
+
+    #[test]
+    fn snapshots_written_are_listed_in_the_runner_log() {
+        let mut app = app_with_files(&["src/a.test.ts"]);
+        handle_test_event(
+            &mut app,
+            TestEvent::SnapshotsWritten {
+                files: vec!["/ws/src/__snapshots__/a.test.ts.snap".into()],
+            },
+        );
+        let lines: Vec<&str> = app.runner_log.lines().map(|l| l.text.as_str()).collect();
+        assert_eq!(lines, ["Wrote snapshot src/__snapshots__/a.test.ts.snap"]);
+    }
+
+    #[test]
+    fn coverage_is_keyed_by_workspace_relative_path() {
+        let mut app = app_with_files(&["src/a.test.ts"]);
+        handle_test_event(
+            &mut app,
+            TestEvent::Coverage {
+                files: vec![("/ws/src/a.ts".into(), FileCoverage::default())],
+            },
+        );
+        let (source, _) = app
+            .coverage
+            .source_for_test(Path::new("src/a.test.ts"))
+            .unwrap();
+        assert_eq!(source, Path::new("src/a.ts"));
+    }
+
+    #[test]
+    fn discovery_keeps_files_with_same_basename() {
+        let app = app_with_files(&["apps/a/src/index.spec.ts", "apps/b/src/index.spec.ts"]);
+        assert_eq!(app.tree.count_kind(NodeKind::File), 2);
+    }
+
+    #[test]
+    fn files_in_a_named_project_are_listed_under_its_name() {
+        let (mut app, _rx) = App::new(PathBuf::from("/ws"));
+        handle_test_event(
+            &mut app,
+            TestEvent::DiscoveryComplete {
+                files: vec![
+                    "crates/core/src/lib.rs".into(),
+                    "crates/core/tests/api.rs".into(),
+                    "src/main.rs".into(),
+                ],
+                projects: vec![
+                    ("crates/core".into(), "lens-core".into()),
+                    (String::new(), "lens".into()),
+                ],
+            },
+        );
+
+        let root = app.tree.find_root_by_name("").unwrap();
+        let core = app.tree.find_child_by_name(root, "lens-core").unwrap();
+        let tests = app.tree.find_child_by_name(core, "tests").unwrap();
+        let api = app
+            .tree
+            .find_file_by_path(Path::new("crates/core/tests/api.rs"))
+            .unwrap();
+        assert_eq!(app.tree.get(api).unwrap().parent, Some(tests));
+        let lens = app.tree.find_child_by_name(root, "lens").unwrap();
+        assert!(app.tree.find_child_by_name(lens, "src").is_some());
+        assert!(app.tree.find_child_by_name(root, "crates").is_none());
+    }
+
+    #[test]
+    fn results_land_on_the_file_with_matching_path() {
+        let mut app = app_with_files(&["apps/a/src/index.spec.ts", "apps/b/src/index.spec.ts"]);
+        finish(
+            &mut app,
+            "/ws/apps/b/src/index.spec.ts",
+            "works",
+            TestStatus::Failed,
+        );
+
+        let a = app
+            .tree
+            .find_file_by_path(Path::new("apps/a/src/index.spec.ts"))
+            .unwrap();
+        let b = app
+            .tree
+            .find_file_by_path(Path::new("apps/b/src/index.spec.ts"))
+            .unwrap();
+        assert!(app.tree.get(a).unwrap().children.is_empty());
+        assert_eq!(app.tree.get(b).unwrap().children.len(), 1);
+        assert_eq!(app.tree.get(b).unwrap().status, TestStatus::Failed);
+    }
+
+    #[test]
+    fn file_finished_purges_only_the_matching_file() {
+        let mut app = app_with_files(&["apps/a/src/index.spec.ts", "apps/b/src/index.spec.ts"]);
+        finish(
+            &mut app,
+            "/ws/apps/a/src/index.spec.ts",
+            "old",
+            TestStatus::Passed,
+        );
+        finish(
+            &mut app,
+            "/ws/apps/b/src/index.spec.ts",
+            "old",
+            TestStatus::Passed,
+        );
+
+        // Re-run b without its test: only b's "old" test should be purged.
+        let b_path = "/ws/apps/b/src/index.spec.ts".to_string();
+        handle_test_event(
+            &mut app,
+            TestEvent::FileStarted {
+                path: b_path.clone(),
+            },
+        );
+        handle_test_event(&mut app, TestEvent::FileFinished { path: b_path });
+
+        let a = app
+            .tree
+            .find_file_by_path(Path::new("apps/a/src/index.spec.ts"))
+            .unwrap();
+        let b = app
+            .tree
+            .find_file_by_path(Path::new("apps/b/src/index.spec.ts"))
+            .unwrap();
+        assert_eq!(app.tree.get(a).unwrap().children.len(), 1);
+        assert!(app.tree.get(b).unwrap().children.is_empty());
+    }
+
+    fn log(app: &mut App, test: Option<&str>, stream: ConsoleStream, content: &str) {
+        handle_test_event(
+            app,
+            TestEvent::ConsoleLog {
+                file: "/ws/src/a.test.ts".to_string(),
+                test: test.map(str::to_string),
+                stream,
+                content: content.to_string(),
+            },
+        );
+    }
+
+    #[test]
+    fn console_output_is_attributed_to_the_running_test() {
+        let mut app = app_with_files(&["src/a.test.ts"]);
+        log(&mut app, None, ConsoleStream::Stdout, "loading fixtures");
+        log(
+            &mut app,
+            Some("math > adds"),
+            ConsoleStream::Stdout,
+            "1 + 1",
+        );
+        log(
+            &mut app,
+            Some("math > subtracts"),
+            ConsoleStream::Stderr,
+            "oops",
+        );
+        finish(
+            &mut app,
+            "/ws/src/a.test.ts",
+            "math > adds",
+            TestStatus::Passed,
+        );
+
+        let file = app
+            .tree
+            .find_file_by_path(Path::new("src/a.test.ts"))
+            .unwrap();
+        let suite = app.tree.find_child_by_name(file, "math").unwrap();
+        let adds = app.tree.find_child_by_name(suite, "adds").unwrap();
+        let subtracts = app.tree.find_child_by_name(suite, "subtracts").unwrap();
+        let texts =
+            |lines: &[ConsoleLine]| lines.iter().map(|l| l.text.clone()).collect::<Vec<_>>();
+
+        assert_eq!(texts(app.tree.console_output(adds, false)), ["1 + 1"]);
+        assert_eq!(
+            app.tree.console_output(subtracts, false)[0].stream,
+            ConsoleStream::Stderr
+        );
+        assert_eq!(
+            texts(app.tree.console_output(adds, true)),
+            ["loading fixtures", "1 + 1", "oops"]
+        );
+        assert_eq!(app.tree.console_output(suite, false).len(), 3);
+
+        handle_test_event(
+            &mut app,
+            TestEvent::FileStarted {
+                path: "/ws/src/a.test.ts".to_string(),
+            },
+        );
+        assert!(app.tree.console_output(adds, false).is_empty());
+        assert!(app.tree.console_output(file, false).is_empty());
+    }
+
+    #[tokio::test]
+    async fn failure_snippets_arrive_in_their_own_event() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.test.ts"), "one\ntwo\nthree\n").unwrap();
+        let (mut app, mut rx) = App::new(dir.path().to_path_buf());
+        handle_test_event(
+            &mut app,
+            TestEvent::DiscoveryComplete {
+                files: vec!["a.test.ts".into()],
+                projects: Vec::new(),
+            },
+        );
+        let failure = crate::models::FailureOutput {
+            message: "boom".into(),
+            expected: None,
+            actual: None,
+            expected_parsed: None,
+            actual_parsed: None,
+            diff: None,
+            source_snippet: None,
+            stack_trace: Some("at a.test.ts:2:1".into()),
+            attachments: Vec::new(),
+        };
+        handle_test_event(
+            &mut app,
+            TestEvent::TestFinished {
+                file: dir.path().join("a.test.ts").to_string_lossy().into_owned(),
+                name: "works".into(),
+                result: Box::new(TestResult {
+                    status: TestStatus::Failed,
+                    duration_ms: None,
+                    failures: vec![failure],
+                }),
+                location: None,
+            },
+        );
+
+        let event = rx.recv().await.unwrap();
+        assert!(matches!(event, TestEvent::SourceSnippets { .. }));
+        handle_test_event(&mut app, event);
+        let file_id = app.tree.find_file_by_path(Path::new("a.test.ts")).unwrap();
+        let test_id = app.tree.find_child_by_name(file_id, "works").unwrap();
+        let result = app.tree.get(test_id).unwrap().result.as_ref().unwrap();
+        let snippet = result.failures[0].source_snippet.as_deref().unwrap();
+        assert!(snippet.contains("> 2 | two"));
+    }
+}
@@ -0,0 +1,480 @@
+use std::collections::{HashMap, VecDeque};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::models::TestStatus;
+
+/// Most recent results per test considered when scoring flakiness.
+const WINDOW: usize = 50;
+
+/// One `TestFinished` result, appended to `.lens/history.jsonl`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryRecord {
+    /// `<workspace-relative file>::<suite > test>`, see [`test_key`].
+    pub test: String,
+    pub status: TestStatus,
+    pub duration_ms: Option<u64>,
+    /// Seconds since the Unix epoch.
+    pub timestamp: u64,
+    /// Code version the test ran against, see [`code_version`].
+    pub commit: Option<String>,
+}
+
+/// Pass/fail flips between consecutive runs of the same code version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Flakiness {
+    pub flips: usize,
+    /// Consecutive pairs of runs that shared a code version.
+    pub comparisons: usize,
+}
+
+impl Flakiness {
+    /// Fraction of comparable run pairs whose outcome flipped, in `0.0..=1.0`.
+    pub fn score(&self) -> f64 {
+        if self.comparisons == 0 {
+            0.0
+        } else {
+            self.flips as f64 / self.comparisons as f64
+        }
+    }
+}
+
+/// Append-only log of test results plus the per-test flakiness derived from it.
+#[derive(Debug, Default)]
+pub struct History {
+    /// Where new records are appended; `None` keeps history in memory only.
+    path: Option<PathBuf>,
+    workspace: Option<PathBuf>,
+    /// Code version of the run in progress.
+    commit: Option<String>,
+    /// Counts runs, so a code version arriving late is matched to its run.
+    run: u64,
+    /// The run's code version is still being computed; its results wait in
+    /// `pending` until [`History::set_code_version`] is called.
+    waiting: bool,
+    pending: Vec<HistoryRecord>,
+    /// Open from a run's first record until it ends.
+    writer: Option<BufWriter<File>>,
+    recent: HashMap<String, VecDeque<(TestStatus, Option<String>)>>,
+    flakiness: HashMap<String, Flakiness>,
+}
+
+pub fn history_path(workspace: &Path) -> PathBuf {
+    workspace.join(".lens").join("history.jsonl")
+}
+
+/// Identify a test across runs by file and full name.
+pub fn test_key(file: &str, full_name: &str) -> String {
+    format!("{file}::{full_name}")
+}
+
+impl History {
+    /// Load the workspace history. Unreadable lines are skipped; a missing file
+    /// starts an empty history. The file is compacted to each test's last
+    /// [`WINDOW`] records, the only ones flakiness looks at.
+    pub fn load(workspace: &Path) -> Self {
+        let path = history_path(workspace);
+        let mut history = Self {
+            path: Some(path.clone()),
+            workspace: Some(workspace.to_path_buf()),
+            ..Self::default()
+        };
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            return history;
+        };
+        let records: Vec<HistoryRecord> = content
+            .lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect();
+        let mut remaining: HashMap<&str, usize> = HashMap::new();
+        for record in &records {
+            *remaining.entry(&record.test).or_default() += 1;
+        }
+        let kept: Vec<&HistoryRecord> = records
+            .iter()
+            .filter(|record| {
+                let count = remaining.get_mut(record.test.as_str()).unwrap();
+                *count -= 1;
+                *count < WINDOW
+            })
+            .collect();
+        if kept.len() < content.lines().count() {
+            // Still readable as it was, so a failed rewrite loses nothing.
+            let _ = compact(&path, &kept);
+        }
+        for record in kept {
+            history.push(record.test.clone(), record.status, record.commit.clone());
+        }
+        history
+    }
+
+    /// Start a run. Its code version takes a few git commands to compute, so
+    /// it's returned as a request for the caller to fulfil off the UI thread
+    /// and hand to [`Self::set_code_version`]. `None` when history is in
+    /// memory only.
+    pub fn begin_run(&mut self) -> std::io::Result<Option<VersionRequest>> {
+        // A previous run whose version never arrived keeps its results anyway.
+        let result = self.set_code_version(self.run, None);
+        self.run += 1;
+        self.commit = None;
+        let request = self.workspace.clone().map(|workspace| {
+            self.waiting = true;
+            VersionRequest {
+                run: self.run,
+                workspace,
+            }
+        });
+        result.map(|()| request)
+    }
+
+    /// Whether results are waiting for the run's code version.
+    pub fn is_waiting(&self) -> bool {
+        self.waiting
+    }
+
+    /// The code version of `run`, computed for a [`VersionRequest`]. Results
+    /// recorded while it was computed are saved now.
+    pub fn set_code_version(&mut self, run: u64, version: Option<String>) -> std::io::Result<()> {
+        if run != self.run || !self.waiting {
+            return Ok(());
+        }
+        self.waiting = false;
+        self.commit = version;
+        let mut result = Ok(());
+        for mut record in std::mem::take(&mut self.pending) {
+            record.commit = self.commit.clone();
+            result = result.and(self.save(record));
+        }
+        // The run may already have ended.
+        result.and(self.flush())
+    }
+
+    /// End the run, writing out its buffered records.
+    pub fn end_run(&mut self) -> std::io::Result<()> {
+        let result = self.flush();
+        self.writer = None;
+        result
+    }
+
+    /// Record a finished test. Only passes and failures count; on a write
+    /// error the history stops persisting and the error is returned once.
+    pub fn record(
+        &mut self,
+        test: String,
+        status: TestStatus,
+        duration_ms: Option<u64>,
+    ) -> std::io::Result<()> {
+        if !matches!(status, TestStatus::Passed | TestStatus::Failed) {
+            return Ok(());
+        }
+        let record = HistoryRecord {
+            test,
+            status,
+            duration_ms,
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+            commit: self.commit.clone(),
+        };
+        if self.waiting {
+            self.pending.push(record);
+            return Ok(());
+        }
+        self.save(record)
+    }
+
+    fn save(&mut self, record: HistoryRecord) -> std::io::Result<()> {
+        let result = self.append(&record);
+        self.push(record.test, record.status, record.commit);
+        result
+    }
+
+    /// Write `record` to the run's writer, opening it for the run's first
+    /// record. A failure stops the history from persisting.
+    fn append(&mut self, record: &HistoryRecord) -> std::io::Result<()> {
+        let Some(ref path) = self.path else {
+            return Ok(());
+        };
+        let result = match self.writer.take() {
+            Some(writer) => Ok(writer),
+            None => open_append(path),
+        }
+        .and_then(|writer| {
+            let line = serde_json::to_string(record).map_err(std::io::Error::other)?;
+            writeln!(self.writer.insert(writer), "{line}")
+        });
+        if result.is_err() {
+            self.path = None;
+            self.writer = None;
+        }
+        result
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        let result = self.writer.as_mut().map_or(Ok(()), |writer| writer.flush());
+        if result.is_err() {
+            self.path = None;
+            self.writer = None;
+        }
+        result
+    }
+
+    /// Flakiness of a test, if it has flipped at least once.
+    pub fn flakiness(&self, test: &str) -> Option<Flakiness> {
+        self.flakiness.get(test).copied().filter(|f| f.flips > 0)
+    }
+
+    /// Every test that has flipped, most flaky first.
+    pub fn flaky_tests(&self) -> Vec<(&str, Flakiness)> {
+        let mut tests: Vec<(&str, Flakiness)> = self
+            .flakiness
+            .iter()
+            .filter(|(_, f)| f.flips > 0)
+            .map(|(test, f)| (test.as_str(), *f))
+            .collect();
+        tests.sort_by(|a, b| {
+            b.1.score()
+                .total_cmp(&a.1.score())
+                .then(b.1.flips.cmp(&a.1.flips))
+                .then(a.0.cmp(b.0))
+        });
+        tests
+    }
+
+    fn push(&mut self, test: String, status: TestStatus, commit: Option<String>) {
+        let recent = self.recent.entry(test.clone()).or_default();
+        recent.push_back((status, commit));
+        if recent.len() > WINDOW {
+            recent.pop_front();
+        }
+        self.flakiness.insert(test, score(recent));
+    }
+}
+
+fn score(recent: &VecDeque<(TestStatus, Option<String>)>) -> Flakiness {
+    let mut flakiness = Flakiness::default();
+    for (a, b) in recent.iter().zip(recent.iter().skip(1)) {
+        // Without a known, shared code version a flip may just be an edit.
+        if a.1.is_none() || a.1 != b.1 {
+            continue;
+        }
+        flakiness.comparisons += 1;
+        if a.0 != b.0 {
+            flakiness.flips += 1;
+        }
+    }
+    flakiness
+}
+
+fn open_append(path: &Path) -> std::io::Result<BufWriter<File>> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    Ok(BufWriter::new(file))
+}
+
+/// Replace the log at `path` with `records`, through a temporary file so a
+/// failure leaves the old log in place.
+fn compact(path: &Path, records: &[&HistoryRecord]) -> std::io::Result<()> {
+    let temp = path.with_extension("jsonl.tmp");
+    let mut writer = BufWriter::new(File::create(&temp)?);
+    for record in records {
+        let line = serde_json::to_string(record).map_err(std::io::Error::other)?;
+        writeln!(writer, "{line}")?;
+    }
+    writer.flush()?;
+    drop(writer);
+    std::fs::rename(&temp, path)
+}
+
+/// A run's code version to compute with [`code_version`], which runs git.
+#[derive(Debug)]
+pub struct VersionRequest {
+    pub run: u64,
+    pub workspace: PathBuf,
+}
+
+/// `HEAD`'s commit, suffixed with a hash of uncommitted changes when the
+/// working tree is dirty. `None` outside a git repository.
+pub fn code_version(workspace: &Path) -> Option<String> {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .current_dir(workspace)
+            .output()
+            .ok()
+            .filter(|o| o.status.success())
+            .map(|o| o.stdout)
+    };
+    let head = git(&["rev-parse", "HEAD"])?;
+    let head = String::from_utf8_lossy(&head).trim().to_string();
+    let diff = git(&["diff", "HEAD"]).unwrap_or_default();
+    if diff.is_empty() {
+        Some(head)
+    } else {
+        Some(format!("{head}+{:016x}", fnv1a(&diff)))
+    }
+}
+
+/// FNV-1a: stable across Rust versions, unlike `DefaultHasher`.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, &b| {
+        (hash ^ b as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history_at(commit: &str) -> History {
+        History {
+            commit: Some(commit.into()),
+            ..History::default()
+        }
+    }
+
+    #[test]
+    fn flips_on_the_same_commit_count_as_flaky() {
+        let mut history = history_at("abc");
+        for status in [
+            TestStatus::Passed,
+            TestStatus::Failed,
+            TestStatus::Passed,
+            TestStatus::Passed,
+        ] {
+            history.record("a.test.ts::t".into(), status, None).unwrap();
+        }
+        let flakiness = history.flakiness("a.test.ts::t").unwrap();
+        assert_eq!(flakiness.flips, 2);
+        assert_eq!(flakiness.comparisons, 3);
+    }
+
+    #[test]
+    fn flips_across_code_changes_are_ignored() {
+        let mut history = history_at("abc");
+        history
+            .record("a.test.ts::t".into(), TestStatus::Failed, None)
+            .unwrap();
+        history.commit = Some("def".into());
+        history
+            .record("a.test.ts::t".into(), TestStatus::Passed, None)
+            .unwrap();
+        assert!(history.flakiness("a.test.ts::t").is_none());
+    }
+
+    #[test]
+    fn skipped_results_are_not_recorded() {
+        let mut history = history_at("abc");
+        for status in [TestStatus::Passed, TestStatus::Skipped, TestStatus::Failed] {
+            history.record("t".into(), status, None).unwrap();
+        }
+        assert_eq!(
+            history.flakiness("t"),
+            Some(Flakiness {
+                flips: 1,
+                comparisons: 1
+            })
+        );
+    }
+
+    #[test]
+    fn flaky_tests_are_sorted_by_score() {
+        let mut history = history_at("abc");
+        for status in [TestStatus::Passed, TestStatus::Failed, TestStatus::Passed] {
+            history.record("always".into(), status, None).unwrap();
+        }
+        for status in [TestStatus::Passed, TestStatus::Passed, TestStatus::Failed] {
+            history.record("sometimes".into(), status, None).unwrap();
+        }
+        history
+            .record("stable".into(), TestStatus::Passed, None)
+            .unwrap();
+        let names: Vec<&str> = history.flaky_tests().iter().map(|(t, _)| *t).collect();
+        assert_eq!(names, ["always", "sometimes"]);
+    }
+
+    #[test]
+    fn history_round_trips_through_the_log_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut history = History::load(dir.path());
+        history.commit = Some("abc".into());
+        history
+            .record("t".into(), TestStatus::Passed, Some(3))
+            .unwrap();
+        history
+            .record("t".into(), TestStatus::Failed, Some(4))
+            .unwrap();
+        history.end_run().unwrap();
+
+        let reloaded = History::load(dir.path());
+        assert_eq!(reloaded.flakiness("t").map(|f| f.flips), Some(1));
+    }
+
+    #[test]
+    fn loading_keeps_only_each_test_s_last_window() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut history = History::load(dir.path());
+        history.commit = Some("abc".into());
+        for i in 0..WINDOW + 10 {
+            let status = if i < 10 {
+                TestStatus::Failed
+            } else {
+                TestStatus::Passed
+            };
+            history.record("old".into(), status, None).unwrap();
+        }
+        history
+            .record("new".into(), TestStatus::Passed, None)
+            .unwrap();
+        history.end_run().unwrap();
+
+        let reloaded = History::load(dir.path());
+        let content = std::fs::read_to_string(history_path(dir.path())).unwrap();
+        let tests: Vec<String> = content
+            .lines()
+            .map(|line| serde_json::from_str::<HistoryRecord>(line).unwrap().test)
+            .collect();
+        assert_eq!(tests.len(), WINDOW + 1);
+        assert_eq!(tests.iter().filter(|t| *t == "old").count(), WINDOW);
+        assert_eq!(tests.last().map(String::as_str), Some("new"));
+        // The dropped failures were older than the window already.
+        assert_eq!(reloaded.flakiness("old"), None);
+    }
+
+    #[test]
+    fn results_wait_for_the_runs_code_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut history = History::load(dir.path());
+        let request = history.begin_run().unwrap().unwrap();
+        history
+            .record("t".into(), TestStatus::Passed, Some(3))
+            .unwrap();
+        assert!(history.is_waiting());
+        assert_eq!(History::load(dir.path()).flakiness("t"), None);
+
+        // A version for an earlier run is ignored.
+        history.set_code_version(request.run - 1, None).unwrap();
+        assert!(history.is_waiting());
+        history
+            .set_code_version(request.run, Some("abc".into()))
+            .unwrap();
+        history
+            .record("t".into(), TestStatus::Failed, Some(4))
+            .unwrap();
+        history.end_run().unwrap();
+
+        let reloaded = History::load(dir.path());
+        assert_eq!(reloaded.flakiness("t").map(|f| f.flips), Some(1));
+    }
+}
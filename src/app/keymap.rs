@@ -0,0 +1,524 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use super::actions::Action;
+
+/// One key press: a key plus the Ctrl/Alt modifiers held with it. Shift is
+/// folded into the key itself (`G`, `BackTab`) since terminals disagree on
+/// whether they report it alongside.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyChord {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl KeyChord {
+    fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        let modifiers = match code {
+            KeyCode::Char(_) | KeyCode::BackTab => modifiers - KeyModifiers::SHIFT,
+            _ => modifiers,
+        };
+        Self {
+            code,
+            modifiers: modifiers
+                & (KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT),
+        }
+    }
+
+    fn from_event(key: KeyEvent) -> Self {
+        Self::new(key.code, key.modifiers)
+    }
+
+    /// Parse `ctrl+d`, `alt+enter`, `shift+tab`, `G`, `pgdn`, `f5`, …
+    fn parse(token: &str) -> Result<Self, String> {
+        // A trailing `+` is the key itself (`ctrl++`), not a separator.
+        let (mods, key) = match token.strip_suffix("++") {
+            Some(mods) => (mods, "+"),
+            None if token == "+" => ("", "+"),
+            None => token.rsplit_once('+').unwrap_or(("", token)),
+        };
+        let mut modifiers = KeyModifiers::NONE;
+        for name in mods.split('+').filter(|m| !m.is_empty()) {
+            modifiers |= match name.to_lowercase().as_str() {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" | "meta" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                _ => return Err(format!("unknown modifier '{name}' in '{token}'")),
+            };
+        }
+        let code = key_code(key).ok_or_else(|| format!("unknown key '{key}' in '{token}'"))?;
+        let code = match code {
+            KeyCode::Tab if modifiers.contains(KeyModifiers::SHIFT) => KeyCode::BackTab,
+            KeyCode::Char(c) if modifiers.contains(KeyModifiers::SHIFT) => {
+                KeyCode::Char(c.to_ascii_uppercase())
+            }
+            code => code,
+        };
+        Ok(Self::new(code, modifiers))
+    }
+
+    /// How the chord is shown in the help overlay.
+    fn label(&self) -> String {
+        let key = match self.code {
+            KeyCode::Char(' ') => "Space".to_string(),
+            KeyCode::Char(c) => c.to_string(),
+            KeyCode::Up => "↑".to_string(),
+            KeyCode::Down => "↓".to_string(),
+            KeyCode::Left => "←".to_string(),
+            KeyCode::Right => "→".to_string(),
+            KeyCode::PageUp => "PgUp".to_string(),
+            KeyCode::PageDown => "PgDn".to_string(),
+            KeyCode::BackTab => "Shift+Tab".to_string(),
+            KeyCode::Esc => "Esc".to_string(),
+            KeyCode::F(n) => format!("F{n}"),
+            code => format!("{code:?}"),
+        };
+        let mut label = String::new();
+        for (modifier, name) in [
+            (KeyModifiers::CONTROL, "Ctrl+"),
+            (KeyModifiers::ALT, "Alt+"),
+            (KeyModifiers::SHIFT, "Shift+"),
+        ] {
+            if self.modifiers.contains(modifier) {
+                label.push_str(name);
+            }
+        }
+        label + &key
+    }
+}
+
+fn key_code(name: &str) -> Option<KeyCode> {
+    let mut chars = name.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Some(KeyCode::Char(c));
+    }
+    Some(match name.to_lowercase().as_str() {
+        "enter" | "return" => KeyCode::Enter,
+        "esc" | "escape" => KeyCode::Esc,
+        "tab" => KeyCode::Tab,
+        "backtab" => KeyCode::BackTab,
+        "space" => KeyCode::Char(' '),
+        "backspace" => KeyCode::Backspace,
+        "delete" | "del" => KeyCode::Delete,
+        "insert" => KeyCode::Insert,
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "pageup" | "pgup" => KeyCode::PageUp,
+        "pagedown" | "pgdn" => KeyCode::PageDown,
+        name => match name.strip_prefix('f').and_then(|n| n.parse().ok()) {
+            Some(n @ 1..=12) => KeyCode::F(n),
+            _ => return None,
+        },
+    })
+}
+
+/// Parse a binding: chords separated by spaces, pressed one after another.
+/// A word that is neither a chord nor a key name is a sequence of plain
+/// characters, so `gg` is the same as `g g`.
+fn parse_sequence(binding: &str) -> Result<Vec<KeyChord>, String> {
+    let mut sequence = Vec::new();
+    for token in binding.split_whitespace() {
+        match KeyChord::parse(token) {
+            Ok(chord) => sequence.push(chord),
+            Err(_) if !token.contains('+') => sequence.extend(
+                token
+                    .chars()
+                    .map(|c| KeyChord::new(KeyCode::Char(c), KeyModifiers::NONE)),
+            ),
+            Err(e) => return Err(e),
+        }
+    }
+    if sequence.is_empty() {
+        return Err("empty key binding".to_string());
+    }
+    Ok(sequence)
+}
+
+/// A group of actions in the help overlay.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Section {
+    Navigation,
+    Actions,
+    Layout,
+}
+
+impl Section {
+    pub fn title(self) -> &'static str {
+        match self {
+            Self::Navigation => "Navigation",
+            Self::Actions => "Actions",
+            Self::Layout => "Layout",
+        }
+    }
+}
+
+/// An action that can be bound in `[keys]`.
+struct Bindable {
+    /// The name used in `lens.toml`.
+    name: &'static str,
+    action: Action,
+    section: Section,
+    description: &'static str,
+    defaults: &'static [&'static str],
+}
+
+const fn bindable(
+    name: &'static str,
+    action: Action,
+    section: Section,
+    description: &'static str,
+    defaults: &'static [&'static str],
+) -> Bindable {
+    Bindable {
+        name,
+        action,
+        section,
+        description,
+        defaults,
+    }
+}
+
+/// Every bindable action with its default keys, in help overlay order.
+#[rustfmt::skip]
+const BINDABLE: &[Bindable] = {
+    use Section::{Actions as A, Layout as L, Navigation as N};
+    &[
+        bindable("navigate_up", Action::NavigateUp, N, "move up", &["k", "up"]),
+        bindable("navigate_down", Action::NavigateDown, N, "move down", &["j", "down"]),
+        bindable("scroll_up", Action::ScrollUp, N, "scroll up half-page", &["ctrl+u", "pageup"]),
+        bindable("scroll_down", Action::ScrollDown, N, "scroll down half-page", &["ctrl+d", "pagedown"]),
+        bindable("collapse", Action::Collapse, N, "collapse", &["h", "left"]),
+        bindable("expand", Action::Expand, N, "expand", &["l", "right"]),
+        bindable("collapse_all", Action::CollapseAll, N, "collapse all", &["H"]),
+        bindable("expand_all", Action::ExpandAll, N, "expand all", &["L"]),
+        bindable("jump_to_start", Action::JumpToStart, N, "jump to start", &["g", "home"]),
+        bindable("jump_to_end", Action::JumpToEnd, N, "jump to end", &["G", "end"]),
+        bindable("prev_file", Action::JumpToPrevFile, N, "previous file", &["{"]),
+        bindable("next_file", Action::JumpToNextFile, N, "next file", &["}"]),
+        bindable("prev_error", Action::JumpToPrevError, N, "previous error", &["["]),
+        bindable("next_error", Action::JumpToNextError, N, "next error", &["]"]),
+        bindable("search_next", Action::SearchNext, N, "next output match", &["n"]),
+        bindable("search_previous", Action::SearchPrevious, N, "previous output match", &["N"]),
+        bindable("focus_next", Action::FocusNext, N, "next panel", &["tab"]),
+        bindable("focus_previous", Action::FocusPrevious, N, "previous panel", &["backtab"]),
+        bindable("select", Action::Select, A, "run selected test / suite", &["enter"]),
+        bindable("run_filtered", Action::RunFiltered, A, "run filtered tests (or all)", &["a"]),
+        bindable("run_all", Action::RunAll, A, "run all files", &["A"]),
+        bindable("rerun_failed", Action::RerunFailed, A, "rerun failed", &["r"]),
+        bindable("toggle_watch", Action::ToggleWatch, A, "toggle watch mode", &["w"]),
+        bindable("open_in_editor", Action::OpenInEditor, A, "open in editor", &["e"]),
+        bindable("open_attachment", Action::OpenAttachment, A, "open trace / screenshot", &["O"]),
+        bindable("filter", Action::FilterEnter, A, "filter (status: file: path: slow:) / search output", &["/"]),
+        bindable("filter_by_file", Action::FilterByFile, A, "filter by current file", &["f"]),
+        bindable("filter_by_dir", Action::FilterByDir, A, "filter by current directory", &["F"]),
+        bindable("toggle_flat_results", Action::ToggleFlatResults, A, "flat ranked filter results", &["t"]),
+        bindable("yank_path", Action::YankPath, A, "yank file path", &["y"]),
+        bindable("yank_failure_location", Action::YankFailureLocation, A, "yank failure location", &["Y"]),
+        bindable("yank_output", Action::YankOutput, A, "copy output", &["c"]),
+        bindable("toggle_file_console", Action::ToggleFileConsole, A, "test / whole-file console output", &["o"]),
+        bindable("update_snapshots", Action::UpdateSnapshots, A, "update snapshots", &["u"]),
+        bindable("toggle_coverage", Action::ToggleCoverage, A, "toggle coverage", &["C"]),
+        bindable("toggle_flaky", Action::ToggleFlaky, A, "flaky tests", &["!"]),
+        bindable("toggle_runner_log", Action::ToggleRunnerLog, A, "runner log", &["`"]),
+        bindable("toggle_help", Action::ToggleHelp, A, "toggle this help", &["?"]),
+        bindable("quit", Action::Quit, A, "quit", &["q", "ctrl+c"]),
+        bindable("grow_tests", Action::GrowTests, L, "widen test panels", &[">"]),
+        bindable("shrink_tests", Action::ShrinkTests, L, "narrow test panels", &["<"]),
+        bindable("grow_tree", Action::GrowTree, L, "grow tree over failures", &["+"]),
+        bindable("shrink_tree", Action::ShrinkTree, L, "shrink tree", &["-"]),
+        bindable("toggle_zoom", Action::ToggleZoom, L, "maximize output", &["z"]),
+        bindable("toggle_stacking", Action::ToggleStacking, L, "side by side / stacked", &["v"]),
+    ]
+};
+
+/// Key sequences → actions for the tree and panels (not the filter input or
+/// overlays). Built from the defaults overridden by the `[keys]` config section.
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    /// (key sequence, index into `BINDABLE`)
+    bindings: Vec<(Vec<KeyChord>, usize)>,
+    /// Keys pressed so far of a multi-key sequence.
+    pending: Vec<KeyChord>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self::from_config(&toml::Table::new()).0
+    }
+}
+
+impl Keymap {
+    /// Build the keymap from `[keys]`: `action = "key"` or `action = ["key", …]`
+    /// replaces that action's default keys, and `[]` unbinds it. Defaults that
+    /// clash with a configured key are dropped. Problems are returned as
+    /// messages and the offending entries skipped.
+    pub fn from_config(keys: &toml::Table) -> (Self, Vec<String>) {
+        let mut errors = Vec::new();
+        let mut bindings: Vec<(Vec<KeyChord>, usize)> = Vec::new();
+        let mut configured = vec![false; BINDABLE.len()];
+
+        for (name, value) in keys {
+            let Some(index) = BINDABLE.iter().position(|b| b.name == name) else {
+                errors.push(format!("unknown action '{name}'"));
+                continue;
+            };
+            let entries: Vec<&str> = match value {
+                toml::Value::String(s) => vec![s.as_str()],
+                toml::Value::Array(values) => match values.iter().map(|v| v.as_str()).collect() {
+                    Some(entries) => entries,
+                    None => {
+                        errors.push(format!("{name}: expected a key or a list of keys"));
+                        continue;
+                    }
+                },
+                _ => {
+                    errors.push(format!("{name}: expected a key or a list of keys"));
+                    continue;
+                }
+            };
+            configured[index] = true;
+            for entry in entries {
+                let sequence = match parse_sequence(entry) {
+                    Ok(sequence) => sequence,
+                    Err(e) => {
+                        errors.push(format!("{name}: {e}"));
+                        continue;
+                    }
+                };
+                if let Some((_, other)) = bindings.iter().find(|(s, _)| overlaps(s, &sequence)) {
+                    errors.push(format!(
+                        "{name}: '{entry}' conflicts with the key for {}",
+                        BINDABLE[*other].name
+                    ));
+                    continue;
+                }
+                bindings.push((sequence, index));
+            }
+        }
+
+        let user_bindings = bindings.len();
+        for (index, bindable) in BINDABLE.iter().enumerate() {
+            if configured[index] {
+                continue;
+            }
+            for default in bindable.defaults {
+                let sequence = parse_sequence(default).expect("default key binding parses");
+                if !bindings[..user_bindings]
+                    .iter()
+                    .any(|(s, _)| overlaps(s, &sequence))
+                {
+                    bindings.push((sequence, index));
+                }
+            }
+        }
+
+        let keymap = Self {
+            bindings,
+            pending: Vec::new(),
+        };
+        (keymap, errors)
+    }
+
+    /// Feed a key press. Returns the bound action once a full sequence has been
+    /// typed; keys that start a longer sequence are held until it completes or
+    /// another key breaks it, in which case that key is looked up on its own.
+    pub fn resolve(&mut self, key: KeyEvent) -> Option<Action> {
+        let chord = KeyChord::from_event(key);
+        self.pending.push(chord);
+        let mut prefix_of_longer = false;
+        for (sequence, index) in &self.bindings {
+            if *sequence == self.pending {
+                self.pending.clear();
+                return Some(BINDABLE[*index].action.clone());
+            }
+            prefix_of_longer |= sequence.starts_with(&self.pending);
+        }
+        if prefix_of_longer {
+            return None;
+        }
+        let retry = self.pending.len() > 1;
+        self.pending.clear();
+        if retry { self.resolve(key) } else { None }
+    }
+
+    /// The keys typed so far of an unfinished sequence, e.g. `g`.
+    pub fn pending_label(&self) -> Option<String> {
+        (!self.pending.is_empty()).then(|| sequence_label(&self.pending))
+    }
+
+    /// The label of the first key bound to `action`, for hints.
+    pub fn key_for(&self, action: &Action) -> Option<String> {
+        self.bindings
+            .iter()
+            .find(|(_, index)| BINDABLE[*index].action == *action)
+            .map(|(sequence, _)| sequence_label(sequence))
+    }
+
+    /// Help overlay rows per section: (keys joined with ` / `, description).
+    /// Actions with no keys are left out.
+    pub fn help(&self) -> Vec<(Section, Vec<(String, &'static str)>)> {
+        let mut sections: Vec<(Section, Vec<(String, &'static str)>)> = Vec::new();
+        for (index, bindable) in BINDABLE.iter().enumerate() {
+            let keys: Vec<String> = self
+                .bindings
+                .iter()
+                .filter(|(_, i)| *i == index)
+                .map(|(sequence, _)| sequence_label(sequence))
+                .collect();
+            if keys.is_empty() {
+                continue;
+            }
+            let row = (keys.join(" / "), bindable.description);
+            match sections.last_mut() {
+                Some((section, rows)) if *section == bindable.section => rows.push(row),
+                _ => sections.push((bindable.section, vec![row])),
+            }
+        }
+        sections
+    }
+}
+
+/// Two sequences can't both be bound when one is a prefix of the other: the
+/// shorter would always fire first.
+fn overlaps(a: &[KeyChord], b: &[KeyChord]) -> bool {
+    a.starts_with(b) || b.starts_with(a)
+}
+
+fn sequence_label(sequence: &[KeyChord]) -> String {
+    let labels: Vec<String> = sequence.iter().map(KeyChord::label).collect();
+    // Plain characters read naturally run together (`gg`); anything else is spaced.
+    if sequence
+        .iter()
+        .all(|c| matches!(c.code, KeyCode::Char(ch) if ch != ' ') && c.modifiers.is_empty())
+    {
+        labels.concat()
+    } else {
+        labels.join(" ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(c: char) -> KeyEvent {
+        KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE)
+    }
+
+    fn keymap(config: &str) -> (Keymap, Vec<String>) {
+        Keymap::from_config(&toml::from_str(config).unwrap())
+    }
+
+    #[test]
+    fn defaults_cover_the_builtin_keys() {
+        let mut keymap = Keymap::default();
+        assert_eq!(keymap.resolve(press('j')), Some(Action::NavigateDown));
+        assert_eq!(keymap.resolve(press('G')), Some(Action::JumpToEnd));
+        assert_eq!(
+            keymap.resolve(KeyEvent::new(KeyCode::Char('d'), KeyModifiers::CONTROL)),
+            Some(Action::ScrollDown)
+        );
+        assert_eq!(
+            keymap.resolve(KeyEvent::new(KeyCode::BackTab, KeyModifiers::SHIFT)),
+            Some(Action::FocusPrevious)
+        );
+        assert_eq!(keymap.resolve(press('+')), Some(Action::GrowTree));
+        assert_eq!(keymap.resolve(press('x')), None);
+    }
+
+    #[test]
+    fn parses_chords_and_sequences() {
+        let chord = |s| KeyChord::parse(s).unwrap();
+        assert_eq!(
+            chord("Ctrl+Shift+x"),
+            KeyChord::new(KeyCode::Char('X'), KeyModifiers::CONTROL)
+        );
+        assert_eq!(chord("shift+tab"), chord("backtab"));
+        assert_eq!(
+            chord("ctrl++"),
+            KeyChord::new(KeyCode::Char('+'), KeyModifiers::CONTROL)
+        );
+        assert_eq!(
+            chord("+"),
+            KeyChord::new(KeyCode::Char('+'), KeyModifiers::NONE)
+        );
+        assert_eq!(
+            chord("F5"),
+            KeyChord::new(KeyCode::F(5), KeyModifiers::NONE)
+        );
+        assert_eq!(
+            parse_sequence("gg").unwrap(),
+            parse_sequence("g g").unwrap()
+        );
+        assert_eq!(parse_sequence("space x").unwrap().len(), 2);
+        assert!(parse_sequence("hyper+x").is_err());
+        assert!(parse_sequence("ctrl+nope").is_err());
+    }
+
+    #[test]
+    fn sequences_wait_for_their_last_key() {
+        let (mut keymap, errors) = keymap("jump_to_start = \"gg\"\n");
+        assert!(errors.is_empty());
+        assert_eq!(keymap.resolve(press('g')), None);
+        assert_eq!(keymap.pending_label().as_deref(), Some("g"));
+        assert_eq!(keymap.resolve(press('g')), Some(Action::JumpToStart));
+        assert_eq!(keymap.pending_label(), None);
+    }
+
+    #[test]
+    fn broken_sequence_replays_the_last_key() {
+        let (mut keymap, _) = keymap("jump_to_start = \"gg\"\n");
+        assert_eq!(keymap.resolve(press('g')), None);
+        assert_eq!(keymap.resolve(press('j')), Some(Action::NavigateDown));
+    }
+
+    #[test]
+    fn configured_keys_replace_defaults_and_win_conflicts() {
+        let (mut keymap, errors) = keymap("quit = \"ctrl+q\"\nrun_all = [\"r\", \"ctrl+r\"]\n");
+        assert!(errors.is_empty());
+        assert_eq!(keymap.resolve(press('q')), None);
+        assert_eq!(keymap.resolve(press('r')), Some(Action::RunAll));
+        assert_eq!(keymap.resolve(press('A')), None);
+        // `r` was taken from rerun_failed, which is left unbound.
+        assert!(
+            keymap
+                .help()
+                .iter()
+                .flat_map(|(_, rows)| rows)
+                .all(|(_, desc)| *desc != "rerun failed")
+        );
+    }
+
+    #[test]
+    fn empty_list_unbinds() {
+        let (mut keymap, errors) = keymap("toggle_coverage = []\n");
+        assert!(errors.is_empty());
+        assert_eq!(keymap.resolve(press('C')), None);
+    }
+
+    #[test]
+    fn reports_invalid_entries() {
+        let (mut keymap, errors) = keymap(
+            "launch = \"x\"\nquit = 3\nrun_all = \"ctrl+nope\"\nselect = \"x\"\nyank_path = \"x y\"\n",
+        );
+        assert_eq!(errors.len(), 4, "{errors:?}");
+        assert!(errors.iter().any(|e| e.contains("unknown action 'launch'")));
+        assert!(errors.iter().any(|e| e.contains("conflicts")));
+        // Valid entries still apply.
+        assert_eq!(keymap.resolve(press('x')), Some(Action::Select));
+    }
+
+    #[test]
+    fn help_lists_effective_keys() {
+        let (keymap, _) = keymap("jump_to_start = [\"gg\", \"home\"]\nquit = \"ctrl+q\"\n");
+        let help = keymap.help();
+        let rows: Vec<&(String, &str)> = help.iter().flat_map(|(_, rows)| rows).collect();
+        assert!(rows.contains(&&("gg / Home".to_string(), "jump to start")));
+        assert!(rows.contains(&&("Ctrl+q".to_string(), "quit")));
+        assert_eq!(help[0].0, Section::Navigation);
+        assert_eq!(help[1].0, Section::Actions);
+    }
+}
@@ -0,0 +1,174 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+/// Percentage a resize key moves a divider by.
+const STEP: u16 = 5;
+/// Dividers stay within this range so no panel disappears.
+const MIN_PCT: u16 = 15;
+const MAX_PCT: u16 = 85;
+/// Below this many columns `auto` stacking puts the panels on top of each other.
+const NARROW_WIDTH: u16 = 100;
+
+/// How the test panels (tree and failure list) and the output are arranged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Stacking {
+    /// Test panels in a column on the left, output on the right.
+    #[default]
+    Horizontal,
+    /// Everything full width: tree, failure list, then output.
+    Vertical,
+    /// Vertical on terminals narrower than 100 columns, horizontal otherwise.
+    Auto,
+}
+
+/// Panel arrangement, set in `[layout]` and changed at runtime with the layout
+/// keys. Runtime changes are saved to `.lens/layout.json`, which takes
+/// precedence over `lens.toml` on the next launch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PanelLayout {
+    pub stacking: Stacking,
+    /// Share of the width (or height, stacked vertically) for the test panels.
+    pub tests_pct: u16,
+    /// Share of the test panels' space for the tree, the rest is the failure list.
+    pub tree_pct: u16,
+    /// Output maximized over the other panels. Not persisted.
+    #[serde(skip)]
+    pub zoomed: bool,
+}
+
+impl Default for PanelLayout {
+    fn default() -> Self {
+        Self {
+            stacking: Stacking::Horizontal,
+            tests_pct: 40,
+            tree_pct: 60,
+            zoomed: false,
+        }
+    }
+}
+
+pub fn layout_path(workspace: &Path) -> PathBuf {
+    workspace.join(".lens").join("layout.json")
+}
+
+impl PanelLayout {
+    /// The saved layout of the workspace, or `configured` if none was saved or
+    /// it can't be read.
+    pub fn load(workspace: &Path, configured: PanelLayout) -> Self {
+        std::fs::read_to_string(layout_path(workspace))
+            .ok()
+            .and_then(|json| serde_json::from_str::<PanelLayout>(&json).ok())
+            .unwrap_or(configured)
+            .clamped()
+    }
+
+    pub fn save(&self, workspace: &Path) -> Result<()> {
+        let path = layout_path(workspace);
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("failed to create {}", dir.display()))?;
+        }
+        let json = serde_json::to_string(self)?;
+        std::fs::write(&path, json).with_context(|| format!("failed to write {}", path.display()))
+    }
+
+    fn clamped(mut self) -> Self {
+        self.tests_pct = self.tests_pct.clamp(MIN_PCT, MAX_PCT);
+        self.tree_pct = self.tree_pct.clamp(MIN_PCT, MAX_PCT);
+        self
+    }
+
+    /// Whether the panels are stacked on a terminal `width` columns wide.
+    pub fn vertical(&self, width: u16) -> bool {
+        match self.stacking {
+            Stacking::Horizontal => false,
+            Stacking::Vertical => true,
+            Stacking::Auto => width < NARROW_WIDTH,
+        }
+    }
+
+    /// Move the divider between the test panels and the output.
+    pub fn resize_tests(&mut self, grow: bool) {
+        self.tests_pct = step(self.tests_pct, grow);
+    }
+
+    /// Move the divider between the tree and the failure list.
+    pub fn resize_tree(&mut self, grow: bool) {
+        self.tree_pct = step(self.tree_pct, grow);
+    }
+
+    /// Switch between side-by-side and stacked panels, resolving `auto` to
+    /// whichever it isn't showing now.
+    pub fn toggle_stacking(&mut self, width: u16) {
+        self.stacking = if self.vertical(width) {
+            Stacking::Horizontal
+        } else {
+            Stacking::Vertical
+        };
+    }
+}
+
+fn step(pct: u16, grow: bool) -> u16 {
+    let pct = if grow {
+        pct + STEP
+    } else {
+        pct.saturating_sub(STEP)
+    };
+    pct.clamp(MIN_PCT, MAX_PCT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resizing_stops_at_the_limits() {
+        let mut layout = PanelLayout::default();
+        for _ in 0..20 {
+            layout.resize_tests(true);
+            layout.resize_tree(false);
+        }
+        assert_eq!((layout.tests_pct, layout.tree_pct), (MAX_PCT, MIN_PCT));
+    }
+
+    #[test]
+    fn auto_stacks_on_narrow_terminals_and_toggles_to_the_other() {
+        let mut layout = PanelLayout {
+            stacking: Stacking::Auto,
+            ..PanelLayout::default()
+        };
+        assert!(layout.vertical(80));
+        assert!(!layout.vertical(160));
+        layout.toggle_stacking(80);
+        assert_eq!(layout.stacking, Stacking::Horizontal);
+        layout.toggle_stacking(80);
+        assert_eq!(layout.stacking, Stacking::Vertical);
+    }
+
+    #[test]
+    fn saved_layout_overrides_the_configured_one() {
+        let dir = tempfile::tempdir().unwrap();
+        let configured: PanelLayout =
+            toml::from_str("stacking = \"auto\"\ntests_pct = 5\n").unwrap();
+        let loaded = PanelLayout::load(dir.path(), configured);
+        assert_eq!(loaded.stacking, Stacking::Auto);
+        assert_eq!(loaded.tests_pct, MIN_PCT);
+        assert_eq!(loaded.tree_pct, 60);
+
+        let saved = PanelLayout {
+            stacking: Stacking::Vertical,
+            tests_pct: 30,
+            zoomed: true,
+            ..PanelLayout::default()
+        };
+        saved.save(dir.path()).unwrap();
+        let loaded = PanelLayout::load(dir.path(), configured);
+        assert_eq!(loaded.stacking, Stacking::Vertical);
+        assert_eq!(loaded.tests_pct, 30);
+        assert!(!loaded.zoomed);
+    }
+}
@@ -6,13 +6,32 @@ use std::{
 use tokio::sync::mpsc;
 
 use crate::{
-    app::notifier::Notifier,
-    models::{NodeKind, RunSummary, TestTree},
+    app::{
+        coverage::Coverage,
+        history::History,
+        keymap::Keymap,
+        layout::PanelLayout,
+        mouse::{Click, PanelAreas},
+        notifier::Notifier,
+        runner_log::RunnerLog,
+        search::OutputSearch,
+    },
+    models::{NodeKind, Query, RunSummary, TestTree},
+    ui::theme::Theme,
 };
 
 pub mod actions;
+pub mod coverage;
 pub mod events;
+pub mod history;
+pub mod keymap;
+pub mod layout;
+pub mod mouse;
 pub mod notifier;
+pub mod runner_log;
+pub mod search;
+pub mod snapshot;
+pub mod source;
 
 pub use actions::{Action, handle_action, trigger_action};
 pub use events::{TestEvent, handle_test_event};
@@ -37,7 +56,31 @@ pub enum Panel {
 pub enum PendingRun {
     File(PathBuf),
     Files(Vec<PathBuf>),
-    Test { file: PathBuf, name: String },
+    Test {
+        file: PathBuf,
+        name: String,
+    },
+    /// Only the tests with these full names (` > `-joined) in `files`.
+    Tests {
+        files: Vec<PathBuf>,
+        names: Vec<String>,
+    },
+    UpdateSnapshots {
+        files: Vec<PathBuf>,
+        name: Option<String>,
+    },
+}
+
+/// A snapshot update waiting for the user to confirm it.
+#[derive(Debug)]
+pub struct SnapshotUpdate {
+    /// The tree node the update was requested for.
+    pub node_id: usize,
+    /// What the prompt names: a file path, test name or project.
+    pub label: String,
+    pub files: Vec<PathBuf>,
+    /// Restricts the update to one test or suite.
+    pub name: Option<String>,
 }
 
 pub struct App {
@@ -62,20 +105,49 @@ pub struct App {
     pub progress_total: usize,
     pub progress_done: usize,
     pub event_tx: mpsc::UnboundedSender<TestEvent>,
-    pub output_lines: Vec<String>,
+    pub runner_log: RunnerLog,
     pub pending_runs: Vec<PendingRun>,
     /// (file_path, line, column)
     pub pending_editor: Option<(PathBuf, Option<u32>, Option<u32>)>,
+    /// (node id, index) of the attachment opened last, so opening again on the
+    /// same test moves on to its next one.
+    pub last_attachment: Option<(usize, usize)>,
     pub should_quit: bool,
     pub filter_active: bool,
     pub filter: tui_input::Input,
+    /// List filter matches flat, best fuzzy score first, instead of as a tree.
+    pub filter_flat: bool,
+    /// Show the console output of the selected test's whole file rather than
+    /// just the test's own.
+    pub show_file_console: bool,
+    pub output_search: OutputSearch,
     pub discovering: bool,
     pub spinner_tick: usize,
     pub summary: Option<RunSummary>,
+    /// `summary` was restored from the previous session rather than produced by a run.
+    pub summary_restored: bool,
     pub run_start: Option<std::time::Instant>,
     pub project_name: Option<String>,
     pub notifier: Notifier,
     pub show_help: bool,
+    pub history: History,
+    pub show_flaky: bool,
+    pub flaky_selected: usize,
+    pub show_runner_log: bool,
+    /// Runner log lines scrolled back from the newest.
+    pub runner_log_scroll: usize,
+    pub confirm_snapshot_update: Option<SnapshotUpdate>,
+    /// Runs collect coverage; also shows the coverage column and panel.
+    pub coverage_enabled: bool,
+    pub coverage: Coverage,
+    /// Effective key bindings, defaults overridden by `[keys]`.
+    pub keymap: Keymap,
+    /// Active color theme, a built-in overridden by `[theme]`.
+    pub theme: Theme,
+    /// Panel positions from the last frame, for mouse hit-testing.
+    pub areas: PanelAreas,
+    pub last_click: Option<Click>,
+    pub layout: PanelLayout,
 }
 
 impl App {
@@ -102,19 +174,37 @@ impl App {
             progress_total: 0,
             progress_done: 0,
             event_tx,
-            output_lines: Vec::new(),
+            runner_log: RunnerLog::default(),
             pending_runs: Vec::new(),
             pending_editor: None,
+            last_attachment: None,
             should_quit: false,
             filter_active: false,
             filter: tui_input::Input::default(),
+            filter_flat: false,
+            show_file_console: false,
+            output_search: OutputSearch::default(),
             discovering: true,
             spinner_tick: 0,
             summary: None,
+            summary_restored: false,
             run_start: None,
             project_name: None,
             notifier: Notifier::new(),
             show_help: false,
+            history: History::default(),
+            show_flaky: false,
+            flaky_selected: 0,
+            show_runner_log: false,
+            runner_log_scroll: 0,
+            confirm_snapshot_update: None,
+            coverage_enabled: false,
+            coverage: Coverage::default(),
+            keymap: Keymap::default(),
+            theme: Theme::default(),
+            areas: PanelAreas::default(),
+            last_click: None,
+            layout: PanelLayout::default(),
         };
         (app, event_rx)
     }
@@ -125,14 +215,23 @@ impl App {
         self.watched_ids = compute_watched_ids(&self.tree, &self.workspace, &self.watch_scope);
     }
 
-    /// Returns visible nodes respecting the current filter query.
+    /// Returns visible nodes respecting the current filter query: the filtered
+    /// tree, or the ranked matches at depth 0 in flat mode.
     pub fn visible_tree_nodes(&self) -> Vec<(usize, usize)> {
         let filter_query = self.filter.value();
 
         if filter_query.is_empty() {
-            self.tree.visible_nodes()
+            return self.tree.visible_nodes();
+        }
+        let query = Query::parse(filter_query);
+        if self.filter_flat {
+            self.tree
+                .ranked_matches(&query)
+                .into_iter()
+                .map(|id| (id, 0))
+                .collect()
         } else {
-            self.tree.visible_nodes_filtered(filter_query)
+            self.tree.visible_nodes_filtered(&query)
         }
     }
 
@@ -215,7 +314,9 @@ fn compute_watched_ids(tree: &TestTree, workspace: &Path, scope: &WatchScope) ->
                 while let Some(id) = stack.pop() {
                     let Some(node) = tree.get(id) else { continue };
                     if (node.kind == NodeKind::Test || node.kind == NodeKind::Suite)
-                        && node.name == *watch_name
+                        && tree
+                            .file_and_full_name(id)
+                            .is_some_and(|(_, name)| name == *watch_name)
                     {
                         collect_subtree(tree, id, &mut ids);
                         // Add every ancestor suite between this node and the file.
@@ -237,12 +338,9 @@ fn compute_watched_ids(tree: &TestTree, workspace: &Path, scope: &WatchScope) ->
     ids
 }
 
-fn find_file_node(tree: &TestTree, _workspace: &Path, scope_path: &Path) -> Option<usize> {
-    let filename = scope_path
-        .file_name()
-        .and_then(|f| f.to_str())
-        .unwrap_or_default();
-    tree.find_file_by_filename(filename)
+fn find_file_node(tree: &TestTree, workspace: &Path, scope_path: &Path) -> Option<usize> {
+    let relative = scope_path.strip_prefix(workspace).unwrap_or(scope_path);
+    tree.find_file_by_path(relative)
 }
 
 fn collect_subtree(tree: &TestTree, id: usize, ids: &mut HashSet<usize>) {
@@ -0,0 +1,329 @@
+use std::time::{Duration, Instant};
+
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::{Position, Rect};
+
+use super::{Action, App, Panel};
+use crate::models::NodeKind;
+
+/// Two clicks on the same row within this window make a double-click.
+const DOUBLE_CLICK: Duration = Duration::from_millis(400);
+/// Rows moved per scroll-wheel notch.
+const SCROLL_LINES: usize = 3;
+
+/// Where each panel was last drawn, bordered area included, so mouse events
+/// can be mapped back to rows. Empty until the first frame.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct PanelAreas {
+    /// The whole frame.
+    pub screen: Rect,
+    /// The tree list, below the filter box when it's shown.
+    pub tree: Rect,
+    pub failed: Rect,
+    pub output: Rect,
+}
+
+impl PanelAreas {
+    fn panel_at(&self, column: u16, row: u16) -> Option<Panel> {
+        let position = Position::new(column, row);
+        [
+            (self.tree, Panel::TestTree),
+            (self.failed, Panel::FailedList),
+            (self.output, Panel::Output),
+        ]
+        .into_iter()
+        .find(|(area, _)| area.contains(position))
+        .map(|(_, panel)| panel)
+    }
+}
+
+/// The last left click, to detect double-clicks.
+#[derive(Debug, Clone, Copy)]
+pub struct Click {
+    panel: Panel,
+    index: usize,
+    at: Instant,
+}
+
+/// Apply a mouse event: clicks focus the panel under the cursor and select
+/// the row, a click on a tree chevron toggles it and the wheel scrolls the
+/// panel under the cursor. A double-click on a tree row returns the action
+/// that runs it, for the caller to dispatch alongside key actions.
+pub fn handle_mouse(app: &mut App, mouse: MouseEvent) -> Option<Action> {
+    // Overlays are keyboard-driven; leave them to the keys.
+    if app.show_help
+        || app.show_flaky
+        || app.show_runner_log
+        || app.confirm_snapshot_update.is_some()
+    {
+        return None;
+    }
+    let panel = app.areas.panel_at(mouse.column, mouse.row)?;
+    match mouse.kind {
+        MouseEventKind::Down(MouseButton::Left) => click(app, panel, mouse.column, mouse.row),
+        MouseEventKind::ScrollUp => {
+            scroll(app, panel, false);
+            None
+        }
+        MouseEventKind::ScrollDown => {
+            scroll(app, panel, true);
+            None
+        }
+        _ => None,
+    }
+}
+
+fn click(app: &mut App, panel: Panel, column: u16, row: u16) -> Option<Action> {
+    app.filter_active = false;
+    app.output_search.active = false;
+    app.active_panel = panel;
+
+    let area = match panel {
+        Panel::TestTree => app.areas.tree,
+        Panel::FailedList => app.areas.failed,
+        Panel::Output => {
+            app.last_click = None;
+            return None;
+        }
+    };
+    // Rows start inside the top border.
+    let line = (row.checked_sub(area.y + 1)?) as usize;
+    if row + 1 >= area.bottom() {
+        return None;
+    }
+
+    let index = match panel {
+        Panel::TestTree => {
+            let index = app.tree_scroll_offset + line;
+            let &(node_id, depth) = app.visible_tree_nodes().get(index)?;
+            if app.selected_tree_index != index {
+                app.output_scroll_offset = 0;
+            }
+            app.selected_tree_index = index;
+            if on_chevron(app, node_id, depth, column.saturating_sub(area.x + 1)) {
+                app.tree.toggle_expanded(node_id);
+                app.last_click = None;
+                return None;
+            }
+            index
+        }
+        _ => {
+            let index = app.failed_scroll_offset + line;
+            if index >= app.tree.failed_nodes().len() {
+                return None;
+            }
+            if app.selected_failed_index != index {
+                app.output_scroll_offset = 0;
+            }
+            app.selected_failed_index = index;
+            index
+        }
+    };
+
+    let now = Instant::now();
+    let double = app.last_click.is_some_and(|last| {
+        last.panel == panel && last.index == index && now.duration_since(last.at) <= DOUBLE_CLICK
+    });
+    if double {
+        app.last_click = None;
+        return (panel == Panel::TestTree).then_some(Action::Select);
+    }
+    app.last_click = Some(Click {
+        panel,
+        index,
+        at: now,
+    });
+    None
+}
+
+/// Whether `x` (relative to the list's inner area) falls on the node's `▶`/`▼`.
+/// The chevron follows the branch prefix, three columns per level.
+fn on_chevron(app: &App, node_id: usize, depth: usize, x: u16) -> bool {
+    let Some(node) = app.tree.get(node_id) else {
+        return false;
+    };
+    let expandable = matches!(
+        node.kind,
+        NodeKind::Project | NodeKind::File | NodeKind::Suite
+    );
+    let start = (depth * 3) as u16;
+    expandable && !node.children.is_empty() && (start..start + 2).contains(&x)
+}
+
+fn scroll(app: &mut App, panel: Panel, down: bool) {
+    match panel {
+        Panel::TestTree => {
+            let max = app
+                .visible_tree_nodes()
+                .len()
+                .saturating_sub(app.tree_viewport_height);
+            app.tree_scroll_offset = step(app.tree_scroll_offset, down).min(max);
+        }
+        Panel::FailedList => {
+            let max = app
+                .tree
+                .failed_nodes()
+                .len()
+                .saturating_sub(app.failed_viewport_height);
+            app.failed_scroll_offset = step(app.failed_scroll_offset, down).min(max);
+        }
+        // Clamped to the content when the panel is drawn.
+        Panel::Output => {
+            app.output_scroll_offset = step(app.output_scroll_offset as usize, down) as u16;
+        }
+    }
+}
+
+fn step(offset: usize, down: bool) -> usize {
+    if down {
+        offset + SCROLL_LINES
+    } else {
+        offset.saturating_sub(SCROLL_LINES)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use crossterm::event::KeyModifiers;
+
+    use super::*;
+    use crate::app::TestEvent;
+
+    /// `src/math.test.ts` with two tests, fully expanded, with the tree drawn
+    /// in a 40×10 panel at the origin (row `i + 1` shows visible node `i`).
+    fn app() -> App {
+        let (mut app, _rx) = App::new(PathBuf::from("/ws"));
+        crate::app::handle_test_event(
+            &mut app,
+            TestEvent::DiscoveryComplete {
+                files: vec!["src/math.test.ts".into()],
+                projects: Vec::new(),
+            },
+        );
+        for name in ["adds", "subtracts"] {
+            crate::app::handle_test_event(
+                &mut app,
+                TestEvent::TestFinished {
+                    file: "/ws/src/math.test.ts".into(),
+                    name: name.into(),
+                    result: Box::new(crate::models::TestResult {
+                        status: crate::models::TestStatus::Passed,
+                        duration_ms: Some(1),
+                        failures: vec![],
+                    }),
+                    location: None,
+                },
+            );
+        }
+        app.tree.expand_all();
+        app.areas = PanelAreas {
+            screen: Rect::new(0, 0, 80, 16),
+            tree: Rect::new(0, 0, 40, 10),
+            failed: Rect::new(0, 10, 40, 5),
+            output: Rect::new(40, 0, 40, 15),
+        };
+        app.tree_viewport_height = 8;
+        app
+    }
+
+    /// Visible index of the file node.
+    fn file_index(app: &App) -> usize {
+        app.visible_tree_nodes()
+            .iter()
+            .position(|&(id, _)| app.tree.get(id).unwrap().kind == NodeKind::File)
+            .unwrap()
+    }
+
+    fn mouse(kind: MouseEventKind, column: u16, row: u16) -> MouseEvent {
+        MouseEvent {
+            kind,
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        }
+    }
+
+    fn left_click(app: &mut App, column: u16, row: u16) -> Option<Action> {
+        handle_mouse(
+            app,
+            mouse(MouseEventKind::Down(MouseButton::Left), column, row),
+        )
+    }
+
+    #[test]
+    fn click_focuses_and_selects_the_row() {
+        let mut app = app();
+        app.active_panel = Panel::Output;
+        assert_eq!(left_click(&mut app, 20, 2), None);
+        assert_eq!(app.active_panel, Panel::TestTree);
+        assert_eq!(app.selected_tree_index, 1);
+    }
+
+    #[test]
+    fn click_on_border_or_below_the_rows_keeps_selection() {
+        let mut app = app();
+        app.selected_tree_index = 1;
+        left_click(&mut app, 20, 0);
+        left_click(&mut app, 20, 8);
+        left_click(&mut app, 20, 9);
+        assert_eq!(app.selected_tree_index, 1);
+    }
+
+    #[test]
+    fn click_on_chevron_toggles_the_node() {
+        let mut app = app();
+        let index = file_index(&app);
+        let (file_id, depth) = app.visible_tree_nodes()[index];
+        let before = app.visible_tree_nodes().len();
+        let (column, row) = (1 + depth as u16 * 3, index as u16 + 1);
+        left_click(&mut app, column, row);
+        assert!(!app.tree.get(file_id).unwrap().expanded);
+        assert_eq!(app.visible_tree_nodes().len(), before - 2);
+        left_click(&mut app, column, row);
+        assert!(app.tree.get(file_id).unwrap().expanded);
+        // Clicking the name only selects.
+        left_click(&mut app, column + 6, row);
+        assert!(app.tree.get(file_id).unwrap().expanded);
+    }
+
+    #[test]
+    fn double_click_runs_the_row() {
+        let mut app = app();
+        let row = file_index(&app) as u16 + 2;
+        assert_eq!(left_click(&mut app, 30, row), None);
+        assert_eq!(left_click(&mut app, 30, row), Some(Action::Select));
+        // A click on another row starts over.
+        assert_eq!(left_click(&mut app, 30, row), None);
+        assert_eq!(left_click(&mut app, 30, row + 1), None);
+    }
+
+    #[test]
+    fn click_on_output_focuses_it() {
+        let mut app = app();
+        left_click(&mut app, 60, 5);
+        assert_eq!(app.active_panel, Panel::Output);
+    }
+
+    #[test]
+    fn wheel_scrolls_the_panel_under_the_cursor() {
+        let mut app = app();
+        handle_mouse(&mut app, mouse(MouseEventKind::ScrollDown, 60, 5));
+        assert_eq!(app.output_scroll_offset, 3);
+        assert_eq!(app.active_panel, Panel::TestTree);
+        // The tree fits its viewport, so there is nothing to scroll.
+        handle_mouse(&mut app, mouse(MouseEventKind::ScrollDown, 5, 5));
+        assert_eq!(app.tree_scroll_offset, 0);
+        handle_mouse(&mut app, mouse(MouseEventKind::ScrollUp, 60, 5));
+        assert_eq!(app.output_scroll_offset, 0);
+    }
+
+    #[test]
+    fn overlays_ignore_the_mouse() {
+        let mut app = app();
+        app.show_help = true;
+        left_click(&mut app, 20, 2);
+        assert_eq!(app.selected_tree_index, 0);
+    }
+}
@@ -0,0 +1,116 @@
+use std::collections::VecDeque;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Past this many lines the oldest are dropped, so long watch sessions stay bounded.
+const MAX_LINES: usize = 5000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogKind {
+    /// A runner process was spawned; the text is its command line.
+    Command,
+    /// Runner output outside the reporter protocol: stderr, banners, reporter notes.
+    Output,
+    Error,
+    /// Messages from lens itself.
+    Info,
+}
+
+#[derive(Debug, Clone)]
+pub struct LogLine {
+    /// When it was logged, `HH:MM:SS.mmm` in the local timezone. Formatted
+    /// once here rather than on every frame the overlay draws.
+    pub clock: String,
+    pub kind: LogKind,
+    /// As the runner printed it, ANSI escapes included.
+    pub text: String,
+}
+
+/// What the runners printed and the commands that started them, for the
+/// runner log overlay. Kept across runs, so a crash stays visible after the
+/// next run starts.
+#[derive(Debug, Default)]
+pub struct RunnerLog {
+    lines: VecDeque<LogLine>,
+}
+
+impl RunnerLog {
+    pub fn push(&mut self, kind: LogKind, text: impl Into<String>) {
+        if self.lines.len() == MAX_LINES {
+            self.lines.pop_front();
+        }
+        self.lines.push_back(LogLine {
+            clock: clock(SystemTime::now()),
+            kind,
+            text: text.into(),
+        });
+    }
+
+    pub fn lines(&self) -> impl DoubleEndedIterator<Item = &LogLine> + ExactSizeIterator {
+        self.lines.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.lines.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+}
+
+/// `HH:MM:SS.mmm` in the local timezone.
+fn clock(time: SystemTime) -> String {
+    let since_epoch = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let secs = since_epoch.as_secs() as i64;
+    let of_day = (secs + utc_offset(secs)).rem_euclid(86_400);
+    format!(
+        "{:02}:{:02}:{:02}.{:03}",
+        of_day / 3600,
+        of_day / 60 % 60,
+        of_day % 60,
+        since_epoch.subsec_millis()
+    )
+}
+
+/// Seconds the local timezone is ahead of UTC at `secs` past the epoch.
+#[cfg(unix)]
+fn utc_offset(secs: i64) -> i64 {
+    let time = secs as libc::time_t;
+    // SAFETY: `localtime_r` only writes to the `tm` it is given.
+    let mut tm: libc::tm = unsafe { std::mem::zeroed() };
+    if unsafe { libc::localtime_r(&time, &mut tm) }.is_null() {
+        return 0;
+    }
+    // `c_long` is only 32 bits wide on some targets.
+    #[allow(clippy::useless_conversion)]
+    i64::from(tm.tm_gmtoff)
+}
+
+#[cfg(not(unix))]
+fn utc_offset(_secs: i64) -> i64 {
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drops_the_oldest_lines_past_the_limit() {
+        let mut log = RunnerLog::default();
+        for i in 0..MAX_LINES + 2 {
+            log.push(LogKind::Output, i.to_string());
+        }
+        assert_eq!(log.len(), MAX_LINES);
+        assert_eq!(log.lines().next().unwrap().text, "2");
+    }
+
+    #[test]
+    fn lines_are_stamped_when_logged() {
+        let mut log = RunnerLog::default();
+        log.push(LogKind::Info, "hi");
+        let clock = &log.lines().next().unwrap().clock;
+        assert_eq!(clock.len(), "HH:MM:SS.mmm".len());
+        assert_eq!(clock.matches(':').count(), 2);
+    }
+}
@@ -0,0 +1,118 @@
+use std::ops::Range;
+
+/// Incremental search in the output panel. Matches are found when the panel is
+/// drawn, since that's where its text is built; the counts from the last frame
+/// are kept here so `n`/`N` can step through them.
+#[derive(Debug, Default)]
+pub struct OutputSearch {
+    pub input: tui_input::Input,
+    /// The query is being typed.
+    pub active: bool,
+    /// Index of the current match.
+    pub current: usize,
+    /// Matches in the output as last drawn.
+    pub count: usize,
+    /// Scroll a match into view on the next frame.
+    pub reveal: Option<Reveal>,
+}
+
+/// Which match the next frame scrolls to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reveal {
+    /// The first one at or below the top of the panel, as the query is typed.
+    FromTop,
+    /// The current one, after stepping with `n`/`N`.
+    Current,
+}
+
+impl OutputSearch {
+    pub fn query(&self) -> &str {
+        self.input.value()
+    }
+
+    /// Move to the next (or previous) match, wrapping around.
+    pub fn step(&mut self, forward: bool) {
+        if self.query().is_empty() || self.count == 0 {
+            return;
+        }
+        self.current = if forward {
+            (self.current + 1) % self.count
+        } else {
+            (self.current + self.count - 1) % self.count
+        };
+        self.reveal = Some(Reveal::Current);
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// Byte ranges of the non-overlapping occurrences of `query` in `text`. Case is
+/// ignored unless the query has an uppercase letter.
+pub fn find_matches(text: &str, query: &str) -> Vec<Range<usize>> {
+    if query.is_empty() {
+        return Vec::new();
+    }
+    let ignore_case = !query.chars().any(char::is_uppercase);
+    let mut matches = Vec::new();
+    let mut start = 0;
+    while let Some(c) = text[start..].chars().next() {
+        match match_len(&text[start..], query, ignore_case) {
+            Some(len) => {
+                matches.push(start..start + len);
+                start += len;
+            }
+            None => start += c.len_utf8(),
+        }
+    }
+    matches
+}
+
+/// Length in bytes of `query` at the start of `text`, if it's there.
+fn match_len(text: &str, query: &str, ignore_case: bool) -> Option<usize> {
+    let mut chars = text.chars();
+    let mut len = 0;
+    for q in query.chars() {
+        let c = chars.next()?;
+        let same = if ignore_case {
+            c.to_lowercase().eq(q.to_lowercase())
+        } else {
+            c == q
+        };
+        if !same {
+            return None;
+        }
+        len += c.len_utf8();
+    }
+    Some(len)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_every_occurrence_with_smart_case() {
+        assert_eq!(find_matches("Error: error", "error"), [0..5, 7..12]);
+        let capitalized = find_matches("Error: error", "Error");
+        assert_eq!((capitalized.len(), &capitalized[0]), (1, &(0..5)));
+        assert_eq!(find_matches("aaaa", "aa"), [0..2, 2..4]);
+        assert_eq!(find_matches("héllo Héllo", "hé"), [0..3, 7..10]);
+        assert!(find_matches("abc", "").is_empty());
+    }
+
+    #[test]
+    fn step_wraps_around() {
+        let mut search = OutputSearch {
+            input: "x".into(),
+            count: 3,
+            ..OutputSearch::default()
+        };
+        search.step(false);
+        assert_eq!(search.current, 2);
+        search.step(true);
+        assert_eq!(search.current, 0);
+        assert_eq!(search.reveal, Some(Reveal::Current));
+    }
+}
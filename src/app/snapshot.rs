@@ -0,0 +1,231 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use super::App;
+use super::events::find_or_create_test_node;
+use crate::models::{RunSummary, TestResult};
+use crate::report;
+
+/// Bumped whenever the on-disk layout changes; older snapshots are ignored.
+const SNAPSHOT_VERSION: u32 = 2;
+
+/// Results of the last run, persisted to `.lens/last-run.json`.
+#[derive(Debug, Serialize, Deserialize)]
+struct Snapshot {
+    version: u32,
+    summary: Option<RunSummary>,
+    files: Vec<FileSnapshot>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct FileSnapshot {
+    /// Workspace-relative path.
+    path: String,
+    tests: Vec<TestSnapshot>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct TestSnapshot {
+    /// Suite and test names joined with ` > `.
+    name: String,
+    result: TestResult,
+    location: Option<(u32, u32)>,
+}
+
+pub fn snapshot_path(workspace: &Path) -> PathBuf {
+    workspace.join(".lens").join("last-run.json")
+}
+
+/// Write every test that has a result to the workspace snapshot file.
+pub fn save(app: &App) -> Result<()> {
+    let files = report::collect(&app.tree)
+        .into_iter()
+        .map(|file| FileSnapshot {
+            path: file.path,
+            tests: file
+                .tests
+                .into_iter()
+                .filter(|t| t.status.is_terminal())
+                .map(|t| TestSnapshot {
+                    name: t.name,
+                    result: TestResult {
+                        status: t.status,
+                        duration_ms: t.duration_ms,
+                        failures: t.failures.to_vec(),
+                    },
+                    location: t.location,
+                })
+                .collect(),
+        })
+        .filter(|f: &FileSnapshot| !f.tests.is_empty())
+        .collect();
+    let snapshot = Snapshot {
+        version: SNAPSHOT_VERSION,
+        summary: app.summary.clone(),
+        files,
+    };
+
+    let path = snapshot_path(&app.workspace);
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("failed to create {}", dir.display()))?;
+    }
+    let json = serde_json::to_string(&snapshot)?;
+    std::fs::write(&path, json).with_context(|| format!("failed to write {}", path.display()))
+}
+
+/// Merge the last snapshot into the discovered tree, marking merged tests as restored.
+/// Files that are no longer discovered are skipped, and tests that already have a
+/// fresh result are left alone. Returns the number of restored tests.
+pub fn restore(app: &mut App) -> Result<usize> {
+    let path = snapshot_path(&app.workspace);
+    let json = match std::fs::read_to_string(&path) {
+        Ok(json) => json,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+        Err(e) => return Err(e).with_context(|| format!("failed to read {}", path.display())),
+    };
+    let snapshot: Snapshot =
+        serde_json::from_str(&json).with_context(|| format!("invalid {}", path.display()))?;
+    if snapshot.version != SNAPSHOT_VERSION {
+        return Ok(0);
+    }
+    Ok(merge(app, snapshot))
+}
+
+fn merge(app: &mut App, snapshot: Snapshot) -> usize {
+    let mut restored = 0;
+    for file in snapshot.files {
+        let Some(file_id) = app.tree.find_file_by_path(Path::new(&file.path)) else {
+            continue;
+        };
+        for test in file.tests {
+            let test_id = find_or_create_test_node(app, file_id, &test.name);
+            if app.tree.get(test_id).is_some_and(|n| n.result.is_some()) {
+                continue;
+            }
+            app.tree.update_result(test_id, test.result);
+            if let Some(node) = app.tree.get_mut(test_id) {
+                node.location = test.location;
+                node.restored = true;
+            }
+            restored += 1;
+        }
+    }
+    if restored > 0 && app.summary.is_none() {
+        app.summary = snapshot.summary;
+        app.summary_restored = app.summary.is_some();
+    }
+    restored
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::{TestEvent, handle_test_event};
+    use crate::models::{FailureOutput, TestStatus};
+
+    fn discovered_app(workspace: &Path, files: &[&str]) -> App {
+        let (mut app, _rx) = App::new(workspace.to_path_buf());
+        handle_test_event(
+            &mut app,
+            TestEvent::DiscoveryComplete {
+                files: files.iter().map(|f| f.to_string()).collect(),
+                projects: Vec::new(),
+            },
+        );
+        app
+    }
+
+    fn finish(app: &mut App, file: &str, name: &str, status: TestStatus) {
+        let failures = (status == TestStatus::Failed)
+            .then(|| FailureOutput {
+                message: "boom".into(),
+                expected: None,
+                actual: None,
+                expected_parsed: None,
+                actual_parsed: None,
+                diff: None,
+                source_snippet: None,
+                stack_trace: None,
+                attachments: Vec::new(),
+            })
+            .into_iter()
+            .collect();
+        handle_test_event(
+            app,
+            TestEvent::TestFinished {
+                file: file.to_string(),
+                name: name.to_string(),
+                result: Box::new(TestResult {
+                    status,
+                    duration_ms: Some(7),
+                    failures,
+                }),
+                location: Some((4, 3)),
+            },
+        );
+    }
+
+    #[test]
+    fn saved_results_are_restored_as_stale() {
+        let dir = tempfile::tempdir().unwrap();
+        let files = ["src/a.test.ts", "src/b.test.ts"];
+
+        let mut app = discovered_app(dir.path(), &files);
+        finish(&mut app, "src/a.test.ts", "math > adds", TestStatus::Passed);
+        finish(&mut app, "src/b.test.ts", "breaks", TestStatus::Failed);
+        save(&app).unwrap();
+
+        let mut app = discovered_app(dir.path(), &files);
+        assert_eq!(restore(&mut app).unwrap(), 2);
+
+        let failed = app.tree.failed_nodes();
+        assert_eq!(failed.len(), 1);
+        let node = app.tree.get(failed[0]).unwrap();
+        assert_eq!(node.name, "breaks");
+        assert!(node.restored);
+        assert_eq!(node.location, Some((4, 3)));
+        let failure = node.result.as_ref().unwrap().failure().unwrap();
+        assert_eq!(failure.message, "boom");
+    }
+
+    #[test]
+    fn restore_skips_files_that_are_no_longer_discovered() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = discovered_app(dir.path(), &["src/gone.test.ts"]);
+        finish(&mut app, "src/gone.test.ts", "old", TestStatus::Failed);
+        save(&app).unwrap();
+
+        let mut app = discovered_app(dir.path(), &["src/a.test.ts"]);
+        assert_eq!(restore(&mut app).unwrap(), 0);
+        assert!(app.tree.failed_nodes().is_empty());
+    }
+
+    #[test]
+    fn restore_without_snapshot_is_a_no_op() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = discovered_app(dir.path(), &["src/a.test.ts"]);
+        assert_eq!(restore(&mut app).unwrap(), 0);
+    }
+
+    #[test]
+    fn fresh_result_clears_restored_marker() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = discovered_app(dir.path(), &["src/a.test.ts"]);
+        finish(&mut app, "src/a.test.ts", "flaky", TestStatus::Failed);
+        save(&app).unwrap();
+
+        let mut app = discovered_app(dir.path(), &["src/a.test.ts"]);
+        restore(&mut app).unwrap();
+        finish(&mut app, "src/a.test.ts", "flaky", TestStatus::Passed);
+
+        let id = app
+            .tree
+            .find_file_by_path(Path::new("src/a.test.ts"))
+            .and_then(|f| app.tree.find_child_by_name(f, "flaky"))
+            .unwrap();
+        assert!(!app.tree.get(id).unwrap().restored);
+    }
+}
@@ -0,0 +1,210 @@
+use std::path::{Path, PathBuf};
+
+use crate::models::TestResult;
+
+/// Lines shown on each side of the failing line.
+const CONTEXT_LINES: u32 = 3;
+
+/// A `path:line[:column]` location parsed from a stack frame.
+#[derive(Debug, PartialEq)]
+pub struct StackFrame {
+    pub path: PathBuf,
+    pub line: u32,
+    pub column: Option<u32>,
+}
+
+/// Find the first stack frame that points at user code, skipping
+/// `node_modules` and node internals.
+pub fn first_user_frame(stack: &str) -> Option<StackFrame> {
+    stack.split_whitespace().find_map(|segment| {
+        let s = segment.trim_matches(|c| c == '(' || c == ')');
+        let s = s.strip_prefix("file://").unwrap_or(s);
+        if s.contains("node_modules") || s.starts_with("node:") {
+            return None;
+        }
+
+        let parts: Vec<&str> = s.rsplitn(3, ':').collect();
+        let (path, line, column) = match parts.as_slice() {
+            [col, line, path] => (*path, line.parse::<u32>().ok()?, col.parse::<u32>().ok()),
+            [line, path] => (*path, line.parse::<u32>().ok()?, None),
+            _ => return None,
+        };
+        if path.is_empty() || !path.contains('.') {
+            return None;
+        }
+        Some(StackFrame {
+            path: PathBuf::from(path),
+            line,
+            column,
+        })
+    })
+}
+
+/// The user frame of each of `result`'s errors that has one and no
+/// `source_snippet` yet, by index. Relative frame paths are resolved against
+/// the workspace.
+pub fn snippet_frames(workspace: &Path, result: &TestResult) -> Vec<(usize, StackFrame)> {
+    result
+        .failures
+        .iter()
+        .enumerate()
+        .filter(|(_, failure)| failure.source_snippet.is_none())
+        .filter_map(|(i, failure)| {
+            let frame = failure.stack_trace.as_deref().and_then(first_user_frame)?;
+            Some((
+                i,
+                StackFrame {
+                    path: workspace.join(&frame.path),
+                    ..frame
+                },
+            ))
+        })
+        .collect()
+}
+
+/// A code frame for each of `frames` whose file can be read. This reads the
+/// files, so it belongs off the UI thread.
+pub fn read_snippets(frames: &[(usize, StackFrame)]) -> Vec<(usize, String)> {
+    frames
+        .iter()
+        .filter_map(|(i, frame)| {
+            let source = std::fs::read_to_string(&frame.path).ok()?;
+            Some((*i, build_snippet(&source, frame.line, frame.column)?))
+        })
+        .collect()
+}
+
+/// Render a code frame around `line` (1-based):
+///
+/// ```text
+///   11 | const a = 1;
+/// > 12 | expect(a).toBe(2);
+///      |           ^
+///   13 | });
+/// ```
+///
+/// The caret row is omitted when the column is unknown.
+pub fn build_snippet(source: &str, line: u32, column: Option<u32>) -> Option<String> {
+    let lines: Vec<&str> = source.lines().collect();
+    if line == 0 || line as usize > lines.len() {
+        return None;
+    }
+    let first = line.saturating_sub(CONTEXT_LINES).max(1);
+    let last = (line + CONTEXT_LINES).min(lines.len() as u32);
+    let width = last.to_string().len();
+
+    let mut out = Vec::new();
+    for n in first..=last {
+        let code = lines[n as usize - 1].replace('\t', "  ");
+        let marker = if n == line { '>' } else { ' ' };
+        out.push(format!("{marker} {n:>width$} | {code}"));
+        if n == line
+            && let Some(col) = column
+        {
+            // Tabs were expanded to two spaces above; keep the caret aligned.
+            let prefix = lines[n as usize - 1]
+                .chars()
+                .take(col.saturating_sub(1) as usize)
+                .map(|c| if c == '\t' { 2 } else { 1 })
+                .sum::<usize>();
+            out.push(format!("  {:width$} | {}^", "", " ".repeat(prefix)));
+        }
+    }
+    Some(out.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_user_frame_skips_node_modules() {
+        let stack = "AssertionError: expected 1 to be 2\n\
+            at node_modules/@vitest/expect/dist/index.js:10:3\n\
+            at /ws/src/math.test.ts:12:15\n\
+            at node:internal/process/task_queues:95:5";
+        assert_eq!(
+            first_user_frame(stack),
+            Some(StackFrame {
+                path: PathBuf::from("/ws/src/math.test.ts"),
+                line: 12,
+                column: Some(15),
+            })
+        );
+    }
+
+    #[test]
+    fn first_user_frame_handles_parens_and_file_urls() {
+        let stack = "at Object.<anonymous> (file:///ws/a.spec.ts:4:7)";
+        let frame = first_user_frame(stack).unwrap();
+        assert_eq!(frame.path, PathBuf::from("/ws/a.spec.ts"));
+        assert_eq!((frame.line, frame.column), (4, Some(7)));
+    }
+
+    #[test]
+    fn first_user_frame_without_column() {
+        let frame = first_user_frame("at src/a.test.ts:9").unwrap();
+        assert_eq!(frame.path, PathBuf::from("src/a.test.ts"));
+        assert_eq!((frame.line, frame.column), (9, None));
+    }
+
+    #[test]
+    fn first_user_frame_ignores_message_text() {
+        assert_eq!(first_user_frame("expected: 1 received: 2"), None);
+    }
+
+    #[test]
+    fn snippet_marks_line_and_column() {
+        let source = (1..=10)
+            .map(|n| format!("line{n}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let snippet = build_snippet(&source, 5, Some(3)).unwrap();
+        let lines: Vec<&str> = snippet.lines().collect();
+        assert_eq!(lines.first(), Some(&"  2 | line2"));
+        assert!(lines.contains(&"> 5 | line5"));
+        assert!(lines.contains(&"    |   ^"));
+        assert_eq!(lines.last(), Some(&"  8 | line8"));
+    }
+
+    #[test]
+    fn snippet_clamps_to_file_bounds() {
+        let snippet = build_snippet("a\nb", 1, None).unwrap();
+        assert_eq!(snippet, "> 1 | a\n  2 | b");
+        assert!(build_snippet("a\nb", 3, None).is_none());
+    }
+
+    #[test]
+    fn snippets_are_read_for_frames_in_readable_files() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.test.ts"), "one\ntwo\n").unwrap();
+        let failure = |stack: &str, snippet: Option<&str>| crate::models::FailureOutput {
+            message: "boom".into(),
+            expected: None,
+            actual: None,
+            expected_parsed: None,
+            actual_parsed: None,
+            diff: None,
+            source_snippet: snippet.map(str::to_owned),
+            stack_trace: Some(stack.into()),
+            attachments: Vec::new(),
+        };
+        let result = TestResult {
+            status: crate::models::TestStatus::Failed,
+            duration_ms: None,
+            failures: vec![
+                failure("at a.test.ts:1:1", Some("kept")),
+                failure("at missing.test.ts:1:1", None),
+                failure("at a.test.ts:2", None),
+            ],
+        };
+
+        let frames = snippet_frames(dir.path(), &result);
+        assert_eq!(frames.iter().map(|(i, _)| *i).collect::<Vec<_>>(), [1, 2]);
+        assert_eq!(frames[1].1.path, dir.path().join("a.test.ts"));
+        assert_eq!(
+            read_snippets(&frames),
+            [(2, "  1 | one\n> 2 | two".to_string())]
+        );
+    }
+}
@@ -0,0 +1,283 @@
+use std::path::PathBuf;
+
+use anyhow::{Context, Result, bail};
+
+use crate::config::Config;
+use crate::headless::RunOptions;
+use crate::report::ReportFormat;
+use crate::runner::RunnerKind;
+
+pub const USAGE: &str = "\
+Usage: lens [OPTIONS] [PROJECT]...
+       lens run [OPTIONS] [PROJECT]...
+       lens list [OPTIONS] [PROJECT]...
+
+Commands:
+  (none)                Open the terminal UI
+  run                   Run the tests headless and write a report
+  list                  Print the discovered test files
+
+Options:
+  -c, --config <PATH>   Read settings from PATH instead of <workspace>/lens.toml
+  -w, --workspace <DIR> Workspace root (default: current directory)
+  -f, --filter <QUERY>  Start with the tree filtered; `run` only runs matches
+      --watch           Start with watch mode on
+      --run-on-start    Run the (filtered) tests once discovery finishes
+      --runner <NAME>   Force vitest, jest, playwright, node, bun,
+                        cargo or pytest
+  -h, --help            Print this help
+  -V, --version         Print the version
+
+Run options:
+      --reporter <FMT>  junit (default) or json
+  -o, --output <PATH>   Write the report to PATH instead of stdout
+
+List options:
+      --tests           Also list the tests recorded by the last run
+
+PROJECT is an Nx project name; several can be given.";
+
+/// What lens was asked to do.
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Tui,
+    Run(RunOptions),
+    List { tests: bool },
+    Help,
+    Version,
+}
+
+/// Parsed command line. Fields left unset fall back to `lens.toml`.
+#[derive(Debug, PartialEq)]
+pub struct Cli {
+    pub command: Command,
+    pub config: Option<PathBuf>,
+    pub workspace: Option<PathBuf>,
+    pub filter: Option<String>,
+    pub watch: bool,
+    pub run_on_start: bool,
+    pub projects: Vec<String>,
+    pub runner: Option<RunnerKind>,
+}
+
+impl Cli {
+    /// Parse the arguments following the program name.
+    pub fn parse(args: &[String]) -> Result<Self> {
+        let mut args = args.iter().peekable();
+        let subcommand = match args.peek().map(|a| a.as_str()) {
+            Some(name @ ("run" | "list")) => {
+                args.next();
+                Some(name)
+            }
+            _ => None,
+        };
+
+        let mut cli = Self {
+            command: Command::Tui,
+            config: None,
+            workspace: None,
+            filter: None,
+            watch: false,
+            run_on_start: false,
+            projects: Vec::new(),
+            runner: None,
+        };
+        let mut format = ReportFormat::Junit;
+        let mut output = None;
+        let mut tests = false;
+
+        while let Some(arg) = args.next() {
+            let (flag, inline) = match arg.split_once('=') {
+                Some((flag, value)) if flag.starts_with("--") => (flag, Some(value.to_string())),
+                _ => (arg.as_str(), None),
+            };
+            let mut value = || {
+                inline
+                    .clone()
+                    .or_else(|| args.next().cloned())
+                    .with_context(|| format!("{flag} requires a value"))
+            };
+            match (subcommand, flag) {
+                (_, "-h" | "--help") => cli.command = Command::Help,
+                (_, "-V" | "--version") => cli.command = Command::Version,
+                (_, "-c" | "--config") => cli.config = Some(PathBuf::from(value()?)),
+                (_, "-w" | "--workspace") => cli.workspace = Some(PathBuf::from(value()?)),
+                (_, "-f" | "--filter") => cli.filter = Some(value()?),
+                (_, "--runner") => cli.runner = Some(value()?.parse()?),
+                (None, "--watch") => cli.watch = true,
+                (None, "--run-on-start") => cli.run_on_start = true,
+                (Some("run"), "--reporter") => format = value()?.parse()?,
+                (Some("run"), "-o" | "--output") => output = Some(PathBuf::from(value()?)),
+                (Some("list"), "--tests") => tests = true,
+                (Some(sub), "--watch" | "--run-on-start") => {
+                    bail!("{flag} is not supported by `lens {sub}`")
+                }
+                _ if flag.starts_with('-') => bail!("unknown option '{flag}'"),
+                _ => cli.projects.push(arg.clone()),
+            }
+        }
+
+        if matches!(cli.command, Command::Tui) {
+            cli.command = match subcommand {
+                Some("run") => Command::Run(RunOptions { format, output }),
+                Some("list") => Command::List { tests },
+                _ => Command::Tui,
+            };
+        }
+        Ok(cli)
+    }
+
+    /// The workspace root: `--workspace`, or the current directory.
+    pub fn workspace(&self) -> Result<PathBuf> {
+        let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+        let Some(dir) = &self.workspace else {
+            return Ok(cwd);
+        };
+        // Runners report canonical paths, which only strip the workspace off
+        // when it has no `..` or symlinks in it either.
+        let dir = cwd.join(dir);
+        match std::fs::canonicalize(&dir) {
+            Ok(canonical) if canonical.is_dir() => Ok(canonical),
+            _ => bail!("workspace '{}' is not a directory", dir.display()),
+        }
+    }
+
+    /// Load `--config` (or the workspace's `lens.toml`) and override it with the
+    /// options given on the command line.
+    pub fn load_config(&self, workspace: &std::path::Path) -> Result<Config> {
+        let mut cfg = match &self.config {
+            Some(path) => Config::load_from(path)?,
+            None => Config::load(workspace),
+        };
+        self.apply(&mut cfg);
+        Ok(cfg)
+    }
+
+    fn apply(&self, cfg: &mut Config) {
+        if !self.projects.is_empty() {
+            cfg.runner.projects = self.projects.clone();
+        }
+        if self.runner.is_some() {
+            cfg.runner.kind = self.runner;
+        }
+        cfg.runner.watch |= self.watch;
+        cfg.runner.run_on_start |= self.run_on_start;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(list: &[&str]) -> Result<Cli> {
+        let args: Vec<String> = list.iter().map(|s| s.to_string()).collect();
+        Cli::parse(&args)
+    }
+
+    #[test]
+    fn no_arguments_opens_the_tui() {
+        let cli = parse(&[]).unwrap();
+        assert_eq!(cli.command, Command::Tui);
+        assert!(cli.projects.is_empty());
+        assert_eq!(cli.runner, None);
+    }
+
+    #[test]
+    fn tui_accepts_projects_and_options_in_any_order() {
+        let cli = parse(&[
+            "web",
+            "--watch",
+            "api",
+            "--filter=math",
+            "--runner",
+            "jest",
+            "-w",
+            "apps",
+            "--run-on-start",
+        ])
+        .unwrap();
+        assert_eq!(cli.command, Command::Tui);
+        assert_eq!(cli.projects, ["web", "api"]);
+        assert_eq!(cli.filter.as_deref(), Some("math"));
+        assert_eq!(cli.runner, Some(RunnerKind::Jest));
+        assert_eq!(cli.workspace, Some(PathBuf::from("apps")));
+        assert!(cli.watch && cli.run_on_start);
+    }
+
+    #[test]
+    fn workspace_is_canonicalized() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("app")).unwrap();
+        std::fs::create_dir_all(dir.path().join("tools")).unwrap();
+        let through_parent = dir.path().join("tools/../app");
+
+        let cli = parse(&["-w", &through_parent.to_string_lossy()]).unwrap();
+        let workspace = cli.workspace().unwrap();
+        assert_eq!(workspace, dir.path().join("app").canonicalize().unwrap());
+        assert!(!workspace.to_string_lossy().contains(".."));
+
+        let missing = dir.path().join("missing");
+        let cli = parse(&["-w", &missing.to_string_lossy()]).unwrap();
+        assert!(cli.workspace().is_err());
+    }
+
+    #[test]
+    fn run_defaults_to_junit_on_stdout() {
+        let cli = parse(&["run"]).unwrap();
+        assert_eq!(
+            cli.command,
+            Command::Run(RunOptions {
+                format: ReportFormat::Junit,
+                output: None,
+            })
+        );
+    }
+
+    #[test]
+    fn run_accepts_separate_and_inline_values() {
+        let cli = parse(&["run", "web", "--reporter", "json", "--output=report.json"]).unwrap();
+        assert_eq!(
+            cli.command,
+            Command::Run(RunOptions {
+                format: ReportFormat::Json,
+                output: Some(PathBuf::from("report.json")),
+            })
+        );
+        assert_eq!(cli.projects, ["web"]);
+    }
+
+    #[test]
+    fn list_accepts_tests_flag() {
+        let cli = parse(&["list", "--tests", "-c", "ci.toml"]).unwrap();
+        assert_eq!(cli.command, Command::List { tests: true });
+        assert_eq!(cli.config, Some(PathBuf::from("ci.toml")));
+    }
+
+    #[test]
+    fn rejects_unknown_misplaced_and_incomplete_options() {
+        assert!(parse(&["--verbose"]).is_err());
+        assert!(parse(&["run", "--output"]).is_err());
+        assert!(parse(&["run", "--reporter", "tap"]).is_err());
+        assert!(parse(&["run", "--watch"]).is_err());
+        assert!(parse(&["--reporter", "json"]).is_err());
+        assert!(parse(&["--runner", "mocha"]).is_err());
+    }
+
+    #[test]
+    fn help_wins_over_subcommand() {
+        assert_eq!(parse(&["run", "--help"]).unwrap().command, Command::Help);
+    }
+
+    #[test]
+    fn command_line_overrides_config() {
+        let mut cfg: Config = toml::from_str(
+            "[runner]\nkind = \"vitest\"\nprojects = [\"web\"]\nrun_on_start = true\n",
+        )
+        .unwrap();
+        let cli = parse(&["api", "--runner", "jest", "--watch"]).unwrap();
+        cli.apply(&mut cfg);
+        assert_eq!(cfg.runner.projects, ["api"]);
+        assert_eq!(cfg.runner.kind, Some(RunnerKind::Jest));
+        assert!(cfg.runner.watch && cfg.runner.run_on_start);
+    }
+}
@@ -1,13 +1,31 @@
 use std::path::Path;
 
+use anyhow::{Context, Result};
 use serde::Deserialize;
 
+use crate::app::layout::PanelLayout;
+use crate::runner::RunnerKind;
+
 #[derive(Debug, Default, Deserialize)]
 pub struct Config {
     #[serde(default)]
     pub discovery: DiscoveryConfig,
     #[serde(default)]
     pub editor: EditorConfig,
+    #[serde(default)]
+    pub coverage: CoverageConfig,
+    #[serde(default)]
+    pub runner: RunnerConfig,
+    /// Key bindings: action name → key or list of keys, e.g.
+    /// `jump_to_start = "gg"` or `scroll_down = ["ctrl+d", "J"]`.
+    #[serde(default)]
+    pub keys: toml::Table,
+    /// Color theme: `name` picks a built-in theme and any other key overrides
+    /// one of its colors, e.g. `name = "latte"` and `red = "#d20f39"`.
+    #[serde(default)]
+    pub theme: toml::Table,
+    #[serde(default)]
+    pub layout: PanelLayout,
 }
 
 /// Controls which files are excluded during test discovery.
@@ -28,6 +46,32 @@ pub struct EditorConfig {
     pub command: Option<String>,
 }
 
+/// Coverage collection, off by default because it slows runs down.
+#[derive(Debug, Default, Deserialize)]
+pub struct CoverageConfig {
+    /// Start with coverage enabled (toggle at runtime with `C`).
+    #[serde(default)]
+    pub enabled: bool,
+}
+
+/// What to run and how, each overridable from the command line.
+#[derive(Debug, Default, Deserialize)]
+pub struct RunnerConfig {
+    /// Force a runner instead of detecting it: "vitest", "jest", "playwright", "node",
+    /// "bun", "cargo" or "pytest".
+    pub kind: Option<RunnerKind>,
+    /// Nx projects to load instead of the whole workspace.
+    /// Example: ["web", "api"]
+    #[serde(default)]
+    pub projects: Vec<String>,
+    /// Start with watch mode on.
+    #[serde(default)]
+    pub watch: bool,
+    /// Run the tests (or the filtered tests) as soon as discovery finishes.
+    #[serde(default)]
+    pub run_on_start: bool,
+}
+
 impl Config {
     /// Load `lens.toml` from the workspace root, falling back to defaults if absent or invalid.
     pub fn load(workspace: &Path) -> Self {
@@ -37,4 +81,12 @@ impl Config {
         };
         toml::from_str(&content).unwrap_or_default()
     }
+
+    /// Load an explicitly given config file. Unlike `load`, a missing or invalid
+    /// file is an error rather than silently ignored.
+    pub fn load_from(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        toml::from_str(&content).with_context(|| format!("invalid {}", path.display()))
+    }
 }
@@ -1,13 +1,17 @@
 use std::path::PathBuf;
+use std::process::Stdio;
 use std::{io, path::Path};
 
 use anyhow::Result;
 use crossterm::{
     ExecutableCommand,
+    event::{DisableMouseCapture, EnableMouseCapture},
     terminal::{self, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use ratatui::prelude::*;
 
+use crate::models::Attachment;
+
 /// Suspend the TUI, open the editor at the given location, then restore the TUI.
 /// `command_override` takes priority over `$EDITOR`.
 pub fn open(
@@ -18,7 +22,9 @@ pub fn open(
     editor_cmd: Option<&str>,
 ) -> Result<()> {
     terminal::disable_raw_mode()?;
-    io::stdout().execute(LeaveAlternateScreen)?;
+    io::stdout()
+        .execute(DisableMouseCapture)?
+        .execute(LeaveAlternateScreen)?;
 
     let editor_env = editor_cmd
         .map(str::to_owned)
@@ -40,7 +46,9 @@ pub fn open(
     build_args(&mut cmd, &parts[0], &path, line, col);
     let result = cmd.status();
 
-    io::stdout().execute(EnterAlternateScreen)?;
+    io::stdout()
+        .execute(EnterAlternateScreen)?
+        .execute(EnableMouseCapture)?;
     terminal::enable_raw_mode()?;
     terminal.clear()?;
 
@@ -48,6 +56,37 @@ pub fn open(
     Ok(())
 }
 
+/// Open a test's attachment without leaving the TUI: Playwright traces in the
+/// trace viewer, anything else in the system's default app for its type.
+pub fn open_attachment(attachment: &Attachment, workspace: &Path) -> Result<()> {
+    viewer_command(attachment)
+        .current_dir(workspace)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| anyhow::anyhow!("failed to launch viewer: {e}"))?;
+    Ok(())
+}
+
+fn viewer_command(attachment: &Attachment) -> std::process::Command {
+    if attachment.name == "trace" && attachment.content_type == "application/zip" {
+        let mut cmd = std::process::Command::new("npx");
+        cmd.args(["playwright", "show-trace"]).arg(&attachment.path);
+        return cmd;
+    }
+    let opener = if cfg!(target_os = "macos") {
+        "open"
+    } else if cfg!(windows) {
+        "explorer"
+    } else {
+        "xdg-open"
+    };
+    let mut cmd = std::process::Command::new(opener);
+    cmd.arg(&attachment.path);
+    cmd
+}
+
 fn build_args(
     cmd: &mut std::process::Command,
     editor: &str,
@@ -138,6 +177,33 @@ mod tests {
             .collect()
     }
 
+    #[test]
+    fn traces_open_in_the_trace_viewer() {
+        let attachment = |name: &str, content_type: &str| Attachment {
+            name: name.into(),
+            content_type: content_type.into(),
+            path: PathBuf::from("test-results/login/trace.zip"),
+        };
+        let program = |cmd: std::process::Command| {
+            let mut parts = vec![cmd.get_program().to_string_lossy().into_owned()];
+            parts.extend(cmd.get_args().map(|a| a.to_string_lossy().into_owned()));
+            parts
+        };
+        assert_eq!(
+            program(viewer_command(&attachment("trace", "application/zip"))),
+            [
+                "npx",
+                "playwright",
+                "show-trace",
+                "test-results/login/trace.zip"
+            ]
+        );
+        assert_ne!(
+            program(viewer_command(&attachment("screenshot", "image/png")))[0],
+            "npx"
+        );
+    }
+
     #[test]
     fn test_editor_kind_nvim_binary_name() {
         assert!(matches!(editor_kind("nvim"), EditorKind::Neovim));
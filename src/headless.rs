@@ -0,0 +1,228 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::{Context, Result, bail};
+use tokio::sync::mpsc::UnboundedReceiver;
+
+use crate::app::{Action, App, PendingRun, TestEvent, handle_action, handle_test_event, snapshot};
+use crate::config::Config;
+use crate::report::{self, ReportFormat};
+use crate::runner::TestRunner;
+use crate::start_runner;
+
+/// Options for `lens run`.
+#[derive(Debug, PartialEq)]
+pub struct RunOptions {
+    pub format: ReportFormat,
+    /// Write the report here instead of stdout.
+    pub output: Option<PathBuf>,
+}
+
+/// Start the runner and wait for discovery, so files that produce no results
+/// still show up. A non-empty `filter` is applied to the tree.
+async fn discover(
+    workspace: PathBuf,
+    cfg: Config,
+    filter: Option<String>,
+) -> Result<(App, UnboundedReceiver<TestEvent>, Arc<dyn TestRunner>)> {
+    let (mut app, mut event_rx) = App::new(workspace.clone());
+    app.project_name = project_label(&cfg.runner.projects);
+    if let Some(filter) = filter {
+        app.filter = tui_input::Input::from(filter);
+    }
+
+    let runner = start_runner(
+        workspace,
+        cfg.runner.projects,
+        cfg.discovery.ignore,
+        cfg.runner.kind,
+        app.event_tx.clone(),
+    )
+    .await
+    .context("failed to initialize test runner")?;
+
+    while app.discovering {
+        let Some(event) = event_rx.recv().await else {
+            break;
+        };
+        if let TestEvent::DiscoveryFailed { message } = event {
+            bail!(message);
+        }
+        handle_test_event(&mut app, event);
+    }
+    Ok((app, event_rx, runner))
+}
+
+/// Run the whole suite (or the tests matching `filter`) without a terminal UI
+/// and write a report. Returns the process exit code: 1 if any test failed, 0 otherwise.
+pub async fn run(
+    workspace: PathBuf,
+    cfg: Config,
+    filter: Option<String>,
+    opts: RunOptions,
+) -> Result<i32> {
+    let (mut app, mut event_rx, runner) = discover(workspace, cfg, filter).await?;
+
+    let runs = if app.filter.value().is_empty() {
+        Vec::new()
+    } else {
+        handle_action(&mut app, Action::RunFiltered);
+        if app.pending_runs.is_empty() {
+            bail!("no tests match '{}'", app.filter.value());
+        }
+        std::mem::take(&mut app.pending_runs)
+    };
+
+    app.full_run = runs.is_empty();
+    app.run_start = Some(std::time::Instant::now());
+    let tx = app.event_tx.clone();
+    let mut run = tokio::spawn(async move {
+        if runs.is_empty() {
+            return runner.run_all(tx).await;
+        }
+        for run in runs {
+            match run {
+                PendingRun::Files(files) => runner.run_files(&files, tx.clone()).await?,
+                PendingRun::Tests { files, names } => {
+                    runner.run_tests(&files, &names, tx.clone()).await?
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    });
+
+    let result = loop {
+        tokio::select! {
+            result = &mut run => break result,
+            Some(event) = event_rx.recv() => handle_event(&mut app, event),
+        }
+    };
+    // The runner task may finish before its last events are processed.
+    while let Ok(event) = event_rx.try_recv() {
+        handle_event(&mut app, event);
+    }
+    // Results wait for the run's code version before reaching the history.
+    while app.history.is_waiting()
+        && let Some(event) = event_rx.recv().await
+    {
+        handle_event(&mut app, event);
+    }
+    result.context("runner task panicked")??;
+
+    let Some(summary) = app.summary.as_ref() else {
+        bail!("test run did not complete");
+    };
+    let report = report::render(opts.format, &app.tree, Some(summary));
+    match &opts.output {
+        Some(path) => std::fs::write(path, report)
+            .with_context(|| format!("failed to write {}", path.display()))?,
+        None => print!("{report}"),
+    }
+
+    eprintln!(
+        "{} passed, {} failed, {} skipped ({} total) in {:.1}s",
+        summary.passed,
+        summary.failed,
+        summary.skipped,
+        summary.total,
+        summary.duration as f64 / 1000.0
+    );
+    Ok(if summary.failed > 0 { 1 } else { 0 })
+}
+
+/// Print the discovered test files (matching `filter`, if any), one per line.
+/// With `tests`, each file is followed by the tests its last run recorded.
+pub async fn list(
+    workspace: PathBuf,
+    cfg: Config,
+    filter: Option<String>,
+    tests: bool,
+) -> Result<()> {
+    let (mut app, _event_rx, _runner) = discover(workspace, cfg, filter).await?;
+    if tests {
+        snapshot::restore(&mut app)?;
+    }
+    print!("{}", render_list(&app, tests));
+    Ok(())
+}
+
+fn render_list(app: &App, tests: bool) -> String {
+    let visible: HashSet<usize> = app
+        .visible_tree_nodes()
+        .into_iter()
+        .map(|(id, _)| id)
+        .collect();
+    let mut out = String::new();
+    for file in report::collect(&app.tree) {
+        let shown = app
+            .tree
+            .find_file_by_path(Path::new(&file.path))
+            .is_some_and(|id| visible.contains(&id));
+        if !shown {
+            continue;
+        }
+        out.push_str(&file.path);
+        out.push('\n');
+        if tests {
+            for test in &file.tests {
+                out.push_str("  ");
+                out.push_str(&test.name);
+                out.push('\n');
+            }
+        }
+    }
+    out
+}
+
+/// Tree title for the selected Nx projects.
+pub fn project_label(projects: &[String]) -> Option<String> {
+    (!projects.is_empty()).then(|| projects.join(", "))
+}
+
+fn handle_event(app: &mut App, event: TestEvent) {
+    if let TestEvent::Error { message } = &event {
+        eprintln!("[ERROR] {message}");
+    }
+    handle_test_event(app, event);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn discovered_app(files: &[&str]) -> App {
+        let (mut app, _rx) = App::new(PathBuf::from("/ws"));
+        handle_test_event(
+            &mut app,
+            TestEvent::DiscoveryComplete {
+                files: files.iter().map(|f| f.to_string()).collect(),
+                projects: Vec::new(),
+            },
+        );
+        app
+    }
+
+    #[test]
+    fn list_prints_every_discovered_file() {
+        let app = discovered_app(&["src/a.test.ts", "src/b.test.ts"]);
+        assert_eq!(render_list(&app, false), "src/a.test.ts\nsrc/b.test.ts\n");
+    }
+
+    #[test]
+    fn list_respects_the_filter() {
+        let mut app = discovered_app(&["src/a.test.ts", "src/b.test.ts"]);
+        app.filter = tui_input::Input::from("b.test".to_string());
+        assert_eq!(render_list(&app, false), "src/b.test.ts\n");
+    }
+
+    #[test]
+    fn project_label_joins_names() {
+        assert_eq!(project_label(&[]), None);
+        assert_eq!(
+            project_label(&["web".to_string(), "api".to_string()]).as_deref(),
+            Some("web, api")
+        );
+    }
+}
@@ -1,7 +1,10 @@
 mod app;
+mod cli;
 mod config;
 mod editor;
+mod headless;
 mod models;
+mod report;
 mod runner;
 mod ui;
 
@@ -12,7 +15,7 @@ use std::sync::Arc;
 use anyhow::Result;
 use crossterm::{
     ExecutableCommand,
-    event::{Event, EventStream},
+    event::{DisableMouseCapture, EnableMouseCapture, Event, EventStream},
     terminal::{self, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use futures_util::StreamExt;
@@ -20,41 +23,103 @@ use ratatui::prelude::*;
 use tokio::sync::mpsc;
 use tokio::time::{Duration, interval};
 
+use app::keymap::Keymap;
+use app::layout::PanelLayout;
+use app::runner_log::LogKind;
 use app::{Action, App, handle_action, handle_test_event, trigger_action};
+use cli::{Cli, Command};
 use runner::{TestRunner, resolve_nx_project};
+use ui::theme::Theme;
 
 use crate::config::Config;
 
 #[tokio::main]
 async fn main() -> Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let cli = match Cli::parse(&args) {
+        Ok(cli) => cli,
+        Err(e) => {
+            eprintln!("lens: {e:#}\n\n{}", cli::USAGE);
+            std::process::exit(2);
+        }
+    };
+    match cli.command {
+        Command::Help => {
+            println!("{}", cli::USAGE);
+            return Ok(());
+        }
+        Command::Version => {
+            println!("lens {}", env!("CARGO_PKG_VERSION"));
+            return Ok(());
+        }
+        _ => {}
+    }
+
+    let workspace = cli.workspace()?;
+    let cfg = cli.load_config(&workspace)?;
+    match cli.command {
+        Command::Run(opts) => {
+            let code = headless::run(workspace, cfg, cli.filter, opts).await?;
+            std::process::exit(code);
+        }
+        Command::List { tests } => return headless::list(workspace, cfg, cli.filter, tests).await,
+        _ => {}
+    }
+
     // Setup terminal
     terminal::enable_raw_mode()?;
-    io::stdout().execute(EnterAlternateScreen)?;
+    io::stdout()
+        .execute(EnterAlternateScreen)?
+        .execute(EnableMouseCapture)?;
     let backend = CrosstermBackend::new(io::stdout());
     let mut terminal = Terminal::new(backend)?;
 
-    let result = run(&mut terminal).await;
+    let result = run(&mut terminal, workspace, cfg, cli.filter).await;
 
     // Teardown terminal
     terminal::disable_raw_mode()?;
-    io::stdout().execute(LeaveAlternateScreen)?;
+    io::stdout()
+        .execute(DisableMouseCapture)?
+        .execute(LeaveAlternateScreen)?;
 
     result
 }
 
-async fn run(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -> Result<()> {
-    let workspace = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
-    let project = std::env::args().nth(1);
-    let cfg = Config::load(&workspace);
-
+async fn run(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    workspace: PathBuf,
+    cfg: Config,
+    filter: Option<String>,
+) -> Result<()> {
     let (mut app, mut event_rx) = App::new(workspace.clone());
-    app.project_name = project.clone();
+    app.project_name = headless::project_label(&cfg.runner.projects);
+    app.history = app::history::History::load(&workspace);
+    app.coverage_enabled = cfg.coverage.enabled;
+    app.watch_mode = cfg.runner.watch;
+    app.layout = PanelLayout::load(&workspace, cfg.layout);
+    let (keymap, key_errors) = Keymap::from_config(&cfg.keys);
+    let (theme, theme_errors) = Theme::from_config(&cfg.theme);
+    app.keymap = keymap;
+    app.theme = theme;
+    let problems: Vec<String> = (key_errors.iter().map(|e| format!("[keys] {e}")))
+        .chain(theme_errors.iter().map(|e| format!("[theme] {e}")))
+        .collect();
+    if !problems.is_empty() {
+        app.notifier
+            .error(format!("Invalid lens.toml: {}", problems.join("; ")));
+    }
+    if let Some(filter) = filter {
+        app.filter = tui_input::Input::from(filter);
+    }
+    // Cleared once discovery finishes and the first run has been started.
+    let mut run_on_start = cfg.runner.run_on_start;
     let mut tick = interval(Duration::from_millis(100));
     let mut test_runner: Option<Arc<dyn TestRunner>> = None;
     let mut runner_rx = Some(start_runner(
         workspace,
-        project,
+        cfg.runner.projects,
         cfg.discovery.ignore,
+        cfg.runner.kind,
         app.event_tx.clone(),
     ));
     let editor_command = cfg.editor.command;
@@ -65,6 +130,7 @@ async fn run(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -> Result<()
             app.refresh_watched_ids();
         }
         terminal.draw(|frame| ui::draw(frame, &mut app))?;
+        let layout = app.layout;
 
         tokio::select! {
             maybe_event = event_stream.next() => {
@@ -72,156 +138,13 @@ async fn run(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -> Result<()
                 None => break,
                 Some(Err(e)) => return Err(e.into()),
                 Some(Ok(Event::Key(key))) => {
-                    let action = trigger_action(key, app.filter_active, app.show_help);
-
-                    if let Some(action) = action {
-                        if let Some(ref runner) = test_runner {
-                            match action {
-                                Action::RunAll => {
-                                    handle_action(&mut app, action);
-                                    app.run_start = Some(std::time::Instant::now());
-                                    let tx = app.event_tx.clone();
-                                    if app.watch_mode {
-                                        // Stop any previous watch, then start a new global watch
-                                        if let Some(h) = app.watch_handle.take() {
-                                            h.abort();
-                                        }
-                                        let runner = Arc::clone(runner);
-                                        let handle = tokio::spawn(async move {
-                                            if let Err(e) = runner.run_all_watch(tx.clone()).await {
-                                                let _ = tx.send(app::TestEvent::Error {
-                                                    message: format!("Watch error: {}", e),
-                                                });
-                                            }
-                                            let _ = tx.send(app::TestEvent::WatchStopped);
-                                        });
-                                        app.watch_handle = Some(handle);
-                                        app.watch_scope = app::WatchScope::All;
-                                        app.watched_ids_stale = true;
-                                    } else {
-                                        let runner = Arc::clone(runner);
-                                        tokio::spawn(async move {
-                                            if let Err(e) = runner.run_all(tx.clone()).await {
-                                                let _ = tx.send(app::TestEvent::Error {
-                                                    message: format!("Runner error: {}", e),
-                                                });
-                                            }
-                                        });
-                                    }
-                                }
-                                Action::ToggleWatch => {
-                                    handle_action(&mut app, Action::ToggleWatch);
-                                    if !app.watch_mode {
-                                        // Turned OFF — kill any active watch process
-                                        if let Some(handle) = app.watch_handle.take() {
-                                            handle.abort();
-                                        }
-                                        app.running = false;
-                                        app.watch_scope = app::WatchScope::None;
-                                        app.watched_ids_stale = true;
-                                    }
-                                    // Turned ON — nothing, process starts lazily on first run
-                                }
-                                other => {
-                                    handle_action(&mut app, other);
-                                    for pending in app.pending_runs.drain(..) {
-                                        app.running = true;
-                                        app.run_start = Some(std::time::Instant::now());
-                                        let tx = app.event_tx.clone();
-
-                                        // In watch mode, stop the previous watch before starting a new one
-                                        if app.watch_mode &&
-                                             let Some(h) = app.watch_handle.take() {
-                                                h.abort();
-                                        }
-
-                                        let runner_clone = Arc::clone(runner);
-                                        match pending {
-                                            app::PendingRun::Files(paths) => {
-                                                if app.watch_mode {
-                                                    app.watch_scope = app::WatchScope::All;
-                                                    app.watched_ids_stale = true;
-                                                    let handle = tokio::spawn(async move {
-                                                        if let Err(e) = runner_clone.run_all_watch(tx.clone()).await {
-                                                            let _ = tx.send(app::TestEvent::Error {
-                                                                message: format!("Watch error: {}", e),
-                                                            });
-                                                        }
-                                                        let _ = tx.send(app::TestEvent::WatchStopped);
-                                                    });
-                                                    app.watch_handle = Some(handle);
-                                                } else {
-                                                    tokio::spawn(async move {
-                                                        if let Err(e) = runner_clone.run_files(&paths, tx.clone()).await {
-                                                            let _ = tx.send(app::TestEvent::Error {
-                                                                message: format!("Runner error: {}", e),
-                                                            });
-                                                        }
-                                                    });
-                                                }
-                                            }
-                                            app::PendingRun::File(path) => {
-                                                if app.watch_mode {
-                                                    app.watch_scope = app::WatchScope::File(path.clone());
-                                                    app.watched_ids_stale = true;
-                                                    let handle = tokio::spawn(async move {
-                                                        if let Err(e) = runner_clone.run_file_watch(&path, tx.clone()).await {
-                                                            let _ = tx.send(app::TestEvent::Error {
-                                                                message: format!("Watch error: {}", e),
-                                                            });
-                                                        }
-                                                        let _ = tx.send(app::TestEvent::WatchStopped);
-                                                    });
-                                                    app.watch_handle = Some(handle);
-                                                } else {
-                                                    tokio::spawn(async move {
-                                                        if let Err(e) = runner_clone.run_file(&path, tx.clone()).await {
-                                                            let _ = tx.send(app::TestEvent::Error {
-                                                                message: format!("Runner error: {}", e),
-                                                            });
-                                                        }
-                                                    });
-                                                }
-                                            }
-                                            app::PendingRun::Test { file, name } => {
-                                                if app.watch_mode {
-                                                    app.watch_scope = app::WatchScope::Test {
-                                                        file: file.clone(),
-                                                        name: name.clone(),
-                                                    };
-                                                    app.watched_ids_stale = true;
-                                                    let handle = tokio::spawn(async move {
-                                                        if let Err(e) = runner_clone.run_test_watch(&file, &name, tx.clone()).await {
-                                                            let _ = tx.send(app::TestEvent::Error {
-                                                                message: format!("Watch error: {}", e),
-                                                            });
-                                                        }
-                                                        let _ = tx.send(app::TestEvent::WatchStopped);
-                                                    });
-                                                    app.watch_handle = Some(handle);
-                                                } else {
-                                                    tokio::spawn(async move {
-                                                        if let Err(e) = runner_clone.run_test(&file, &name, tx.clone()).await {
-                                                            let _ = tx.send(app::TestEvent::Error {
-                                                                message: format!("Runner error: {}", e),
-                                                            });
-                                                        }
-                                                    });
-                                                }
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-                        } else {
-                            // Runner not ready yet — handle navigation/UI actions, but skip run actions
-                            match action {
-                                Action::RunAll | Action::RunFiltered | Action::RerunFailed | Action::ToggleWatch | Action::Select => {
-                                    app.output_lines.push("[INFO] Runner is still loading...".into());
-                                }
-                                other => handle_action(&mut app, other),
-                            }
-                        }
+                    if let Some(action) = trigger_action(&mut app, key) {
+                        perform(&mut app, test_runner.as_ref(), action);
+                    }
+                }
+                Some(Ok(Event::Mouse(mouse))) => {
+                    if let Some(action) = app::mouse::handle_mouse(&mut app, mouse) {
+                        perform(&mut app, test_runner.as_ref(), action);
                     }
                 }
                 Some(Ok(_)) => {}
@@ -232,6 +155,7 @@ async fn run(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -> Result<()
                 runner_rx = None;
                 match result {
                     Ok(r) => {
+                        r.set_coverage(app.coverage_enabled);
                         test_runner = Some(r);
                     }
                     Err(_) => {
@@ -242,7 +166,15 @@ async fn run(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -> Result<()
             }
 
             Some(test_event) = event_rx.recv() => {
+                let discovered = matches!(test_event, app::TestEvent::DiscoveryComplete { .. });
+                let finished = matches!(test_event, app::TestEvent::RunFinished { .. });
                 handle_test_event(&mut app, test_event);
+                if discovered && let Err(e) = app::snapshot::restore(&mut app) {
+                    app.notifier.error(format!("Could not restore last run: {e}"));
+                }
+                if finished && let Err(e) = app::snapshot::save(&app) {
+                    app.notifier.error(format!("Could not save run results: {e}"));
+                }
             }
 
             _ = tick.tick() => {
@@ -253,12 +185,31 @@ async fn run(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -> Result<()
             }
         }
 
+        if run_on_start
+            && !app.discovering
+            && let Some(ref runner) = test_runner
+        {
+            run_on_start = false;
+            let action = if app.filter.value().is_empty() {
+                Action::RunAll
+            } else {
+                Action::RunFiltered
+            };
+            dispatch(&mut app, runner, action);
+        }
+
         if let Some((path, line, col)) = app.pending_editor.take()
             && let Err(e) = editor::open(terminal, path, line, col, editor_command.as_deref())
         {
             app.notifier.error(e.to_string());
         }
 
+        if app.layout != layout
+            && let Err(e) = app.layout.save(&app.workspace)
+        {
+            app.notifier.error(format!("Could not save layout: {e}"));
+        }
+
         if app.should_quit {
             break;
         }
@@ -267,63 +218,285 @@ async fn run(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -> Result<()
     Ok(())
 }
 
+/// Handle a key or mouse action. Until the runner is ready only navigation and
+/// UI actions are handled; run actions are skipped.
+fn perform(app: &mut App, runner: Option<&Arc<dyn TestRunner>>, action: Action) {
+    if let Some(runner) = runner {
+        dispatch(app, runner, action);
+        return;
+    }
+    match action {
+        Action::RunAll
+        | Action::RunFiltered
+        | Action::RerunFailed
+        | Action::ToggleWatch
+        | Action::Select
+        | Action::ConfirmUpdateSnapshots => {
+            app.runner_log
+                .push(LogKind::Info, "Runner is still loading...");
+        }
+        other => handle_action(app, other),
+    }
+}
+
+/// Handle an action once the runner is ready, spawning any runs it queues.
+fn dispatch(app: &mut App, runner: &Arc<dyn TestRunner>, action: Action) {
+    match action {
+        Action::RunAll => {
+            handle_action(app, action);
+            app.run_start = Some(std::time::Instant::now());
+            let tx = app.event_tx.clone();
+            if app.watch_mode {
+                // Stop any previous watch, then start a new global watch
+                if let Some(h) = app.watch_handle.take() {
+                    h.abort();
+                }
+                let runner = Arc::clone(runner);
+                let handle = tokio::spawn(async move {
+                    if let Err(e) = runner.run_all_watch(tx.clone()).await {
+                        let _ = tx.send(app::TestEvent::Error {
+                            message: format!("Watch error: {}", e),
+                        });
+                    }
+                    let _ = tx.send(app::TestEvent::WatchStopped);
+                });
+                app.watch_handle = Some(handle);
+                app.watch_scope = app::WatchScope::All;
+                app.watched_ids_stale = true;
+            } else {
+                let runner = Arc::clone(runner);
+                tokio::spawn(async move {
+                    if let Err(e) = runner.run_all(tx.clone()).await {
+                        let _ = tx.send(app::TestEvent::Error {
+                            message: format!("Runner error: {}", e),
+                        });
+                    }
+                });
+            }
+        }
+        Action::ToggleCoverage => {
+            handle_action(app, action);
+            runner.set_coverage(app.coverage_enabled);
+        }
+        Action::ToggleWatch => {
+            handle_action(app, Action::ToggleWatch);
+            if !app.watch_mode {
+                // Turned OFF — kill any active watch process
+                if let Some(handle) = app.watch_handle.take() {
+                    handle.abort();
+                }
+                app.running = false;
+                app.watch_scope = app::WatchScope::None;
+                app.watched_ids_stale = true;
+            }
+            // Turned ON — nothing, process starts lazily on first run
+        }
+        other => {
+            handle_action(app, other);
+            for pending in app.pending_runs.drain(..) {
+                app.running = true;
+                app.run_start = Some(std::time::Instant::now());
+                let tx = app.event_tx.clone();
+
+                // In watch mode, stop the previous watch before starting a new one.
+                // Snapshot updates and filtered test runs are one-off runs and leave the watch alone.
+                let one_off = matches!(
+                    pending,
+                    app::PendingRun::UpdateSnapshots { .. } | app::PendingRun::Tests { .. }
+                );
+                if app.watch_mode
+                    && !one_off
+                    && let Some(h) = app.watch_handle.take()
+                {
+                    h.abort();
+                }
+
+                let runner_clone = Arc::clone(runner);
+                match pending {
+                    app::PendingRun::Files(paths) => {
+                        if app.watch_mode {
+                            app.watch_scope = app::WatchScope::All;
+                            app.watched_ids_stale = true;
+                            let handle = tokio::spawn(async move {
+                                if let Err(e) = runner_clone.run_all_watch(tx.clone()).await {
+                                    let _ = tx.send(app::TestEvent::Error {
+                                        message: format!("Watch error: {}", e),
+                                    });
+                                }
+                                let _ = tx.send(app::TestEvent::WatchStopped);
+                            });
+                            app.watch_handle = Some(handle);
+                        } else {
+                            tokio::spawn(async move {
+                                if let Err(e) = runner_clone.run_files(&paths, tx.clone()).await {
+                                    let _ = tx.send(app::TestEvent::Error {
+                                        message: format!("Runner error: {}", e),
+                                    });
+                                }
+                            });
+                        }
+                    }
+                    app::PendingRun::File(path) => {
+                        if app.watch_mode {
+                            app.watch_scope = app::WatchScope::File(path.clone());
+                            app.watched_ids_stale = true;
+                            let handle = tokio::spawn(async move {
+                                if let Err(e) = runner_clone.run_file_watch(&path, tx.clone()).await
+                                {
+                                    let _ = tx.send(app::TestEvent::Error {
+                                        message: format!("Watch error: {}", e),
+                                    });
+                                }
+                                let _ = tx.send(app::TestEvent::WatchStopped);
+                            });
+                            app.watch_handle = Some(handle);
+                        } else {
+                            tokio::spawn(async move {
+                                if let Err(e) = runner_clone.run_file(&path, tx.clone()).await {
+                                    let _ = tx.send(app::TestEvent::Error {
+                                        message: format!("Runner error: {}", e),
+                                    });
+                                }
+                            });
+                        }
+                    }
+                    app::PendingRun::Test { file, name } => {
+                        if app.watch_mode {
+                            app.watch_scope = app::WatchScope::Test {
+                                file: file.clone(),
+                                name: name.clone(),
+                            };
+                            app.watched_ids_stale = true;
+                            let handle = tokio::spawn(async move {
+                                if let Err(e) =
+                                    runner_clone.run_test_watch(&file, &name, tx.clone()).await
+                                {
+                                    let _ = tx.send(app::TestEvent::Error {
+                                        message: format!("Watch error: {}", e),
+                                    });
+                                }
+                                let _ = tx.send(app::TestEvent::WatchStopped);
+                            });
+                            app.watch_handle = Some(handle);
+                        } else {
+                            tokio::spawn(async move {
+                                if let Err(e) =
+                                    runner_clone.run_test(&file, &name, tx.clone()).await
+                                {
+                                    let _ = tx.send(app::TestEvent::Error {
+                                        message: format!("Runner error: {}", e),
+                                    });
+                                }
+                            });
+                        }
+                    }
+                    app::PendingRun::Tests { files, names } => {
+                        tokio::spawn(async move {
+                            if let Err(e) = runner_clone.run_tests(&files, &names, tx.clone()).await
+                            {
+                                let _ = tx.send(app::TestEvent::Error {
+                                    message: format!("Runner error: {}", e),
+                                });
+                            }
+                        });
+                    }
+                    app::PendingRun::UpdateSnapshots { files, name } => {
+                        tokio::spawn(async move {
+                            if let Err(e) = runner_clone
+                                .update_snapshots(&files, name.as_deref(), tx.clone())
+                                .await
+                            {
+                                let _ = tx.send(app::TestEvent::Error {
+                                    message: format!("Runner error: {}", e),
+                                });
+                            }
+                        });
+                    }
+                }
+            }
+        }
+    }
+}
+
 /// Spawn the async runner-init task and return a receiver for the constructed runner.
+///
+/// Each of `projects` is resolved to its Nx root and discovered; when none are
+/// given the whole workspace is.
 fn start_runner(
     workspace: PathBuf,
-    project: Option<String>,
+    projects: Vec<String>,
     ignore_patterns: Vec<String>,
+    kind: Option<runner::RunnerKind>,
     event_tx: mpsc::UnboundedSender<app::TestEvent>,
 ) -> tokio::sync::oneshot::Receiver<Arc<dyn TestRunner>> {
     let (runner_tx, runner_rx) = tokio::sync::oneshot::channel();
     tokio::spawn(async move {
-        let project_root = match project {
-            Some(name) => {
-                let ws_clone = workspace.clone();
-                let name_clone = name.clone();
-                let result = tokio::task::spawn_blocking(move || {
-                    resolve_nx_project(&ws_clone, &name_clone).ok()
-                })
-                .await
-                .ok()
-                .flatten();
-                if result.is_none() {
-                    let r: Arc<dyn TestRunner> = runner::detect(workspace, None, ignore_patterns);
-                    let _ = runner_tx.send(Arc::clone(&r));
-                    let _ = event_tx.send(app::TestEvent::DiscoveryFailed {
-                        message: format!("Nx project '{}' not found", name),
-                    });
-                    return;
-                }
-                result
-            }
-            None => None,
-        };
+        let mut project_roots = Vec::new();
+        for name in projects {
+            let ws_clone = workspace.clone();
+            let name_clone = name.clone();
+            let root = tokio::task::spawn_blocking(move || {
+                resolve_nx_project(&ws_clone, &name_clone).ok()
+            })
+            .await
+            .ok()
+            .flatten();
+            let Some(root) = root else {
+                let r: Arc<dyn TestRunner> =
+                    runner::detect(workspace, Vec::new(), ignore_patterns, kind);
+                let _ = runner_tx.send(Arc::clone(&r));
+                let _ = event_tx.send(app::TestEvent::DiscoveryFailed {
+                    message: format!("Nx project '{}' not found", name),
+                });
+                return;
+            };
+            project_roots.push(root);
+        }
 
-        let discover_root = project_root.as_deref().unwrap_or(&workspace).to_path_buf();
+        let discover_roots = if project_roots.is_empty() {
+            vec![workspace.clone()]
+        } else {
+            project_roots.clone()
+        };
         let r: Arc<dyn TestRunner> =
-            runner::detect(workspace.clone(), project_root, ignore_patterns);
+            runner::detect(workspace.clone(), project_roots, ignore_patterns, kind);
         let _ = runner_tx.send(Arc::clone(&r));
 
-        match r.discover(&discover_root).await {
-            Ok(files) => {
-                let displays: Vec<String> = files
-                    .iter()
-                    .map(|f| {
-                        f.path
-                            .strip_prefix(&workspace)
-                            .unwrap_or(&f.path)
-                            .to_string_lossy()
-                            .to_string()
-                    })
-                    .collect();
-                let _ = event_tx.send(app::TestEvent::DiscoveryComplete { files: displays });
-            }
-            Err(_) => {
+        let mut displays: Vec<String> = Vec::new();
+        let mut projects: Vec<(String, String)> = Vec::new();
+        for root in &discover_roots {
+            let Ok(files) = r.discover(root).await else {
                 let _ = event_tx.send(app::TestEvent::DiscoveryFailed {
                     message: "Failed to discover test files".into(),
                 });
+                return;
+            };
+            for f in files {
+                let display = f
+                    .path
+                    .strip_prefix(&workspace)
+                    .unwrap_or(&f.path)
+                    .to_string_lossy()
+                    .to_string();
+                if !displays.contains(&display) {
+                    displays.push(display);
+                }
+                if let Some((name, dir)) = f.project {
+                    let dir = dir
+                        .strip_prefix(&workspace)
+                        .unwrap_or(&dir)
+                        .to_string_lossy()
+                        .to_string();
+                    if !projects.iter().any(|(d, _)| *d == dir) {
+                        projects.push((dir, name));
+                    }
+                }
             }
         }
+        let _ = event_tx.send(app::TestEvent::DiscoveryComplete {
+            files: displays,
+            projects,
+        });
     });
     runner_rx
 }
@@ -0,0 +1,185 @@
+//! fzf-style fuzzy matching: the pattern's characters must appear in order,
+//! and the alignment with the best score wins. Matches at word boundaries and
+//! runs of consecutive characters score higher; gaps cost a little.
+
+const SCORE_MATCH: i32 = 16;
+const GAP_START: i32 = -3;
+const GAP_EXTENSION: i32 = -1;
+/// After a separator such as `/`, `-`, `.` or a space, or at the start of the text.
+const BONUS_BOUNDARY: i32 = SCORE_MATCH / 2;
+/// A lowercase → uppercase transition (`userProfile`).
+const BONUS_CAMEL: i32 = BONUS_BOUNDARY - 1;
+const BONUS_CONSECUTIVE: i32 = -(GAP_START + GAP_EXTENSION);
+/// The first pattern character's bonus counts double.
+const FIRST_CHAR_MULTIPLIER: i32 = 2;
+
+/// A successful match: higher scores are better; `positions` are the char
+/// indices of `text` the pattern matched, ascending.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FuzzyMatch {
+    pub score: i32,
+    pub positions: Vec<usize>,
+}
+
+/// Match `pattern` against `text`, case-insensitively. An empty pattern matches
+/// everything with score 0.
+pub fn fuzzy_match(pattern: &str, text: &str) -> Option<FuzzyMatch> {
+    let pattern: Vec<char> = pattern.chars().flat_map(char::to_lowercase).collect();
+    if pattern.is_empty() {
+        return Some(FuzzyMatch {
+            score: 0,
+            positions: Vec::new(),
+        });
+    }
+    let original: Vec<char> = text.chars().collect();
+    let lower: Vec<char> = original
+        .iter()
+        .map(|c| c.to_lowercase().next().unwrap_or(*c))
+        .collect();
+    if !is_subsequence(&pattern, &lower) {
+        return None;
+    }
+
+    let n = lower.len();
+    let m = pattern.len();
+    let bonus: Vec<i32> = (0..n)
+        .map(|j| char_bonus(j.checked_sub(1).map(|k| original[k]), original[j]))
+        .collect();
+
+    // score[i][j]: best score with pattern[..=i] matched and pattern[i] at text[j].
+    // consecutive[i][j]: whether that best alignment put pattern[i - 1] at j - 1.
+    // chunk[i][j]: the bonus of the run of consecutive matches ending at j, which
+    // every character of the run earns (so `spec` after a `/` beats `s/p/e/c`).
+    const NONE: i32 = i32::MIN / 2;
+    let mut score = vec![vec![NONE; n]; m];
+    let mut consecutive = vec![vec![false; n]; m];
+    let mut chunk = vec![vec![0; n]; m];
+    for j in 0..n {
+        if lower[j] == pattern[0] {
+            score[0][j] = SCORE_MATCH + bonus[j] * FIRST_CHAR_MULTIPLIER;
+            chunk[0][j] = bonus[j];
+        }
+    }
+    for i in 1..m {
+        // Best score[i - 1][k] for k < j - 1, with the gap penalty up to j applied.
+        let mut best_gapped = NONE;
+        for j in 1..n {
+            if j >= 2 {
+                best_gapped = (best_gapped + GAP_EXTENSION).max(score[i - 1][j - 2] + GAP_START);
+            }
+            if lower[j] != pattern[i] {
+                continue;
+            }
+            let run_bonus = chunk[i - 1][j - 1].max(bonus[j]).max(BONUS_CONSECUTIVE);
+            let adjacent = score[i - 1][j - 1] + SCORE_MATCH + run_bonus;
+            let gapped = best_gapped + SCORE_MATCH + bonus[j];
+            if adjacent >= gapped && score[i - 1][j - 1] > NONE {
+                score[i][j] = adjacent;
+                consecutive[i][j] = true;
+                chunk[i][j] = run_bonus;
+            } else if best_gapped > NONE {
+                score[i][j] = gapped;
+                chunk[i][j] = bonus[j];
+            }
+        }
+    }
+
+    let (mut j, best) = score[m - 1]
+        .iter()
+        .enumerate()
+        .max_by_key(|&(j, &s)| (s, std::cmp::Reverse(j)))
+        .map(|(j, &s)| (j, s))?;
+    if best <= NONE {
+        return None;
+    }
+
+    let mut positions = vec![0; m];
+    for i in (0..m).rev() {
+        positions[i] = j;
+        if i == 0 {
+            break;
+        }
+        if consecutive[i][j] {
+            j -= 1;
+        } else {
+            // Any predecessor that yields this score through the gap path.
+            let target = score[i][j] - SCORE_MATCH - bonus[j];
+            j = (0..j - 1)
+                .rev()
+                .find(|&k| {
+                    score[i - 1][k] > NONE
+                        && score[i - 1][k] + GAP_START + GAP_EXTENSION * (j - k - 2) as i32
+                            == target
+                })
+                .unwrap_or(0);
+        }
+    }
+    Some(FuzzyMatch {
+        score: best,
+        positions,
+    })
+}
+
+fn is_subsequence(pattern: &[char], text: &[char]) -> bool {
+    let mut rest = pattern.iter().peekable();
+    for c in text {
+        if rest.peek() == Some(&c) {
+            rest.next();
+        }
+    }
+    rest.peek().is_none()
+}
+
+fn char_bonus(prev: Option<char>, c: char) -> i32 {
+    let Some(prev) = prev else {
+        return BONUS_BOUNDARY;
+    };
+    if prev.is_lowercase() && c.is_uppercase() {
+        BONUS_CAMEL
+    } else if !prev.is_alphanumeric() && c.is_alphanumeric() {
+        BONUS_BOUNDARY
+    } else {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn score(pattern: &str, text: &str) -> i32 {
+        fuzzy_match(pattern, text).unwrap().score
+    }
+
+    #[test]
+    fn matches_subsequences_case_insensitively() {
+        let m = fuzzy_match("upcs", "src/User-Profile.component.spec.ts").unwrap();
+        assert_eq!(m.positions.len(), 4);
+        assert!(fuzzy_match("xyz", "src/user.spec.ts").is_none());
+        assert!(fuzzy_match("ba", "ab").is_none());
+    }
+
+    #[test]
+    fn prefers_word_boundaries() {
+        let m = fuzzy_match("upc", "src/user-profile.component.spec.ts").unwrap();
+        assert_eq!(m.positions, [4, 9, 17]);
+    }
+
+    #[test]
+    fn prefers_consecutive_runs() {
+        let m = fuzzy_match("spec", "s/p/e/c/spec.ts").unwrap();
+        assert_eq!(m.positions, [8, 9, 10, 11]);
+    }
+
+    #[test]
+    fn tighter_matches_rank_higher() {
+        assert!(score("login", "src/auth/login.test.ts") > score("login", "src/lo/g/in.test.ts"));
+        assert!(score("spec", "app.spec.ts") > score("spec", "s_p_e_c.ts"));
+    }
+
+    #[test]
+    fn positions_are_char_indices() {
+        let m = fuzzy_match("é", "café").unwrap();
+        assert_eq!(m.positions, [3]);
+    }
+}
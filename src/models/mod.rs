@@ -1,7 +1,10 @@
+pub mod fuzzy;
+pub mod query;
 pub mod result;
 pub mod status;
 pub mod tree;
 
-pub use result::{FailureOutput, RunSummary, TestResult};
+pub use query::Query;
+pub use result::{Attachment, ConsoleLine, ConsoleStream, FailureOutput, RunSummary, TestResult};
 pub use status::TestStatus;
 pub use tree::{NodeKind, TestNode, TestTree};
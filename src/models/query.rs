@@ -0,0 +1,333 @@
+use super::fuzzy::fuzzy_match;
+use super::status::TestStatus;
+
+/// A parsed tree filter: whitespace-separated terms that must all match.
+///
+/// - `text` fuzzily matches (fzf-style) the workspace-relative file path, the
+///   test's full name or a project name
+/// - `status:failed` (or any prefix, e.g. `status:f`) matches the result status
+/// - `file:auth` matches the workspace-relative file path
+/// - `path:src/auth` matches exactly that file, or every file under that directory
+/// - `slow:>500ms`, `slow:<1s`, `slow:200` (at least 200ms) match the duration
+/// - a leading `-` or `!` negates a term; `"two words"` quotes a phrase
+///
+/// Matching is case-insensitive. Qualifiers with a value that doesn't parse are
+/// treated as plain text.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Query {
+    terms: Vec<Term>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Term {
+    negated: bool,
+    kind: TermKind,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum TermKind {
+    Text(String),
+    Status(TestStatus),
+    File(String),
+    Path(String),
+    Duration(Comparison, u64),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Comparison {
+    Greater,
+    AtLeast,
+    Less,
+    AtMost,
+}
+
+/// What a query term is checked against for one tree node.
+pub struct Candidate<'a> {
+    /// Names of the projects above the node's file.
+    pub projects: Vec<&'a str>,
+    /// Workspace-relative path of the file the node belongs to.
+    pub file: Option<&'a str>,
+    /// Suite and test names below the file joined with ` > `; empty for files.
+    pub full_name: String,
+    pub status: TestStatus,
+    pub duration_ms: Option<u64>,
+}
+
+/// Statuses in the order a `status:` prefix is resolved.
+const STATUSES: [(&str, TestStatus); 5] = [
+    ("failed", TestStatus::Failed),
+    ("passed", TestStatus::Passed),
+    ("skipped", TestStatus::Skipped),
+    ("running", TestStatus::Running),
+    ("pending", TestStatus::Pending),
+];
+
+impl Query {
+    pub fn parse(input: &str) -> Self {
+        let terms = tokenize(input)
+            .into_iter()
+            .filter_map(|token| parse_term(&token))
+            .collect();
+        Self { terms }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// Relevance of a matching candidate (the sum of its fuzzy text scores), or
+    /// `None` if any term rejects it.
+    pub fn score(&self, candidate: &Candidate) -> Option<i32> {
+        self.terms.iter().try_fold(0, |total, term| {
+            let score = term.score(candidate);
+            match (score, term.negated) {
+                (Some(score), false) => Some(total + score),
+                (None, true) => Some(total),
+                _ => None,
+            }
+        })
+    }
+
+    /// Char indices of `text` matched by the (non-negated) free-text terms, for
+    /// highlighting.
+    pub fn highlight(&self, text: &str) -> Vec<usize> {
+        let mut positions: Vec<usize> = self
+            .terms
+            .iter()
+            .filter(|term| !term.negated)
+            .filter_map(|term| match &term.kind {
+                TermKind::Text(pattern) => fuzzy_match(pattern, text),
+                _ => None,
+            })
+            .flat_map(|m| m.positions)
+            .collect();
+        positions.sort_unstable();
+        positions.dedup();
+        positions
+    }
+}
+
+impl Term {
+    /// The term's score against `c` ignoring negation: the best fuzzy score for
+    /// text, 0 for a matching qualifier, `None` when it doesn't match.
+    fn score(&self, c: &Candidate) -> Option<i32> {
+        match &self.kind {
+            TermKind::Text(text) => c
+                .file
+                .into_iter()
+                .chain((!c.full_name.is_empty()).then_some(c.full_name.as_str()))
+                .chain(c.projects.iter().copied())
+                .filter_map(|haystack| fuzzy_match(text, haystack))
+                .map(|m| m.score)
+                .max(),
+            TermKind::Status(status) => (c.status == *status).then_some(0),
+            TermKind::File(text) => c
+                .file
+                .is_some_and(|f| f.to_lowercase().contains(text))
+                .then_some(0),
+            TermKind::Path(path) => c
+                .file
+                .is_some_and(|f| {
+                    let f = f.to_lowercase();
+                    f == *path
+                        || f.strip_prefix(path.as_str())
+                            .is_some_and(|r| r.starts_with('/'))
+                })
+                .then_some(0),
+            TermKind::Duration(cmp, ms) => c
+                .duration_ms
+                .is_some_and(|d| match cmp {
+                    Comparison::Greater => d > *ms,
+                    Comparison::AtLeast => d >= *ms,
+                    Comparison::Less => d < *ms,
+                    Comparison::AtMost => d <= *ms,
+                })
+                .then_some(0),
+        }
+    }
+}
+
+/// A `path:` term for a workspace-relative file or directory, quoted when the
+/// path has whitespace in it.
+pub fn path_term(path: &str) -> String {
+    if path.contains(char::is_whitespace) {
+        format!("path:\"{path}\"")
+    } else {
+        format!("path:{path}")
+    }
+}
+
+/// Split on whitespace, keeping `"quoted phrases"` together.
+fn tokenize(input: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    for c in input.chars() {
+        match c {
+            '"' => quoted = !quoted,
+            c if c.is_whitespace() && !quoted => {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+    tokens
+}
+
+fn parse_term(token: &str) -> Option<Term> {
+    let (negated, body) = match token.strip_prefix(['-', '!']) {
+        Some(rest) if !rest.is_empty() => (true, rest),
+        _ => (false, token),
+    };
+    let lower = body.to_lowercase();
+    let kind = match lower.split_once(':') {
+        Some(("status", value)) if !value.is_empty() => STATUSES
+            .iter()
+            .find(|(name, _)| name.starts_with(value))
+            .map(|&(_, status)| TermKind::Status(status)),
+        Some(("file", value)) if !value.is_empty() => Some(TermKind::File(value.to_string())),
+        Some(("path", value)) if !value.trim_end_matches('/').is_empty() => {
+            Some(TermKind::Path(value.trim_end_matches('/').to_string()))
+        }
+        Some(("slow", value)) => parse_duration(value).map(|(cmp, ms)| TermKind::Duration(cmp, ms)),
+        _ => None,
+    }
+    .unwrap_or(TermKind::Text(lower));
+    Some(Term { negated, kind })
+}
+
+/// `>500ms`, `<=2s`, `300` → comparison and milliseconds. No operator means "at least".
+fn parse_duration(value: &str) -> Option<(Comparison, u64)> {
+    let (cmp, rest) = if let Some(rest) = value.strip_prefix(">=") {
+        (Comparison::AtLeast, rest)
+    } else if let Some(rest) = value.strip_prefix("<=") {
+        (Comparison::AtMost, rest)
+    } else if let Some(rest) = value.strip_prefix('>') {
+        (Comparison::Greater, rest)
+    } else if let Some(rest) = value.strip_prefix('<') {
+        (Comparison::Less, rest)
+    } else {
+        (Comparison::AtLeast, value)
+    };
+    let ms = if let Some(n) = rest.strip_suffix("ms") {
+        n.parse().ok()?
+    } else if let Some(n) = rest.strip_suffix('s') {
+        (n.parse::<f64>().ok()? * 1000.0) as u64
+    } else {
+        rest.parse().ok()?
+    };
+    Some((cmp, ms))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matches(query: &str, candidate: &Candidate) -> bool {
+        Query::parse(query).score(candidate).is_some()
+    }
+
+    fn candidate(full_name: &str, status: TestStatus, ms: Option<u64>) -> Candidate<'static> {
+        Candidate {
+            projects: vec!["web"],
+            file: Some("src/auth/login.test.ts"),
+            full_name: full_name.to_string(),
+            status,
+            duration_ms: ms,
+        }
+    }
+
+    #[test]
+    fn text_fuzzily_matches_path_full_name_and_project() {
+        let c = candidate("Login > rejects bad passwords", TestStatus::Passed, None);
+        assert!(matches("login", &c));
+        assert!(matches("authlogin", &c));
+        assert!(matches("BAD PASS", &c));
+        assert!(matches("web", &c));
+        assert!(!matches("signup", &c));
+    }
+
+    #[test]
+    fn quoted_phrase_is_one_term() {
+        let c = candidate("rejects bad passwords", TestStatus::Passed, None);
+        assert!(matches("\"bad passwords\"", &c));
+        assert!(!matches("\"passwords bad\"", &c));
+    }
+
+    #[test]
+    fn qualifiers_match_status_file_and_duration() {
+        let c = candidate("slow one", TestStatus::Failed, Some(800));
+        assert!(matches("status:failed", &c));
+        assert!(matches("status:f", &c));
+        assert!(!matches("status:skipped", &c));
+        assert!(matches("file:auth", &c));
+        assert!(!matches("file:billing", &c));
+        assert!(matches("slow:>500ms", &c));
+        assert!(!matches("slow:>1s", &c));
+        assert!(matches("slow:<=0.8s", &c));
+        assert!(matches("slow:800", &c));
+    }
+
+    #[test]
+    fn path_matches_one_file_or_directory_exactly() {
+        let c = candidate("t", TestStatus::Passed, None);
+        assert!(matches("path:src/auth/login.test.ts", &c));
+        assert!(matches("path:src/auth", &c));
+        assert!(matches("path:src/auth/", &c));
+        assert!(!matches("path:src/au", &c));
+        assert!(!matches("path:login.test.ts", &c));
+        assert!(!matches("path:src/auth/login.test", &c));
+        assert_eq!(path_term("src/my dir"), "path:\"src/my dir\"");
+        assert_eq!(
+            Query::parse(&path_term("src/my dir")),
+            Query::parse("path:\"src/my dir\"")
+        );
+    }
+
+    #[test]
+    fn untimed_tests_are_never_slow() {
+        let c = candidate("t", TestStatus::Pending, None);
+        assert!(!matches("slow:>0", &c));
+        assert!(matches("-slow:>0", &c));
+    }
+
+    #[test]
+    fn negation_and_conjunction() {
+        let c = candidate("login works", TestStatus::Passed, Some(5));
+        assert!(matches("login -status:failed", &c));
+        assert!(!matches("login !status:passed", &c));
+        assert!(!matches("login signup", &c));
+    }
+
+    #[test]
+    fn malformed_qualifiers_fall_back_to_text() {
+        let c = candidate("status:bogus", TestStatus::Passed, None);
+        assert!(matches("status:bogus", &c));
+        assert!(!matches(
+            "slow:fast",
+            &candidate("x", TestStatus::Passed, None)
+        ));
+        assert!(matches("-", &candidate("a-b", TestStatus::Passed, None)));
+        assert!(Query::parse("   ").is_empty());
+    }
+
+    #[test]
+    fn closer_matches_score_higher() {
+        let query = Query::parse("adds");
+        let exact = query.score(&candidate("math > adds", TestStatus::Passed, None));
+        let scattered = query.score(&candidate("a dog dies", TestStatus::Passed, None));
+        assert!(exact > scattered);
+    }
+
+    #[test]
+    fn highlight_ignores_qualifiers_and_negated_terms() {
+        let query = Query::parse("ad status:failed -zz");
+        assert_eq!(query.highlight("math > adds"), [7, 8]);
+        assert!(query.highlight("nothing").is_empty());
+    }
+}
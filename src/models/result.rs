@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use serde::{Deserialize, Serialize};
 
 use super::status::TestStatus;
@@ -6,7 +8,17 @@ use super::status::TestStatus;
 pub struct TestResult {
     pub status: TestStatus,
     pub duration_ms: Option<u64>,
-    pub failure: Option<FailureOutput>,
+    /// Every error the test produced, in the order raised. `expect.soft` and
+    /// similar can report more than one.
+    #[serde(default)]
+    pub failures: Vec<FailureOutput>,
+}
+
+impl TestResult {
+    /// The first error — the one the test is considered to have failed on.
+    pub fn failure(&self) -> Option<&FailureOutput> {
+        self.failures.first()
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -16,15 +28,28 @@ pub struct FailureOutput {
     pub actual: Option<String>,
     /// Parsed structured form of `expected`, if the runner could produce it.
     /// May be an `Object` or `Array` value.
-    #[serde(skip)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub expected_parsed: Option<serde_json::Value>,
     /// Parsed structured form of `actual`, if the runner could produce it.
     /// May be an `Object` or `Array` value.
-    #[serde(skip)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub actual_parsed: Option<serde_json::Value>,
     pub diff: Option<String>,
     pub source_snippet: Option<String>,
     pub stack_trace: Option<String>,
+    /// Files the runner saved for the failed test, such as Playwright traces
+    /// and screenshots.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attachments: Vec<Attachment>,
+}
+
+/// A file a runner saved alongside a test result.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Attachment {
+    /// The runner's name for it, e.g. `trace` or `screenshot`.
+    pub name: String,
+    pub content_type: String,
+    pub path: PathBuf,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -34,4 +59,29 @@ pub struct RunSummary {
     pub failed: usize,
     pub skipped: usize,
     pub duration: u64,
+    /// New snapshots written during the run.
+    #[serde(default)]
+    pub snapshots_written: usize,
+    /// Existing snapshots rewritten because they no longer matched.
+    #[serde(default)]
+    pub snapshots_updated: usize,
+    /// Snapshots no test checked; left in place unless removed by an update.
+    #[serde(default)]
+    pub snapshots_obsolete: usize,
+}
+
+/// The stream a line of console output was written to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConsoleStream {
+    #[default]
+    Stdout,
+    Stderr,
+}
+
+/// Output a test file logged (`console.log`, `console.error`, …) while it ran.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConsoleLine {
+    pub stream: ConsoleStream,
+    pub text: String,
 }
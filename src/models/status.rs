@@ -1,7 +1,7 @@
 use ratatui::style::Color;
 use serde::{Deserialize, Serialize};
 
-use crate::ui::theme;
+use crate::ui::theme::Theme;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub enum TestStatus {
@@ -24,13 +24,13 @@ impl TestStatus {
         }
     }
 
-    pub fn color(&self) -> Color {
+    pub fn color(&self, theme: &Theme) -> Color {
         match self {
-            TestStatus::Passed => theme::GREEN,
-            TestStatus::Failed => theme::RED,
-            TestStatus::Running => theme::YELLOW,
-            TestStatus::Skipped => theme::OVERLAY0,
-            TestStatus::Pending => theme::SUBTEXT0,
+            TestStatus::Passed => theme.green,
+            TestStatus::Failed => theme.red,
+            TestStatus::Running => theme.yellow,
+            TestStatus::Skipped => theme.overlay0,
+            TestStatus::Pending => theme.subtext0,
         }
     }
 
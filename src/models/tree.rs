@@ -1,7 +1,9 @@
-use std::collections::HashSet;
-use std::path::PathBuf;
+use std::cell::{RefCell, RefMut};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 
-use super::result::TestResult;
+use super::query::{Candidate, Query};
+use super::result::{ConsoleLine, TestResult};
 use super::status::TestStatus;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -25,7 +27,9 @@ pub struct TestNode {
     pub status: TestStatus,
     pub result: Option<TestResult>,
     pub expanded: bool,
-    pub console_output: Vec<String>,
+    /// Console output logged while the node ran: a test's own lines, or every
+    /// line of a file, including those logged outside any test.
+    pub console_output: Vec<ConsoleLine>,
     /// Source location (line, column) for this test, if known.
     pub location: Option<(u32, u32)>,
     /// Tombstoned nodes are children removed when a file re-ran with fewer tests.
@@ -33,12 +37,32 @@ pub struct TestNode {
     /// Marked at FileStarted; cleared when the test reports a result. Still-stale nodes
     /// are purged at FileFinished, meaning they no longer exist in the new run.
     pub stale: bool,
+    /// Result was restored from the previous session's snapshot and hasn't been
+    /// re-run yet. Cleared as soon as a fresh result arrives.
+    pub restored: bool,
 }
 
 #[derive(Debug, Default)]
 pub struct TestTree {
     nodes: Vec<TestNode>,
     root_ids: Vec<usize>,
+    /// File node ids keyed by their normalized workspace-relative path.
+    file_index: HashMap<String, usize>,
+    /// Bumped by every change that can change what a query matches.
+    generation: u64,
+    /// Matches of the last query, reused until the query or the tree changes;
+    /// the tree is filtered several times a frame.
+    match_cache: RefCell<MatchCache>,
+}
+
+/// A query's scored matches and highlighted positions for one generation of
+/// the tree.
+#[derive(Debug, Default)]
+struct MatchCache {
+    generation: u64,
+    query: Option<Query>,
+    scored: Option<Vec<(usize, i32)>>,
+    highlights: HashMap<String, Vec<usize>>,
 }
 
 impl TestTree {
@@ -73,8 +97,14 @@ impl TestTree {
         path: Option<PathBuf>,
         parent: Option<usize>,
     ) -> usize {
+        self.generation += 1;
         let id = self.nodes.len();
         let expanded = !matches!(kind, NodeKind::Test);
+        if kind == NodeKind::File
+            && let Some(ref p) = path
+        {
+            self.file_index.insert(path_key(p), id);
+        }
         self.nodes.push(TestNode {
             id,
             parent,
@@ -89,6 +119,7 @@ impl TestTree {
             location: None,
             deleted: false,
             stale: false,
+            restored: false,
         });
         id
     }
@@ -102,6 +133,7 @@ impl TestTree {
     }
 
     pub fn get_mut(&mut self, id: usize) -> Option<&mut TestNode> {
+        self.generation += 1;
         self.nodes.get_mut(id)
     }
 
@@ -123,12 +155,12 @@ impl TestTree {
             .find(|&id| self.nodes.get(id).is_some_and(|n| n.name == name))
     }
 
-    /// Find any non-deleted File node whose name equals `filename` (the basename, not full path).
-    pub fn find_file_by_filename(&self, filename: &str) -> Option<usize> {
-        self.nodes
-            .iter()
-            .find(|n| !n.deleted && n.kind == NodeKind::File && n.name == filename)
-            .map(|n| n.id)
+    /// Find the non-deleted File node for a workspace-relative path.
+    pub fn find_file_by_path(&self, path: &Path) -> Option<usize> {
+        self.file_index
+            .get(&path_key(path))
+            .copied()
+            .filter(|&id| !self.nodes[id].deleted)
     }
 
     /// Returns a flat list of visible node ids (respecting expanded/collapsed state),
@@ -151,41 +183,30 @@ impl TestTree {
         }
     }
 
-    /// Returns visible nodes filtered by a case-insensitive substring match on File/Project names.
-    /// Matching nodes show all descendants; ancestor nodes of matches are also shown.
-    pub fn visible_nodes_filtered(&self, query: &str) -> Vec<(usize, usize)> {
-        let query_lower = query.to_lowercase();
-
-        // Nodes whose own name matches (Files and Projects only — tests/suites aren't filtered)
-        let direct_matches: HashSet<usize> = self
-            .nodes
-            .iter()
-            .filter(|n| {
-                !n.deleted
-                    && matches!(n.kind, NodeKind::File | NodeKind::Project)
-                    && n.name.to_lowercase().contains(&query_lower)
-            })
-            .map(|n| n.id)
-            .collect();
-
-        if direct_matches.is_empty() {
+    /// Returns visible nodes filtered by `query` (see [`Query`]). Tests and files
+    /// that haven't run yet are matched; the ancestors of every match stay visible.
+    pub fn visible_nodes_filtered(&self, query: &Query) -> Vec<(usize, usize)> {
+        let matches = self.query_matches(query);
+        if matches.is_empty() {
             return vec![];
         }
 
-        // Ancestor IDs of every direct match (so the path to matches stays visible)
-        let mut ancestor_ids: HashSet<usize> = HashSet::new();
-        for &id in &direct_matches {
+        // Ancestor IDs of every match (so the path to matches stays visible)
+        let mut shown = matches;
+        for id in shown.clone() {
             let mut cur = self.nodes[id].parent;
             while let Some(p) = cur {
-                ancestor_ids.insert(p);
+                if !shown.insert(p) {
+                    break;
+                }
                 cur = self.nodes[p].parent;
             }
         }
 
         let mut result = Vec::new();
         for &root_id in &self.root_ids {
-            if direct_matches.contains(&root_id) || ancestor_ids.contains(&root_id) {
-                self.collect_filtered(root_id, 0, &direct_matches, &ancestor_ids, &mut result);
+            if shown.contains(&root_id) {
+                self.collect_filtered(root_id, 0, &shown, &mut result);
             }
         }
         result
@@ -195,33 +216,133 @@ impl TestTree {
         &self,
         id: usize,
         depth: usize,
-        direct_matches: &HashSet<usize>,
-        ancestor_ids: &HashSet<usize>,
+        shown: &HashSet<usize>,
         result: &mut Vec<(usize, usize)>,
     ) {
         result.push((id, depth));
-        let node = &self.nodes[id];
-        if !node.expanded {
+        if !self.nodes[id].expanded {
             return;
         }
-        if direct_matches.contains(&id) {
-            // Show everything under a directly matching node
-            for &child_id in &node.children {
-                self.collect_visible(child_id, depth + 1, result);
+        for &child_id in &self.nodes[id].children {
+            if shown.contains(&child_id) {
+                self.collect_filtered(child_id, depth + 1, shown, result);
             }
-        } else {
-            // Only descend into children that lead to a match
-            for &child_id in &node.children {
-                if direct_matches.contains(&child_id) || ancestor_ids.contains(&child_id) {
-                    self.collect_filtered(
-                        child_id,
-                        depth + 1,
-                        direct_matches,
-                        ancestor_ids,
-                        result,
-                    );
+        }
+    }
+
+    /// Ids of the tests matching `query`, plus the matching files that have no
+    /// tests yet (they haven't run, so only their own path can match).
+    pub fn query_matches(&self, query: &Query) -> HashSet<usize> {
+        self.scored_matches(query)
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect()
+    }
+
+    /// The matches of `query` (as in [`Self::query_matches`]), best score first
+    /// and in tree order among equal scores.
+    pub fn ranked_matches(&self, query: &Query) -> Vec<usize> {
+        let mut matches = self.scored_matches(query);
+        matches.sort_by_key(|&(id, score)| (std::cmp::Reverse(score), id));
+        matches.into_iter().map(|(id, _)| id).collect()
+    }
+
+    fn scored_matches(&self, query: &Query) -> Vec<(usize, i32)> {
+        if let Some(scored) = &self.match_cache(query).scored {
+            return scored.clone();
+        }
+        let mut matches = Vec::new();
+        for &root_id in &self.root_ids {
+            self.collect_matches(root_id, query, &mut Vec::new(), None, &mut matches);
+        }
+        self.match_cache(query).scored = Some(matches.clone());
+        matches
+    }
+
+    /// Char indices of `text` the query's text terms match (see
+    /// [`Query::highlight`]), remembered along with the query's matches.
+    pub fn highlight(&self, query: &Query, text: &str) -> Vec<usize> {
+        let mut cache = self.match_cache(query);
+        if let Some(positions) = cache.highlights.get(text) {
+            return positions.clone();
+        }
+        let positions = query.highlight(text);
+        cache.highlights.insert(text.to_string(), positions.clone());
+        positions
+    }
+
+    /// The cache for `query`, emptied first if it was filled for another query
+    /// or before the tree last changed.
+    fn match_cache(&self, query: &Query) -> RefMut<'_, MatchCache> {
+        let mut cache = self.match_cache.borrow_mut();
+        if cache.generation != self.generation || cache.query.as_ref() != Some(query) {
+            *cache = MatchCache {
+                generation: self.generation,
+                query: Some(query.clone()),
+                ..MatchCache::default()
+            };
+        }
+        cache
+    }
+
+    /// `names` holds the project names above a file, then the suite names below it.
+    fn collect_matches<'a>(
+        &'a self,
+        id: usize,
+        query: &Query,
+        names: &mut Vec<&'a str>,
+        file: Option<(&'a str, usize)>,
+        matches: &mut Vec<(usize, i32)>,
+    ) {
+        let node = &self.nodes[id];
+        if node.deleted {
+            return;
+        }
+        let file = match node.kind {
+            NodeKind::File => node
+                .path
+                .as_deref()
+                .and_then(Path::to_str)
+                .map(|path| (path, names.len())),
+            _ => file,
+        };
+
+        let is_leaf = match node.kind {
+            NodeKind::Test => true,
+            NodeKind::File => self.subtree_test_counts(id).2 == 0,
+            _ => false,
+        };
+        if is_leaf {
+            let projects_end = file.map_or(names.len(), |(_, depth)| depth);
+            let mut full_name = names[projects_end..].join(" > ");
+            if node.kind == NodeKind::Test {
+                if !full_name.is_empty() {
+                    full_name.push_str(" > ");
                 }
+                full_name.push_str(&node.name);
+            }
+            let candidate = Candidate {
+                projects: names[..projects_end].to_vec(),
+                file: file.map(|(path, _)| path),
+                full_name,
+                status: node.status,
+                duration_ms: node.result.as_ref().and_then(|r| r.duration_ms),
+            };
+            if let Some(score) = query.score(&candidate) {
+                matches.push((id, score));
             }
+            return;
+        }
+
+        let pushed = matches!(node.kind, NodeKind::Project | NodeKind::Suite);
+        if pushed {
+            names.push(&node.name);
+        }
+        for &child_id in &node.children {
+            self.collect_matches(child_id, query, names, file, matches);
+        }
+        if pushed {
+            names.pop();
         }
     }
 
@@ -249,12 +370,27 @@ impl TestTree {
         }
     }
 
+    /// The enclosing file's path and the node's full name below it
+    /// (suite and test names joined with ` > `).
+    pub fn file_and_full_name(&self, id: usize) -> Option<(&Path, String)> {
+        let mut names = Vec::new();
+        let mut current = self.nodes.get(id)?;
+        while current.kind != NodeKind::File {
+            names.push(current.name.as_str());
+            current = self.nodes.get(current.parent?)?;
+        }
+        names.reverse();
+        Some((current.path.as_deref()?, names.join(" > ")))
+    }
+
     /// Update a test node's result and propagate status up to ancestors.
     pub fn update_result(&mut self, id: usize, result: TestResult) {
+        self.generation += 1;
         let status = result.status;
         if let Some(node) = self.nodes.get_mut(id) {
             node.status = status;
             node.result = Some(result);
+            node.restored = false;
         }
         self.propagate_status(id);
     }
@@ -339,6 +475,34 @@ impl TestTree {
         }
     }
 
+    /// Drop the console output of a node and everything under it.
+    pub fn clear_console(&mut self, id: usize) {
+        self.nodes[id].console_output.clear();
+        let children = self.nodes[id].children.clone();
+        for child_id in children {
+            self.clear_console(child_id);
+        }
+    }
+
+    /// The console output to show for `id`: a test's own lines, or those of the
+    /// whole file it's in for any other node or when `whole_file` is set.
+    pub fn console_output(&self, id: usize, whole_file: bool) -> &[ConsoleLine] {
+        let Some(node) = self.get(id) else {
+            return &[];
+        };
+        if node.kind == NodeKind::Test && !whole_file {
+            return &node.console_output;
+        }
+        let mut current = Some(id);
+        while let Some(node) = current.and_then(|id| self.get(id)) {
+            if node.kind == NodeKind::File {
+                return &node.console_output;
+            }
+            current = node.parent;
+        }
+        &[]
+    }
+
     /// Remove any descendants still marked stale after a file run completes.
     /// These are tests/suites that no longer exist in the file.
     pub fn purge_stale_children(&mut self, id: usize) {
@@ -358,7 +522,13 @@ impl TestTree {
 
     /// Mark a node and its entire subtree as deleted.
     fn delete_subtree(&mut self, id: usize) {
+        self.generation += 1;
         self.nodes[id].deleted = true;
+        if self.nodes[id].kind == NodeKind::File
+            && let Some(ref p) = self.nodes[id].path
+        {
+            self.file_index.remove(&path_key(p));
+        }
         let children = self.nodes[id].children.clone();
         for child_id in children {
             self.delete_subtree(child_id);
@@ -412,12 +582,206 @@ impl TestTree {
             + self_count
     }
 
+    /// Paths of the File-kind nodes in the subtree rooted at `id`.
+    pub fn subtree_file_paths(&self, id: usize) -> Vec<&Path> {
+        let node = &self.nodes[id];
+        if node.deleted {
+            return Vec::new();
+        }
+        if node.kind == NodeKind::File {
+            return node.path.as_deref().into_iter().collect();
+        }
+        node.children
+            .iter()
+            .flat_map(|&c| self.subtree_file_paths(c))
+            .collect()
+    }
+
     /// Reset all nodes to Pending (for re-run).
     pub fn reset(&mut self) {
+        self.generation += 1;
         for node in &mut self.nodes {
             node.status = TestStatus::Pending;
             node.result = None;
+            node.restored = false;
             node.console_output.clear();
         }
     }
 }
+
+/// Normalize a workspace-relative path into a `file_index` key:
+/// forward slashes, no leading `./`.
+fn path_key(path: &Path) -> String {
+    let s = path.to_string_lossy().replace('\\', "/");
+    s.trim_start_matches("./").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn files_with_same_basename_are_distinct() {
+        let mut tree = TestTree::new();
+        let root = tree.add_root(NodeKind::Workspace, "apps".into(), None);
+        let a = tree.add_child(
+            root,
+            NodeKind::File,
+            "index.spec.ts".into(),
+            Some(PathBuf::from("apps/a/src/index.spec.ts")),
+        );
+        let b = tree.add_child(
+            root,
+            NodeKind::File,
+            "index.spec.ts".into(),
+            Some(PathBuf::from("apps/b/src/index.spec.ts")),
+        );
+
+        assert_eq!(
+            tree.find_file_by_path(Path::new("apps/a/src/index.spec.ts")),
+            Some(a)
+        );
+        assert_eq!(
+            tree.find_file_by_path(Path::new("apps/b/src/index.spec.ts")),
+            Some(b)
+        );
+        assert_eq!(tree.find_file_by_path(Path::new("index.spec.ts")), None);
+    }
+
+    #[test]
+    fn file_lookup_normalizes_separators_and_dot_prefix() {
+        let mut tree = TestTree::new();
+        let id = tree.add_root(
+            NodeKind::File,
+            "foo.test.ts".into(),
+            Some(PathBuf::from("src/foo.test.ts")),
+        );
+        assert_eq!(
+            tree.find_file_by_path(Path::new("./src/foo.test.ts")),
+            Some(id)
+        );
+        assert_eq!(
+            tree.find_file_by_path(Path::new("src\\foo.test.ts")),
+            Some(id)
+        );
+    }
+
+    #[test]
+    fn file_and_full_name_joins_suites_below_the_file() {
+        let mut tree = TestTree::new();
+        let file = tree.add_root(
+            NodeKind::File,
+            "a.test.ts".into(),
+            Some(PathBuf::from("src/a.test.ts")),
+        );
+        let suite = tree.add_child(file, NodeKind::Suite, "math".into(), None);
+        let test = tree.add_child(suite, NodeKind::Test, "adds".into(), None);
+
+        let (path, name) = tree.file_and_full_name(test).unwrap();
+        assert_eq!(path, Path::new("src/a.test.ts"));
+        assert_eq!(name, "math > adds");
+        assert_eq!(tree.file_and_full_name(file).unwrap().1, "");
+    }
+
+    /// src/a.test.ts › math › {adds (failed, 900ms), subtracts (passed)}, src/b.test.ts (not run)
+    fn filter_tree() -> (TestTree, [usize; 5]) {
+        let mut tree = TestTree::new();
+        let root = tree.add_root(NodeKind::Workspace, "src".into(), None);
+        let a = tree.add_child(
+            root,
+            NodeKind::File,
+            "a.test.ts".into(),
+            Some(PathBuf::from("src/a.test.ts")),
+        );
+        let suite = tree.add_child(a, NodeKind::Suite, "math".into(), None);
+        let adds = tree.add_child(suite, NodeKind::Test, "adds".into(), None);
+        let subtracts = tree.add_child(suite, NodeKind::Test, "subtracts".into(), None);
+        let b = tree.add_child(
+            root,
+            NodeKind::File,
+            "b.test.ts".into(),
+            Some(PathBuf::from("src/b.test.ts")),
+        );
+        tree.update_result(
+            adds,
+            TestResult {
+                status: TestStatus::Failed,
+                duration_ms: Some(900),
+                failures: vec![],
+            },
+        );
+        tree.update_result(
+            subtracts,
+            TestResult {
+                status: TestStatus::Passed,
+                duration_ms: Some(3),
+                failures: vec![],
+            },
+        );
+        (tree, [root, a, suite, adds, b])
+    }
+
+    fn filtered_ids(tree: &TestTree, query: &str) -> Vec<usize> {
+        tree.visible_nodes_filtered(&Query::parse(query))
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect()
+    }
+
+    #[test]
+    fn filter_matches_test_names_and_keeps_ancestors() {
+        let (tree, [root, a, suite, adds, _]) = filter_tree();
+        assert_eq!(filtered_ids(&tree, "adds"), [root, a, suite, adds]);
+    }
+
+    #[test]
+    fn filter_by_file_name_shows_its_tests_and_unrun_files() {
+        let (tree, [root, _, _, _, b]) = filter_tree();
+        assert_eq!(filtered_ids(&tree, "b.test"), [root, b]);
+        assert_eq!(filtered_ids(&tree, "math").len(), 5);
+    }
+
+    #[test]
+    fn filter_qualifiers_and_negation() {
+        let (tree, [root, a, suite, adds, b]) = filter_tree();
+        assert_eq!(filtered_ids(&tree, "status:failed"), [root, a, suite, adds]);
+        assert_eq!(filtered_ids(&tree, "slow:>500ms"), [root, a, suite, adds]);
+        assert_eq!(
+            filtered_ids(&tree, "-status:passed"),
+            [root, a, suite, adds, b]
+        );
+        assert!(filtered_ids(&tree, "file:c.test").is_empty());
+    }
+
+    #[test]
+    fn cached_matches_follow_tree_changes() {
+        let (mut tree, [root, a, suite, adds, _]) = filter_tree();
+        let failed = Query::parse("status:failed");
+        assert_eq!(tree.ranked_matches(&failed), [adds]);
+        assert_eq!(tree.highlight(&Query::parse("ad"), "math > adds"), [7, 8]);
+
+        tree.update_result(
+            adds,
+            TestResult {
+                status: TestStatus::Passed,
+                duration_ms: Some(1),
+                failures: vec![],
+            },
+        );
+        assert!(tree.ranked_matches(&failed).is_empty());
+        assert_eq!(filtered_ids(&tree, "adds"), [root, a, suite, adds]);
+    }
+
+    #[test]
+    fn ranked_matches_put_the_closest_match_first() {
+        let (tree, [_, _, _, adds, _]) = filter_tree();
+        let ranked = tree.ranked_matches(&Query::parse("adds"));
+        assert_eq!(ranked[0], adds);
+    }
+
+    #[test]
+    fn filter_is_fuzzy_over_paths() {
+        let (tree, [root, _, _, _, b]) = filter_tree();
+        assert_eq!(filtered_ids(&tree, "srcbtest"), [root, b]);
+    }
+}
@@ -0,0 +1,170 @@
+use serde::Serialize;
+
+use super::{FileReport, TestReport};
+use crate::models::{RunSummary, TestStatus};
+
+/// Bumped whenever a field is removed or changes meaning.
+const SCHEMA_VERSION: u32 = 2;
+
+#[derive(Serialize)]
+struct Report<'a> {
+    version: u32,
+    summary: Summary,
+    files: Vec<File<'a>>,
+}
+
+#[derive(Serialize)]
+struct Summary {
+    total: usize,
+    passed: usize,
+    failed: usize,
+    skipped: usize,
+    duration_ms: u64,
+}
+
+#[derive(Serialize)]
+struct File<'a> {
+    path: &'a str,
+    status: &'static str,
+    duration_ms: u64,
+    tests: Vec<Test<'a>>,
+    console: Vec<&'a str>,
+}
+
+#[derive(Serialize)]
+struct Test<'a> {
+    name: &'a str,
+    status: &'static str,
+    duration_ms: Option<u64>,
+    location: Option<Location>,
+    failures: Vec<Failure<'a>>,
+}
+
+#[derive(Serialize)]
+struct Location {
+    line: u32,
+    column: u32,
+}
+
+#[derive(Serialize)]
+struct Failure<'a> {
+    message: &'a str,
+    expected: Option<&'a str>,
+    actual: Option<&'a str>,
+    diff: Option<&'a str>,
+    stack: Option<&'a str>,
+}
+
+/// Render results as a versioned JSON document.
+pub fn render(files: &[FileReport<'_>], summary: Option<&RunSummary>) -> String {
+    let count = |status| files.iter().map(|f| f.count(status)).sum::<usize>();
+    let total: usize = files.iter().map(|f| f.tests.len()).sum();
+    let passed = count(TestStatus::Passed);
+    let failed = count(TestStatus::Failed);
+    let report = Report {
+        version: SCHEMA_VERSION,
+        summary: Summary {
+            total,
+            passed,
+            failed,
+            skipped: total - passed - failed,
+            duration_ms: summary
+                .map(|s| s.duration)
+                .unwrap_or_else(|| files.iter().map(FileReport::duration_ms).sum()),
+        },
+        files: files.iter().map(file).collect(),
+    };
+    serde_json::to_string_pretty(&report).expect("report is always serializable")
+}
+
+fn file<'a>(report: &'a FileReport<'a>) -> File<'a> {
+    let status = if report.count(TestStatus::Failed) > 0 {
+        TestStatus::Failed
+    } else if report.count(TestStatus::Passed) > 0 {
+        TestStatus::Passed
+    } else {
+        TestStatus::Skipped
+    };
+    File {
+        path: &report.path,
+        status: status_name(status),
+        duration_ms: report.duration_ms(),
+        tests: report.tests.iter().map(test).collect(),
+        console: report.console.iter().map(|l| l.text.as_str()).collect(),
+    }
+}
+
+fn test<'a>(report: &'a TestReport<'a>) -> Test<'a> {
+    Test {
+        name: &report.name,
+        status: status_name(report.status),
+        duration_ms: report.duration_ms,
+        location: report
+            .location
+            .map(|(line, column)| Location { line, column }),
+        failures: report
+            .failures
+            .iter()
+            .map(|f| Failure {
+                message: &f.message,
+                expected: f.expected.as_deref(),
+                actual: f.actual.as_deref(),
+                diff: f.diff.as_deref(),
+                stack: f.stack_trace.as_deref(),
+            })
+            .collect(),
+    }
+}
+
+/// Stable lowercase names, independent of the enum's `Debug`/serde spelling.
+fn status_name(status: TestStatus) -> &'static str {
+    match status {
+        TestStatus::Passed => "passed",
+        TestStatus::Failed => "failed",
+        TestStatus::Skipped => "skipped",
+        // Never reported a result during the run.
+        TestStatus::Pending | TestStatus::Running => "skipped",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_versioned_summary_and_tests() {
+        let files = vec![FileReport {
+            path: "src/a.test.ts".into(),
+            tests: vec![
+                TestReport {
+                    name: "a > passes".into(),
+                    status: TestStatus::Passed,
+                    duration_ms: Some(4),
+                    location: Some((3, 5)),
+                    failures: &[],
+                },
+                TestReport {
+                    name: "a > never ran".into(),
+                    status: TestStatus::Pending,
+                    duration_ms: None,
+                    location: None,
+                    failures: &[],
+                },
+            ],
+            console: &[],
+        }];
+        let value: serde_json::Value = serde_json::from_str(&render(&files, None)).unwrap();
+
+        assert_eq!(value["version"], 2);
+        assert_eq!(value["summary"]["total"], 2);
+        assert_eq!(value["summary"]["passed"], 1);
+        assert_eq!(value["summary"]["skipped"], 1);
+        assert_eq!(value["summary"]["duration_ms"], 4);
+        let file = &value["files"][0];
+        assert_eq!(file["path"], "src/a.test.ts");
+        assert_eq!(file["status"], "passed");
+        assert_eq!(file["tests"][0]["location"]["line"], 3);
+        assert_eq!(file["tests"][1]["status"], "skipped");
+        assert_eq!(file["tests"][1]["failures"], serde_json::json!([]));
+    }
+}
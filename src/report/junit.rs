@@ -0,0 +1,271 @@
+use std::fmt::Write;
+
+use super::{FileReport, TestReport};
+use crate::models::{ConsoleStream, RunSummary, TestStatus};
+
+/// Render results as a JUnit XML document, one `<testsuite>` per file.
+pub fn render(files: &[FileReport<'_>], summary: Option<&RunSummary>) -> String {
+    let tests: usize = files.iter().map(|f| f.tests.len()).sum();
+    let failures: usize = files.iter().map(|f| f.count(TestStatus::Failed)).sum();
+    let skipped = tests
+        - failures
+        - files
+            .iter()
+            .map(|f| f.count(TestStatus::Passed))
+            .sum::<usize>();
+    let duration_ms = summary
+        .map(|s| s.duration)
+        .unwrap_or_else(|| files.iter().map(FileReport::duration_ms).sum());
+
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(
+        out,
+        "<testsuites name=\"lens\" tests=\"{tests}\" failures=\"{failures}\" skipped=\"{skipped}\" time=\"{}\">",
+        seconds(duration_ms)
+    );
+    for file in files {
+        write_suite(&mut out, file);
+    }
+    out.push_str("</testsuites>\n");
+    out
+}
+
+fn write_suite(out: &mut String, file: &FileReport<'_>) {
+    let failures = file.count(TestStatus::Failed);
+    let skipped = file.tests.len() - failures - file.count(TestStatus::Passed);
+    let _ = writeln!(
+        out,
+        "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{failures}\" skipped=\"{skipped}\" time=\"{}\">",
+        escape(&file.path),
+        file.tests.len(),
+        seconds(file.duration_ms())
+    );
+    for test in &file.tests {
+        write_case(out, &file.path, test);
+    }
+    for (stream, tag) in [
+        (ConsoleStream::Stdout, "system-out"),
+        (ConsoleStream::Stderr, "system-err"),
+    ] {
+        let lines: Vec<&str> = file
+            .console
+            .iter()
+            .filter(|l| l.stream == stream)
+            .map(|l| l.text.as_str())
+            .collect();
+        if !lines.is_empty() {
+            let _ = writeln!(out, "    <{tag}>{}</{tag}>", escape(&lines.join("\n")));
+        }
+    }
+    out.push_str("  </testsuite>\n");
+}
+
+fn write_case(out: &mut String, path: &str, test: &TestReport<'_>) {
+    let _ = write!(
+        out,
+        "    <testcase name=\"{}\" classname=\"{}\" file=\"{}\"",
+        escape(&test.name),
+        escape(path),
+        escape(path)
+    );
+    if let Some((line, _)) = test.location {
+        let _ = write!(out, " line=\"{line}\"");
+    }
+    let _ = write!(out, " time=\"{}\"", seconds(test.duration_ms.unwrap_or(0)));
+
+    match test.status {
+        TestStatus::Passed => out.push_str("/>\n"),
+        TestStatus::Failed => {
+            out.push_str(">\n");
+            let message = test.failures.first().map_or("", |f| f.message.as_str());
+            let _ = writeln!(
+                out,
+                "      <failure message=\"{}\">{}</failure>",
+                escape(first_line(message)),
+                escape(&failure_body(test))
+            );
+            out.push_str("    </testcase>\n");
+        }
+        // Tests that never reported a result are counted as skipped.
+        TestStatus::Skipped | TestStatus::Pending | TestStatus::Running => {
+            out.push_str(">\n      <skipped/>\n    </testcase>\n");
+        }
+    }
+}
+
+/// All of the test's errors, separated by blank lines.
+fn failure_body(test: &TestReport<'_>) -> String {
+    let mut body = String::new();
+    for failure in test.failures {
+        if !body.is_empty() {
+            body.push_str("\n\n");
+        }
+        body.push_str(&failure.message);
+        if let Some(expected) = &failure.expected {
+            let _ = write!(body, "\n\nExpected: {expected}");
+        }
+        if let Some(actual) = &failure.actual {
+            let _ = write!(body, "\nReceived: {actual}");
+        }
+        if let Some(stack) = &failure.stack_trace {
+            let _ = write!(body, "\n\n{stack}");
+        }
+    }
+    body
+}
+
+fn first_line(s: &str) -> &str {
+    s.lines().next().unwrap_or("")
+}
+
+fn seconds(ms: u64) -> String {
+    format!("{:.3}", ms as f64 / 1000.0)
+}
+
+/// Escape text for use in XML attributes and character data, dropping ANSI
+/// escape sequences and the control characters XML 1.0 cannot represent.
+fn escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\x1b' => skip_escape_sequence(&mut chars),
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            '\t' | '\n' | '\r' => out.push(c),
+            c if (c as u32) < 0x20 => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Skip the rest of an escape sequence whose ESC was just read: a CSI
+/// (`ESC [`, up to its final byte) or an OSC (`ESC ]`, up to BEL or `ESC \`).
+/// Anything else after the ESC is kept.
+fn skip_escape_sequence(chars: &mut std::iter::Peekable<std::str::Chars>) {
+    match chars.next_if(|&c| c == '[' || c == ']') {
+        Some('[') => {
+            for c in chars.by_ref() {
+                if ('\x40'..='\x7e').contains(&c) {
+                    break;
+                }
+            }
+        }
+        Some(']') => {
+            while let Some(c) = chars.next() {
+                if c == '\x07' || (c == '\x1b' && chars.next_if_eq(&'\\').is_some()) {
+                    break;
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ConsoleLine, FailureOutput};
+
+    fn test(name: &str, status: TestStatus) -> TestReport<'static> {
+        TestReport {
+            name: name.into(),
+            status,
+            duration_ms: Some(1500),
+            location: None,
+            failures: &[],
+        }
+    }
+
+    #[test]
+    fn renders_counts_and_cases() {
+        let files = vec![FileReport {
+            path: "src/a.test.ts".into(),
+            tests: vec![
+                test("a > passes", TestStatus::Passed),
+                test("a > skips", TestStatus::Skipped),
+            ],
+            console: &[],
+        }];
+        let xml = render(&files, None);
+        assert!(xml.contains(
+            "<testsuites name=\"lens\" tests=\"2\" failures=\"0\" skipped=\"1\" time=\"3.000\">"
+        ));
+        assert!(xml.contains("<testcase name=\"a &gt; passes\" classname=\"src/a.test.ts\""));
+        assert!(xml.contains("<skipped/>"));
+    }
+
+    #[test]
+    fn failure_includes_message_and_stack() {
+        let failure = FailureOutput {
+            message: "expected 1 to be 2 <number>".into(),
+            expected: Some("2".into()),
+            actual: Some("1".into()),
+            expected_parsed: None,
+            actual_parsed: None,
+            diff: None,
+            source_snippet: None,
+            stack_trace: Some("at src/a.test.ts:3:5".into()),
+            attachments: Vec::new(),
+        };
+        let mut failed = test("fails", TestStatus::Failed);
+        let failures = [failure];
+        failed.failures = &failures;
+        let console = vec![
+            ConsoleLine {
+                stream: ConsoleStream::Stdout,
+                text: "log \u{1b}line".into(),
+            },
+            ConsoleLine {
+                stream: ConsoleStream::Stderr,
+                text: "warned".into(),
+            },
+        ];
+        let files = vec![FileReport {
+            path: "a.test.ts".into(),
+            tests: vec![failed],
+            console: &console,
+        }];
+        let xml = render(&files, None);
+        assert!(xml.contains("<failure message=\"expected 1 to be 2 &lt;number&gt;\">"));
+        assert!(xml.contains("Expected: 2\nReceived: 1\n\nat src/a.test.ts:3:5</failure>"));
+        assert!(xml.contains("<system-out>log line</system-out>"));
+        assert!(xml.contains("<system-err>warned</system-err>"));
+    }
+
+    #[test]
+    fn coloured_output_loses_its_escape_sequences() {
+        let failure = FailureOutput {
+            message: "expected \u{1b}[31m1\u{1b}[39m to be \u{1b}[32m2\u{1b}[39m".into(),
+            expected: None,
+            actual: None,
+            expected_parsed: None,
+            actual_parsed: None,
+            diff: None,
+            source_snippet: None,
+            stack_trace: None,
+            attachments: Vec::new(),
+        };
+        let mut failed = test("fails", TestStatus::Failed);
+        let failures = [failure];
+        failed.failures = &failures;
+        let console = vec![ConsoleLine {
+            stream: ConsoleStream::Stdout,
+            text:
+                "\u{1b}[1;33mwarn\u{1b}[0m see \u{1b}]8;;https://x.dev\u{7}docs\u{1b}]8;;\u{1b}\\"
+                    .into(),
+        }];
+        let files = vec![FileReport {
+            path: "a.test.ts".into(),
+            tests: vec![failed],
+            console: &console,
+        }];
+        let xml = render(&files, None);
+        assert!(xml.contains("<failure message=\"expected 1 to be 2\">"));
+        assert!(xml.contains("<system-out>warn see docs</system-out>"));
+    }
+}
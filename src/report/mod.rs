@@ -0,0 +1,178 @@
+pub mod json;
+pub mod junit;
+
+use std::str::FromStr;
+
+use crate::models::{ConsoleLine, FailureOutput, NodeKind, RunSummary, TestStatus, TestTree};
+
+/// Output format for headless runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    Junit,
+    Json,
+}
+
+impl FromStr for ReportFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "junit" => Ok(ReportFormat::Junit),
+            "json" => Ok(ReportFormat::Json),
+            other => anyhow::bail!("unknown reporter '{other}' (expected junit or json)"),
+        }
+    }
+}
+
+/// A test file and the results of every test under it.
+#[derive(Debug)]
+pub struct FileReport<'a> {
+    /// Workspace-relative path.
+    pub path: String,
+    pub tests: Vec<TestReport<'a>>,
+    /// Everything the file logged, its tests' output included.
+    pub console: &'a [ConsoleLine],
+}
+
+/// A single test, named by its suite path within the file.
+#[derive(Debug)]
+pub struct TestReport<'a> {
+    /// Suite and test names joined with ` > `.
+    pub name: String,
+    pub status: TestStatus,
+    pub duration_ms: Option<u64>,
+    pub location: Option<(u32, u32)>,
+    pub failures: &'a [FailureOutput],
+}
+
+impl FileReport<'_> {
+    /// Sum of the durations reported for this file's tests.
+    pub fn duration_ms(&self) -> u64 {
+        self.tests.iter().filter_map(|t| t.duration_ms).sum()
+    }
+
+    pub fn count(&self, status: TestStatus) -> usize {
+        self.tests.iter().filter(|t| t.status == status).count()
+    }
+}
+
+/// Render the tree's results in the requested format.
+pub fn render(format: ReportFormat, tree: &TestTree, summary: Option<&RunSummary>) -> String {
+    let files = collect(tree);
+    match format {
+        ReportFormat::Junit => junit::render(&files, summary),
+        ReportFormat::Json => json::render(&files, summary),
+    }
+}
+
+/// Flatten the tree into one report per (non-deleted) file, in tree order.
+pub fn collect(tree: &TestTree) -> Vec<FileReport<'_>> {
+    let mut files = Vec::new();
+    for &root_id in tree.root_ids() {
+        collect_files(tree, root_id, &mut files);
+    }
+    files
+}
+
+fn collect_files<'a>(tree: &'a TestTree, id: usize, files: &mut Vec<FileReport<'a>>) {
+    let Some(node) = tree.get(id) else { return };
+    if node.deleted {
+        return;
+    }
+    if node.kind == NodeKind::File {
+        let path = node
+            .path
+            .as_ref()
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or_else(|| node.name.clone());
+        let mut tests = Vec::new();
+        for &child_id in &node.children {
+            collect_tests(tree, child_id, &mut Vec::new(), &mut tests);
+        }
+        files.push(FileReport {
+            path,
+            tests,
+            console: &node.console_output,
+        });
+        return;
+    }
+    for &child_id in &node.children {
+        collect_files(tree, child_id, files);
+    }
+}
+
+fn collect_tests<'a>(
+    tree: &'a TestTree,
+    id: usize,
+    suites: &mut Vec<&'a str>,
+    tests: &mut Vec<TestReport<'a>>,
+) {
+    let Some(node) = tree.get(id) else { return };
+    if node.deleted {
+        return;
+    }
+    suites.push(&node.name);
+    if node.kind == NodeKind::Test {
+        let result = node.result.as_ref();
+        tests.push(TestReport {
+            name: suites.join(" > "),
+            status: node.status,
+            duration_ms: result.and_then(|r| r.duration_ms),
+            location: node.location,
+            failures: result.map(|r| r.failures.as_slice()).unwrap_or_default(),
+        });
+    } else {
+        for &child_id in &node.children {
+            collect_tests(tree, child_id, suites, tests);
+        }
+    }
+    suites.pop();
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::models::TestResult;
+
+    #[test]
+    fn collect_names_tests_by_suite_path() {
+        let mut tree = TestTree::new();
+        let root = tree.add_root(NodeKind::Workspace, "src".into(), None);
+        let file = tree.add_child(
+            root,
+            NodeKind::File,
+            "math.test.ts".into(),
+            Some(PathBuf::from("src/math.test.ts")),
+        );
+        let suite = tree.add_child(file, NodeKind::Suite, "math".into(), None);
+        let test = tree.add_child(suite, NodeKind::Test, "adds".into(), None);
+        tree.update_result(
+            test,
+            TestResult {
+                status: TestStatus::Passed,
+                duration_ms: Some(3),
+                failures: Vec::new(),
+            },
+        );
+
+        let files = collect(&tree);
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].path, "src/math.test.ts");
+        assert_eq!(files[0].tests.len(), 1);
+        assert_eq!(files[0].tests[0].name, "math > adds");
+        assert_eq!(files[0].tests[0].status, TestStatus::Passed);
+        assert_eq!(files[0].duration_ms(), 3);
+    }
+
+    #[test]
+    fn report_format_parses_known_names() {
+        assert_eq!(
+            "junit".parse::<ReportFormat>().unwrap(),
+            ReportFormat::Junit
+        );
+        assert_eq!("json".parse::<ReportFormat>().unwrap(), ReportFormat::Json);
+        assert!("tap".parse::<ReportFormat>().is_err());
+    }
+}
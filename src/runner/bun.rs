@@ -0,0 +1,981 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Instant;
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::process::{ChildStderr, ChildStdout, Command};
+use tokio::sync::mpsc;
+
+use crate::app::TestEvent;
+use crate::app::coverage::{FileCoverage, line_ranges};
+use crate::models::{FailureOutput, RunSummary, TestResult, TestStatus};
+
+use super::vitest::{ChildGuard, LogFile, strip_ansi, write_log};
+use super::{
+    DiscoveredFile, RunnerBase, TestRunner, command_line, discover_files, escape_test_name,
+    full_names_pattern, has_vite_config,
+};
+
+/// Bun's default test file patterns: `.test`, `_test`, `.spec` and `_spec`
+/// files of each extension it runs.
+const PATTERNS: [&str; 16] = [
+    "*.test.ts",
+    "*.test.tsx",
+    "*.test.js",
+    "*.test.jsx",
+    "*_test.ts",
+    "*_test.tsx",
+    "*_test.js",
+    "*_test.jsx",
+    "*.spec.ts",
+    "*.spec.tsx",
+    "*.spec.js",
+    "*.spec.jsx",
+    "*_spec.ts",
+    "*_spec.tsx",
+    "*_spec.js",
+    "*_spec.jsx",
+];
+
+const MARKERS: [&str; 3] = ["bunfig.toml", "bun.lockb", "bun.lock"];
+
+/// Returns true if `root` has a `bunfig.toml` or a Bun lockfile, and neither a
+/// Vite or Vitest config nor a `test` script running Vitest.
+pub fn is_bun_project(root: &Path) -> bool {
+    let runs_vitest = std::fs::read_to_string(root.join("package.json"))
+        .ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+        .and_then(|pkg| pkg["scripts"]["test"].as_str().map(str::to_owned))
+        .is_some_and(|script| script.contains("vitest"));
+    !has_vite_config(root) && !runs_vitest && MARKERS.iter().any(|m| root.join(m).is_file())
+}
+
+/// Bun adapter that spawns `bun test`. Bun has no reporter API, so results
+/// are read from its console output as they're printed: a `file:` header
+/// before each file's tests, a `(pass)`/`(fail)` line per test named like
+/// lens names it, and a failure's error printed just above its line. The
+/// JUnit report it writes on exit adds where each test and `describe` is.
+///
+/// What tests print goes to the runner log rather than to the test, because
+/// it arrives on stdout apart from the results.
+pub struct BunRunner {
+    base: RunnerBase,
+    /// Collect coverage on every run.
+    coverage: AtomicBool,
+}
+
+impl BunRunner {
+    pub fn new(
+        workspace: PathBuf,
+        project_roots: Vec<PathBuf>,
+        ignore_patterns: Vec<String>,
+    ) -> Self {
+        Self {
+            base: RunnerBase::new(workspace, project_roots, ignore_patterns),
+            coverage: AtomicBool::new(false),
+        }
+    }
+
+    /// Spawn `bun test` with the given args and turn its output into events.
+    ///
+    /// When `watch` is true, passes `--watch` so bun stays alive and re-runs
+    /// on file changes. Non-zero exit is not treated as an error in watch mode
+    /// (the process is killed on toggle-off).
+    async fn spawn_and_stream(
+        &self,
+        args: &[String],
+        files: &[PathBuf],
+        tx: mpsc::UnboundedSender<TestEvent>,
+        watch: bool,
+    ) -> Result<()> {
+        let cwd = self.base.default_cwd();
+        let junit_file = tempfile::Builder::new()
+            .prefix("lens-bun-junit-")
+            .suffix(".xml")
+            .tempfile()
+            .context("failed to create temp JUnit file")?;
+        let coverage_dir = if self.coverage.load(Ordering::Relaxed) {
+            Some(
+                tempfile::Builder::new()
+                    .prefix("lens-bun-coverage-")
+                    .tempdir()
+                    .context("failed to create temp coverage directory")?,
+            )
+        } else {
+            None
+        };
+
+        let mut cmd = Command::new("bun");
+        cmd.arg("test");
+        if watch {
+            cmd.arg("--watch");
+        }
+        if let Some(ref dir) = coverage_dir {
+            cmd.arg("--coverage")
+                .arg("--coverage-reporter=lcov")
+                .arg(format!("--coverage-dir={}", dir.path().display()));
+        }
+        cmd.arg("--reporter=junit")
+            .arg(format!(
+                "--reporter-outfile={}",
+                junit_file.path().to_string_lossy()
+            ))
+            .args(args)
+            .args(files);
+
+        // Log the full command for debugging (LENS_DEBUG=path)
+        self.base.log(&format!("[cmd] {:?}", cmd.as_std()));
+        self.base.log(&format!("[cwd] {:?}", cwd));
+        let _ = tx.send(TestEvent::CommandStarted {
+            command: command_line(&cmd),
+            cwd: cwd.clone(),
+        });
+
+        // Put the child in its own process group so killing it (via ChildGuard)
+        // also takes out anything the tests spawned (prevents orphans).
+        #[cfg(unix)]
+        {
+            use std::os::unix::process::CommandExt;
+            cmd.as_std_mut().process_group(0);
+        }
+
+        let mut child = cmd
+            .current_dir(&cwd)
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped())
+            .spawn()
+            .context("failed to spawn bun")?;
+
+        drop(child.stdin.take());
+        let stdout = child.stdout.take().context("missing stdout")?;
+        let stderr = child.stderr.take().context("missing stderr")?;
+
+        let mut child_guard = ChildGuard::new(child);
+
+        let mut output = BunOutput::new(cwd.clone(), watch);
+        stream_bun_output(stdout, stderr, &mut output, &tx, self.base.log_file.clone()).await;
+
+        if !watch && let Some(ref mut child) = child_guard.child {
+            let status = child.wait().await.context("failed to wait for bun")?;
+
+            let report = std::fs::read_to_string(junit_file.path()).unwrap_or_default();
+            for (file, name, location) in junit_locations(&report, &cwd) {
+                if output.ran(&file, &name) {
+                    let _ = tx.send(TestEvent::SuiteLocation {
+                        file,
+                        name,
+                        location,
+                    });
+                }
+            }
+            if let Some(ref dir) = coverage_dir {
+                let lcov =
+                    std::fs::read_to_string(dir.path().join("lcov.info")).unwrap_or_default();
+                let _ = tx.send(TestEvent::Coverage {
+                    files: lcov_files(&lcov, &cwd),
+                });
+            }
+            for event in output.end() {
+                let _ = tx.send(event);
+            }
+
+            // Bun exits with 1 when tests fail; that's already visible in the tree.
+            if !status.success() && status.code() != Some(1) {
+                let _ = tx.send(TestEvent::Error {
+                    message: format!("bun test exited with code {}", status.code().unwrap_or(-1)),
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Feed bun's stderr, where it reports results, through `output`, and pass
+/// stdout on as plain output.
+async fn stream_bun_output(
+    stdout: ChildStdout,
+    stderr: ChildStderr,
+    output: &mut BunOutput,
+    tx: &mpsc::UnboundedSender<TestEvent>,
+    log_file: Option<LogFile>,
+) {
+    let tx_out = tx.clone();
+    let log_out = log_file.clone();
+    let stdout_handle = tokio::spawn(async move {
+        let reader = BufReader::new(stdout);
+        let mut lines = reader.lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            if let Some(ref lf) = log_out {
+                write_log(lf, &format!("[stdout] {}", line));
+            }
+            let _ = tx_out.send(TestEvent::Output { line });
+        }
+    });
+
+    let reader = BufReader::new(stderr);
+    let mut lines = reader.lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        if let Some(ref lf) = log_file {
+            write_log(lf, &format!("[stderr] {}", line));
+        }
+        for event in output.line(&line) {
+            let _ = tx.send(event);
+        }
+    }
+
+    stdout_handle.await.ok();
+}
+
+/// Turns `bun test`'s console output into events, one line at a time.
+struct BunOutput {
+    /// Bun's working directory, which the file headers are relative to.
+    cwd: PathBuf,
+    /// Set between the first file header of a run and its `Ran N tests` line.
+    started: Option<Instant>,
+    /// The file whose tests are being printed.
+    file: Option<String>,
+    /// Lines since the last result, which hold the next failure's error.
+    pending: Vec<String>,
+    passed: usize,
+    failed: usize,
+    skipped: usize,
+    /// Files and full names of every test reported.
+    reported: HashSet<(String, String)>,
+    /// End each run at bun's `Ran N tests` line, as in watch mode. Otherwise
+    /// the caller ends it with [`BunOutput::end`] once bun exits, after the
+    /// locations its JUnit report gives.
+    ends_at_summary: bool,
+}
+
+impl BunOutput {
+    fn new(cwd: PathBuf, ends_at_summary: bool) -> Self {
+        Self {
+            cwd,
+            started: None,
+            file: None,
+            pending: Vec::new(),
+            passed: 0,
+            failed: 0,
+            skipped: 0,
+            reported: HashSet::new(),
+            ends_at_summary,
+        }
+    }
+
+    fn line(&mut self, raw: &str) -> Vec<TestEvent> {
+        let line = strip_ansi(raw);
+        let mut events = Vec::new();
+
+        if let Some(path) = self.file_header(&line) {
+            self.start_run(&mut events);
+            if let Some(previous) = self.file.take() {
+                events.push(TestEvent::FileFinished { path: previous });
+            }
+            events.push(TestEvent::FileStarted { path: path.clone() });
+            self.file = Some(path);
+            self.pending.clear();
+        } else if let Some((status, name, duration_ms)) = parse_result(&line) {
+            self.start_run(&mut events);
+            let failures = if status == TestStatus::Failed {
+                vec![failure(&self.pending)]
+            } else {
+                Vec::new()
+            };
+            self.pending.clear();
+            match status {
+                TestStatus::Passed => self.passed += 1,
+                TestStatus::Failed => self.failed += 1,
+                _ => self.skipped += 1,
+            }
+            if let Some(file) = self.file.clone() {
+                self.reported.insert((file.clone(), name.clone()));
+                events.push(TestEvent::TestFinished {
+                    file,
+                    name,
+                    result: Box::new(TestResult {
+                        status,
+                        duration_ms,
+                        failures,
+                    }),
+                    location: None,
+                });
+            }
+        } else if line.starts_with("Ran ") && line.contains(" across ") {
+            if self.ends_at_summary {
+                events.extend(self.end());
+            }
+            events.push(TestEvent::Output {
+                line: raw.to_string(),
+            });
+        } else {
+            self.pending.push(line);
+            events.push(TestEvent::Output {
+                line: raw.to_string(),
+            });
+        }
+        events
+    }
+
+    /// Finish the current run, if one started.
+    fn end(&mut self) -> Vec<TestEvent> {
+        let Some(started) = self.started.take() else {
+            return Vec::new();
+        };
+        let mut events = Vec::new();
+        if let Some(file) = self.file.take() {
+            events.push(TestEvent::FileFinished { path: file });
+        }
+        events.push(TestEvent::RunFinished {
+            summary: RunSummary {
+                total: self.passed + self.failed + self.skipped,
+                passed: self.passed,
+                failed: self.failed,
+                skipped: self.skipped,
+                duration: started.elapsed().as_millis() as u64,
+                ..Default::default()
+            },
+        });
+        self.pending.clear();
+        (self.passed, self.failed, self.skipped) = (0, 0, 0);
+        events
+    }
+
+    /// Whether `name` in `file` is a test bun reported or a suite around one.
+    fn ran(&self, file: &str, name: &str) -> bool {
+        let prefix = format!("{name} > ");
+        self.reported
+            .iter()
+            .any(|(f, n)| f == file && (n == name || n.starts_with(&prefix)))
+    }
+
+    fn start_run(&mut self, events: &mut Vec<TestEvent>) {
+        if self.started.is_none() {
+            self.started = Some(Instant::now());
+            events.push(TestEvent::RunStarted);
+        }
+    }
+
+    /// The absolute path of the test file a `path/to/a.test.ts:` line
+    /// introduces.
+    fn file_header(&self, line: &str) -> Option<String> {
+        let path = line.strip_suffix(':')?;
+        if path.is_empty() || path.starts_with(char::is_whitespace) {
+            return None;
+        }
+        let path = self.cwd.join(path);
+        path.is_file().then(|| path.to_string_lossy().into_owned())
+    }
+}
+
+/// A result line, `(pass) suite > test [1.20ms]`: the status, full name and
+/// duration. Bun prints check marks instead when writing to a terminal.
+fn parse_result(line: &str) -> Option<(TestStatus, String, Option<u64>)> {
+    let (status, rest) = [
+        ("(pass) ", TestStatus::Passed),
+        ("(fail) ", TestStatus::Failed),
+        ("(skip) ", TestStatus::Skipped),
+        ("(todo) ", TestStatus::Skipped),
+        ("✓ ", TestStatus::Passed),
+        ("✗ ", TestStatus::Failed),
+    ]
+    .into_iter()
+    .find_map(|(prefix, status)| Some((status, line.trim_start().strip_prefix(prefix)?)))?;
+
+    let (name, duration_ms) = match rest.rsplit_once(" [") {
+        Some((name, time)) if time.ends_with(']') => {
+            let time = &time[..time.len() - 1];
+            let ms = if let Some(ms) = time.strip_suffix("ms") {
+                ms.parse::<f64>().ok()
+            } else {
+                time.strip_suffix('s')
+                    .and_then(|s| s.parse::<f64>().ok())
+                    .map(|s| s * 1000.0)
+            };
+            match ms {
+                Some(ms) => (name, Some(ms as u64)),
+                None => (rest, None),
+            }
+        }
+        _ => (rest, None),
+    };
+    Some((status, name.to_string(), duration_ms))
+}
+
+/// The failure bun printed above a `(fail)` line: a code frame, then
+/// `error: message`, the `Expected:`/`Received:` values or a diff, and the
+/// stack.
+fn failure(lines: &[String]) -> FailureOutput {
+    let start = lines.iter().position(|line| {
+        let line = line.trim_start();
+        line.starts_with("error: ") || is_error_name(line)
+    });
+    let Some(start) = start else {
+        return FailureOutput {
+            message: "Test failed".to_string(),
+            expected: None,
+            actual: None,
+            expected_parsed: None,
+            actual_parsed: None,
+            diff: None,
+            source_snippet: None,
+            stack_trace: None,
+            attachments: Vec::new(),
+        };
+    };
+
+    let first = lines[start].trim_start();
+    let message = first.strip_prefix("error: ").unwrap_or(first).to_string();
+    let rest = &lines[start + 1..];
+    let stack_start = rest
+        .iter()
+        .position(|line| line.trim_start().starts_with("at "))
+        .unwrap_or(rest.len());
+    let (body, stack) = rest.split_at(stack_start);
+
+    let value = |label: &str| {
+        body.iter().find_map(|line| {
+            let (key, value) = line.trim().split_once(": ")?;
+            key.starts_with(label).then(|| value.to_string())
+        })
+    };
+    let (expected, actual) = match (value("Expected"), value("Received")) {
+        (Some(expected), Some(actual)) => (Some(expected), Some(actual)),
+        _ => (None, None),
+    };
+    let diff = if expected.is_none() {
+        let text = body.join("\n");
+        let text = text.trim_matches('\n');
+        (!text.trim().is_empty()).then(|| text.to_string())
+    } else {
+        None
+    };
+    let stack: Vec<&str> = stack
+        .iter()
+        .map(|line| line.trim())
+        .filter(|line| line.starts_with("at "))
+        .collect();
+
+    FailureOutput {
+        message,
+        expected,
+        actual,
+        expected_parsed: None,
+        actual_parsed: None,
+        diff,
+        source_snippet: None,
+        stack_trace: (!stack.is_empty()).then(|| stack.join("\n")),
+        attachments: Vec::new(),
+    }
+}
+
+/// Whether `line` starts like `TypeError: …`, how bun prints errors other
+/// than `Error`.
+fn is_error_name(line: &str) -> bool {
+    line.split_once(": ").is_some_and(|(name, _)| {
+        name.ends_with("Error") && name.chars().all(|c| c.is_ascii_alphanumeric())
+    })
+}
+
+/// Where the suites and tests in a bun JUnit report are, as `(file, full
+/// name, (line, column))`. The outermost `<testsuite>` of each file is named
+/// after it; those inside it are `describe` blocks.
+fn junit_locations(xml: &str, cwd: &Path) -> Vec<(String, String, (u32, u32))> {
+    let mut locations = Vec::new();
+    // Names of the open `<testsuite>` elements below the file's.
+    let mut suites: Vec<Option<String>> = Vec::new();
+    let mut file = String::new();
+
+    for tag in xml_tags(xml) {
+        let (element, attrs, self_closing) = match tag {
+            XmlTag::Close("testsuite") => {
+                suites.pop();
+                continue;
+            }
+            XmlTag::Open {
+                name: element @ ("testsuite" | "testcase"),
+                attrs,
+                self_closing,
+            } => (element, attrs, self_closing),
+            _ => continue,
+        };
+
+        let attr = |key: &str| {
+            attrs
+                .iter()
+                .find(|(k, _)| *k == key)
+                .map(|(_, v)| v.as_str())
+        };
+        let name = attr("name").unwrap_or_default().to_string();
+        if let Some(path) = attr("file") {
+            file = cwd.join(path).to_string_lossy().into_owned();
+        }
+        let location = attr("line").and_then(|line| line.parse().ok()).map(|line| {
+            (
+                line,
+                attr("column").and_then(|c| c.parse().ok()).unwrap_or(1),
+            )
+        });
+
+        let is_file_suite =
+            element == "testsuite" && (suites.is_empty() || attr("file") == Some(&name));
+        let full_name = || {
+            suites
+                .iter()
+                .flatten()
+                .map(String::as_str)
+                .chain([name.as_str()])
+                .collect::<Vec<_>>()
+                .join(" > ")
+        };
+        if !is_file_suite && let Some(location) = location {
+            locations.push((file.clone(), full_name(), location));
+        }
+        if element == "testsuite" && !self_closing {
+            suites.push((!is_file_suite).then(|| name.clone()));
+        }
+    }
+    locations
+}
+
+/// A start or end tag of an XML document.
+#[derive(Debug, PartialEq)]
+enum XmlTag<'a> {
+    Open {
+        name: &'a str,
+        /// Values unescaped.
+        attrs: Vec<(&'a str, String)>,
+        self_closing: bool,
+    },
+    Close(&'a str),
+}
+
+/// The tags of an XML document in order. Text, CDATA sections, comments,
+/// processing instructions and declarations are skipped; attribute values
+/// may be quoted either way and hold a `>`. Reading stops at a malformed tag.
+fn xml_tags(xml: &str) -> Vec<XmlTag<'_>> {
+    fn skip_past<'a>(rest: &'a str, end: &str) -> &'a str {
+        rest.find(end).map_or("", |i| &rest[i + end.len()..])
+    }
+    let mut tags = Vec::new();
+    let mut rest = xml;
+    while let Some(start) = rest.find('<') {
+        rest = &rest[start..];
+        if let Some(after) = rest.strip_prefix("<![CDATA[") {
+            rest = skip_past(after, "]]>");
+        } else if let Some(after) = rest.strip_prefix("<!--") {
+            rest = skip_past(after, "-->");
+        } else if let Some(after) = rest.strip_prefix("<?") {
+            rest = skip_past(after, "?>");
+        } else if let Some(after) = rest.strip_prefix("<!") {
+            rest = skip_past(after, ">");
+        } else if let Some(after) = rest.strip_prefix("</") {
+            let Some(end) = after.find('>') else {
+                break;
+            };
+            tags.push(XmlTag::Close(after[..end].trim()));
+            rest = &after[end + 1..];
+        } else {
+            let body = &rest[1..];
+            let name_end = body
+                .find(|c: char| c.is_whitespace() || c == '/' || c == '>')
+                .unwrap_or(body.len());
+            let mut attrs = Vec::new();
+            let mut cursor = &body[name_end..];
+            let self_closing = loop {
+                cursor = cursor.trim_start();
+                if let Some(after) = cursor.strip_prefix("/>") {
+                    cursor = after;
+                    break true;
+                }
+                if let Some(after) = cursor.strip_prefix('>') {
+                    cursor = after;
+                    break false;
+                }
+                let Some(eq) = cursor.find('=') else {
+                    return tags;
+                };
+                let value = cursor[eq + 1..].trim_start();
+                let Some(quote) = value.chars().next().filter(|c| *c == '"' || *c == '\'') else {
+                    return tags;
+                };
+                let Some(end) = value[1..].find(quote) else {
+                    return tags;
+                };
+                attrs.push((cursor[..eq].trim(), unescape(&value[1..end + 1])));
+                cursor = &value[end + 2..];
+            };
+            tags.push(XmlTag::Open {
+                name: &body[..name_end],
+                attrs,
+                self_closing,
+            });
+            rest = cursor;
+        }
+    }
+    tags
+}
+
+/// Undo XML's entity escaping.
+fn unescape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        let Some(semi) = rest.find(';') else {
+            break;
+        };
+        let entity = &rest[1..semi];
+        let c = match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => entity
+                .strip_prefix("#x")
+                .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                .or_else(|| entity.strip_prefix('#').and_then(|dec| dec.parse().ok()))
+                .and_then(char::from_u32),
+        };
+        match c {
+            Some(c) => {
+                out.push(c);
+                rest = &rest[semi + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Per-file coverage from an lcov report. Bun only reports lines and
+/// functions, so branches count as fully covered.
+fn lcov_files(lcov: &str, cwd: &Path) -> Vec<(String, FileCoverage)> {
+    let mut files = Vec::new();
+    let mut current: Option<(String, FileCoverage, Vec<u32>)> = None;
+    for line in lcov.lines() {
+        let (key, value) = line.split_once(':').unwrap_or((line, ""));
+        let count = || value.trim().parse::<u32>().unwrap_or(0);
+        match (key, current.as_mut()) {
+            ("SF", _) => {
+                let path = cwd.join(value).to_string_lossy().into_owned();
+                current = Some((path, FileCoverage::default(), Vec::new()));
+            }
+            ("DA", Some((_, _, uncovered))) => {
+                if let Some((line, hits)) = value.split_once(',')
+                    && hits.split(',').next() == Some("0")
+                    && let Ok(line) = line.parse()
+                {
+                    uncovered.push(line);
+                }
+            }
+            ("LF", Some((_, coverage, _))) => coverage.lines.total = count(),
+            ("LH", Some((_, coverage, _))) => coverage.lines.covered = count(),
+            ("FNF", Some((_, coverage, _))) => coverage.functions.total = count(),
+            ("FNH", Some((_, coverage, _))) => coverage.functions.covered = count(),
+            ("BRF", Some((_, coverage, _))) => coverage.branches.total = count(),
+            ("BRH", Some((_, coverage, _))) => coverage.branches.covered = count(),
+            ("end_of_record", _) => {
+                if let Some((path, mut coverage, uncovered)) = current.take() {
+                    coverage.uncovered = line_ranges(&uncovered);
+                    files.push((path, coverage));
+                }
+            }
+            _ => {}
+        }
+    }
+    files
+}
+
+#[async_trait]
+impl TestRunner for BunRunner {
+    async fn discover(&self, workspace: &Path) -> Result<Vec<DiscoveredFile>> {
+        discover_files(
+            workspace,
+            &self.base.workspace,
+            &PATTERNS,
+            &self.base.ignore_patterns,
+        )
+    }
+
+    async fn run_all(&self, tx: mpsc::UnboundedSender<TestEvent>) -> Result<()> {
+        let files = self.base.all_files(self).await?;
+        self.spawn_and_stream(&[], &files, tx, false).await
+    }
+
+    async fn run_files(
+        &self,
+        files: &[PathBuf],
+        tx: mpsc::UnboundedSender<TestEvent>,
+    ) -> Result<()> {
+        self.spawn_and_stream(&[], files, tx, false).await
+    }
+
+    async fn run_file(&self, file: &Path, tx: mpsc::UnboundedSender<TestEvent>) -> Result<()> {
+        self.spawn_and_stream(&[], &[file.to_path_buf()], tx, false)
+            .await
+    }
+
+    async fn run_test(
+        &self,
+        file: &Path,
+        test_name: &str,
+        tx: mpsc::UnboundedSender<TestEvent>,
+    ) -> Result<()> {
+        let args = [
+            "--test-name-pattern".to_string(),
+            full_names_pattern(&[test_name.to_string()], &[]),
+        ];
+        self.spawn_and_stream(&args, &[file.to_path_buf()], tx, false)
+            .await
+    }
+
+    async fn run_tests(
+        &self,
+        files: &[PathBuf],
+        names: &[String],
+        tx: mpsc::UnboundedSender<TestEvent>,
+    ) -> Result<()> {
+        let args = [
+            "--test-name-pattern".to_string(),
+            full_names_pattern(names, &[]),
+        ];
+        self.spawn_and_stream(&args, files, tx, false).await
+    }
+
+    async fn update_snapshots(
+        &self,
+        files: &[PathBuf],
+        test_name: Option<&str>,
+        tx: mpsc::UnboundedSender<TestEvent>,
+    ) -> Result<()> {
+        let mut args = vec!["--update-snapshots".to_string()];
+        if let Some(name) = test_name {
+            args.push("--test-name-pattern".to_string());
+            args.push(escape_test_name(name));
+        }
+        self.spawn_and_stream(&args, files, tx, false).await
+    }
+
+    async fn run_file_watch(
+        &self,
+        file: &Path,
+        tx: mpsc::UnboundedSender<TestEvent>,
+    ) -> Result<()> {
+        self.spawn_and_stream(&[], &[file.to_path_buf()], tx, true)
+            .await
+    }
+
+    async fn run_test_watch(
+        &self,
+        file: &Path,
+        test_name: &str,
+        tx: mpsc::UnboundedSender<TestEvent>,
+    ) -> Result<()> {
+        let args = [
+            "--test-name-pattern".to_string(),
+            full_names_pattern(&[test_name.to_string()], &[]),
+        ];
+        self.spawn_and_stream(&args, &[file.to_path_buf()], tx, true)
+            .await
+    }
+
+    async fn run_all_watch(&self, tx: mpsc::UnboundedSender<TestEvent>) -> Result<()> {
+        let files = self.base.all_files(self).await?;
+        self.spawn_and_stream(&[], &files, tx, true).await
+    }
+
+    fn set_coverage(&self, enabled: bool) {
+        self.coverage.store(enabled, Ordering::Relaxed);
+    }
+
+    fn name(&self) -> &str {
+        "Bun"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_bun_markers_without_vitest() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(!is_bun_project(dir.path()));
+
+        std::fs::write(dir.path().join("bun.lockb"), "").unwrap();
+        assert!(is_bun_project(dir.path()));
+
+        std::fs::write(
+            dir.path().join("package.json"),
+            r#"{ "scripts": { "test": "vitest run" } }"#,
+        )
+        .unwrap();
+        assert!(!is_bun_project(dir.path()));
+    }
+
+    #[test]
+    fn parses_results_and_failures_from_console_output() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("math.test.ts"), "").unwrap();
+        let file = dir
+            .path()
+            .join("math.test.ts")
+            .to_string_lossy()
+            .into_owned();
+
+        let mut output = BunOutput::new(dir.path().to_path_buf(), true);
+        let mut events = Vec::new();
+        for line in [
+            "bun test v1.1.38",
+            "",
+            "math.test.ts:",
+            "(pass) math > adds [0.12ms]",
+            "4 |   test(\"fails\", () => {",
+            "5 |     expect(1).toBe(2);",
+            "                  ^",
+            "error: expect(received).toBe(expected)",
+            "",
+            "Expected: 2",
+            "Received: 1",
+            "",
+            "      at <anonymous> (/repo/math.test.ts:5:15)",
+            "(fail) math > fails [0.31ms]",
+            "(todo) math > later",
+            "Ran 3 tests across 1 files. [12.00ms]",
+        ] {
+            events.extend(output.line(line));
+        }
+
+        let finished: Vec<(&str, &TestResult)> = events
+            .iter()
+            .filter_map(|event| match event {
+                TestEvent::TestFinished { name, result, .. } => Some((name.as_str(), &**result)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(finished.len(), 3);
+        assert_eq!(finished[0].0, "math > adds");
+        assert_eq!(finished[0].1.duration_ms, Some(0));
+        assert_eq!(finished[2].1.status, TestStatus::Skipped);
+
+        let failure = &finished[1].1.failures[0];
+        assert_eq!(failure.message, "expect(received).toBe(expected)");
+        assert_eq!(failure.expected.as_deref(), Some("2"));
+        assert_eq!(failure.actual.as_deref(), Some("1"));
+        assert_eq!(
+            failure.stack_trace.as_deref(),
+            Some("at <anonymous> (/repo/math.test.ts:5:15)")
+        );
+
+        assert!(matches!(&events[2], TestEvent::RunStarted));
+        assert!(matches!(
+            events.iter().rev().nth(1),
+            Some(TestEvent::RunFinished { summary }) if summary.failed == 1 && summary.skipped == 1
+        ));
+        assert!(
+            events
+                .iter()
+                .any(|event| matches!(event, TestEvent::FileFinished { path } if *path == file))
+        );
+        assert!(output.ran(&file, "math"));
+        assert!(!output.ran(&file, "mat"));
+    }
+
+    #[test]
+    fn junit_report_locates_describes_and_tests() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites name="bun test" tests="2" failures="0">
+  <testsuite name="src/math.test.ts" file="src/math.test.ts" tests="2">
+    <testsuite name="math &amp; more" file="src/math.test.ts" line="3" tests="2">
+      <testcase name="adds &quot;1&quot;" classname="math" file="src/math.test.ts" line="4" />
+      <testcase name="fails" classname="math" file="src/math.test.ts" line="7">
+        <failure type="AssertionError" />
+      </testcase>
+    </testsuite>
+    <testcase name="top level" file="src/math.test.ts" line="12" />
+  </testsuite>
+</testsuites>"#;
+        let file = "/repo/src/math.test.ts".to_string();
+        assert_eq!(
+            junit_locations(xml, Path::new("/repo")),
+            [
+                (file.clone(), "math & more".to_string(), (3, 1)),
+                (file.clone(), "math & more > adds \"1\"".to_string(), (4, 1)),
+                (file.clone(), "math & more > fails".to_string(), (7, 1)),
+                (file, "top level".to_string(), (12, 1)),
+            ]
+        );
+    }
+
+    #[test]
+    fn xml_tags_skip_cdata_and_comments_and_read_either_quote() {
+        let xml = r#"<?xml version="1.0"?>
+<!-- <testcase name="commented"/> -->
+<testcase name='a > b' file = "x&#x41;&lt;.ts">
+  <failure><![CDATA[expected <testcase name="fake"/> & more]]></failure>
+</testcase>"#;
+        assert_eq!(
+            xml_tags(xml),
+            [
+                XmlTag::Open {
+                    name: "testcase",
+                    attrs: vec![("name", "a > b".into()), ("file", "xA<.ts".into())],
+                    self_closing: false,
+                },
+                XmlTag::Open {
+                    name: "failure",
+                    attrs: Vec::new(),
+                    self_closing: false,
+                },
+                XmlTag::Close("failure"),
+                XmlTag::Close("testcase"),
+            ]
+        );
+    }
+
+    #[test]
+    fn runs_end_after_the_junit_locations_unless_watching() {
+        let lines = [
+            "math.test.ts:",
+            "(pass) adds [0.12ms]",
+            "Ran 1 tests across 1 files. [2.00ms]",
+        ];
+        let finishes = |events: &[TestEvent]| {
+            events
+                .iter()
+                .any(|event| matches!(event, TestEvent::RunFinished { .. }))
+        };
+
+        let mut output = BunOutput::new(PathBuf::from("/repo"), false);
+        let events: Vec<TestEvent> = lines.iter().flat_map(|line| output.line(line)).collect();
+        assert!(!finishes(&events));
+        assert!(finishes(&output.end()));
+
+        let mut output = BunOutput::new(PathBuf::from("/repo"), true);
+        let events: Vec<TestEvent> = lines.iter().flat_map(|line| output.line(line)).collect();
+        assert!(finishes(&events));
+        assert!(output.end().is_empty());
+    }
+
+    #[test]
+    fn reads_lcov_coverage() {
+        let lcov = "TN:\nSF:src/math.ts\nFNF:2\nFNH:1\nDA:1,1\nDA:2,0\nDA:3,0\nLF:3\nLH:1\nend_of_record\n";
+        let files = lcov_files(lcov, Path::new("/repo"));
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].0, "/repo/src/math.ts");
+        assert_eq!(files[0].1.lines.covered, 1);
+        assert_eq!(files[0].1.functions.total, 2);
+        assert_eq!(files[0].1.uncovered, [(2, 3)]);
+    }
+}
@@ -0,0 +1,1496 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result, bail};
+use async_trait::async_trait;
+use serde::Deserialize;
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::process::Command;
+use tokio::sync::{OnceCell, mpsc};
+
+use crate::app::TestEvent;
+use crate::models::{ConsoleStream, FailureOutput, RunSummary, TestResult, TestStatus};
+
+use super::vitest::{ChildGuard, write_log};
+use super::{
+    DiscoveredFile, RunnerBase, TestRunner, command_line, discover_files, rerun_on_change,
+};
+
+/// Files whose changes re-run watched tests: sources and manifests.
+const WATCH_EXTENSIONS: [&str; 2] = ["rs", "toml"];
+
+/// Target kinds `cargo test --lib` selects.
+const LIB_KINDS: [&str; 6] = ["lib", "rlib", "dylib", "cdylib", "staticlib", "proc-macro"];
+
+/// How long a test binary's results wait for the line cargo prints on stderr
+/// before running it. The two pipes are read separately, so the line can
+/// arrive a little after the binary's first output.
+const BINARY_LINE_WAIT: Duration = Duration::from_secs(2);
+
+/// Returns true if `root` has a `Cargo.toml` and no `package.json`.
+pub fn is_cargo_project(root: &Path) -> bool {
+    root.join("Cargo.toml").is_file() && !root.join("package.json").is_file()
+}
+
+/// The parts of `cargo metadata` lens uses.
+#[derive(Debug, Clone, Deserialize)]
+struct Metadata {
+    workspace_root: PathBuf,
+    packages: Vec<Package>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct Package {
+    name: String,
+    manifest_path: PathBuf,
+    targets: Vec<Target>,
+}
+
+impl Package {
+    fn dir(&self) -> &Path {
+        self.manifest_path.parent().unwrap_or(&self.manifest_path)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct Target {
+    name: String,
+    kind: Vec<String>,
+    src_path: PathBuf,
+}
+
+impl Target {
+    fn is_lib(&self) -> bool {
+        self.kind.iter().any(|k| LIB_KINDS.contains(&k.as_str()))
+    }
+
+    fn is(&self, kind: &str) -> bool {
+        self.kind.iter().any(|k| k == kind)
+    }
+
+    /// Libraries, binaries and integration tests; examples and benches don't
+    /// run tests by default.
+    fn has_tests(&self) -> bool {
+        self.is_lib() || self.is("bin") || self.is("test")
+    }
+
+    /// The `cargo test` flags selecting this target.
+    fn selector(&self) -> Vec<String> {
+        if self.is_lib() {
+            vec!["--lib".to_string()]
+        } else if self.is("bin") {
+            vec!["--bin".to_string(), self.name.clone()]
+        } else {
+            vec!["--test".to_string(), self.name.clone()]
+        }
+    }
+
+    /// The directory its modules' files are found in.
+    fn module_dir(&self) -> &Path {
+        self.src_path.parent().unwrap_or(&self.src_path)
+    }
+}
+
+/// The package a source file belongs to, the targets compiling it and its
+/// module path in them (empty for a target's root file).
+struct Located<'a> {
+    workspace: &'a Metadata,
+    package: &'a Package,
+    targets: Vec<&'a Target>,
+    module: String,
+}
+
+/// Find the targets `file` is compiled into. A file under a target's root
+/// directory is taken to be one of its modules; when several targets share
+/// the directory (a library and `src/main.rs`, or the integration tests
+/// sharing a `tests/common/mod.rs`) it belongs to all of them.
+fn locate<'a>(workspaces: &'a [Metadata], file: &Path) -> Option<Located<'a>> {
+    let (workspace, package) = workspaces
+        .iter()
+        .flat_map(|ws| ws.packages.iter().map(move |p| (ws, p)))
+        .filter(|(_, p)| file.starts_with(p.dir()))
+        .max_by_key(|(_, p)| p.dir().components().count())?;
+    let targets: Vec<&Target> = package.targets.iter().filter(|t| t.has_tests()).collect();
+
+    if let Some(target) = targets.iter().find(|t| t.src_path == file) {
+        return Some(Located {
+            workspace,
+            package,
+            targets: vec![target],
+            module: String::new(),
+        });
+    }
+
+    let depth = |t: &Target| t.module_dir().components().count();
+    let deepest = targets
+        .iter()
+        .filter(|t| file.starts_with(t.module_dir()))
+        .map(|t| depth(t))
+        .max()?;
+    let targets: Vec<&Target> = targets
+        .into_iter()
+        .filter(|t| file.starts_with(t.module_dir()) && depth(t) == deepest)
+        .collect();
+
+    let relative = file.strip_prefix(targets[0].module_dir()).ok()?;
+    let mut module: Vec<String> = relative
+        .with_extension("")
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().into_owned())
+        .collect();
+    if module.last().is_some_and(|m| m == "mod") {
+        module.pop();
+    }
+    Some(Located {
+        workspace,
+        package,
+        targets,
+        module: module.join("::"),
+    })
+}
+
+/// The file a test is declared in, and its name below that file, for a test
+/// of the target rooted at `root`. The longest run of leading modules with a
+/// file of their own picks the file; the modules after it are suites.
+fn test_path(root: &Path, name: &str) -> (PathBuf, String) {
+    let segments: Vec<&str> = name.split("::").collect();
+    let dir = root.parent().unwrap_or(root);
+    for split in (1..segments.len()).rev() {
+        let modules = segments[..split].join("/");
+        for candidate in [
+            dir.join(format!("{modules}.rs")),
+            dir.join(&modules).join("mod.rs"),
+        ] {
+            if candidate.is_file() {
+                return (candidate, segments[split..].join(" > "));
+            }
+        }
+    }
+    (root.to_path_buf(), segments.join(" > "))
+}
+
+/// Whether a lens name is a doc test's, `Item > method (line 12)`.
+fn is_doc_test(name: &str) -> bool {
+    name.ends_with(')') && name.contains(" (line ")
+}
+
+/// A doc test's file and name, from rustdoc's `src/lib.rs - Item::method (line 12)`.
+fn doc_test_path(workspace_root: &Path, name: &str) -> Option<(PathBuf, String)> {
+    let (path, item) = name.split_once(" - ")?;
+    Some((workspace_root.join(path), item.replace("::", " > ")))
+}
+
+/// Where a test binary's tests are declared.
+#[derive(Debug, Clone, PartialEq)]
+enum Binary {
+    /// Unit or integration tests of the target rooted at this file.
+    Tests(PathBuf),
+    /// A library's doc tests, which name their own files.
+    Doc(PathBuf),
+}
+
+/// The binary a line cargo prints before running it stands for, from
+/// `Running unittests src/lib.rs (target/debug/deps/my_crate-1a2b)` or
+/// `Doc-tests my_crate`. `None` when it isn't such a line; `Some(None)` when
+/// it is but the target isn't in `workspace`.
+fn binary_line(line: &str, workspace: &Metadata) -> Option<Option<Binary>> {
+    let line = line.trim();
+    let targets = || workspace.packages.iter().flat_map(|p| &p.targets);
+    if let Some(name) = line.strip_prefix("Doc-tests ") {
+        let found = targets().any(|t| t.is_lib() && t.name.replace('-', "_") == name);
+        return Some(found.then(|| Binary::Doc(workspace.workspace_root.clone())));
+    }
+    let rest = line.strip_prefix("Running ")?;
+    let (src, exe) = rest.strip_suffix(')')?.rsplit_once(" (")?;
+    let src = src.strip_prefix("unittests ").unwrap_or(src);
+    let exe = Path::new(exe).file_name()?.to_string_lossy();
+    let crate_name = exe.rsplit_once('-').map_or(exe.as_ref(), |(name, _)| name);
+    let target =
+        targets().find(|t| t.name.replace('-', "_") == crate_name && t.src_path.ends_with(src));
+    Some(target.map(|t| Binary::Tests(t.src_path.clone())))
+}
+
+/// The binary whose results just started on stdout: the next one cargo named
+/// on stderr. Gives up after `wait` so a missing line can't hold up stdout;
+/// `missed` counts the binaries that did, and the lines arriving late for
+/// them are skipped so later binaries still pair with their own.
+async fn next_binary(
+    lines: &mut mpsc::UnboundedReceiver<Option<Binary>>,
+    missed: &mut usize,
+    wait: Duration,
+) -> Option<Binary> {
+    loop {
+        match tokio::time::timeout(wait, lines.recv()).await {
+            Ok(Some(_)) if *missed > 0 => *missed -= 1,
+            Ok(binary) => return binary.flatten(),
+            Err(_) => {
+                *missed += 1;
+                return None;
+            }
+        }
+    }
+}
+
+/// The root file of the target a nextest binary id names: `my-crate` for the
+/// library, `my-crate::bin/tool` for a binary and `my-crate::api` for an
+/// integration test.
+fn nextest_binary(workspaces: &[Metadata], id: &str) -> Option<PathBuf> {
+    let (package, target) = id.split_once("::").unwrap_or((id, ""));
+    let package = workspaces
+        .iter()
+        .flat_map(|ws| &ws.packages)
+        .find(|p| p.name == package)?;
+    let found = package.targets.iter().find(|t| match target {
+        "" => t.is_lib(),
+        _ => match target.strip_prefix("bin/") {
+            Some(bin) => t.is("bin") && t.name == bin,
+            None => t.is("test") && t.name == target,
+        },
+    })?;
+    Some(found.src_path.clone())
+}
+
+/// One line of libtest's JSON output.
+#[derive(Debug, Deserialize)]
+struct LibtestEvent {
+    #[serde(rename = "type")]
+    kind: String,
+    event: String,
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    exec_time: Option<f64>,
+    #[serde(default)]
+    stdout: Option<String>,
+    #[serde(default)]
+    message: Option<String>,
+}
+
+/// What a failed test printed, split into its own output and the failure.
+///
+/// A panic reads `thread 'name' (id) panicked at src/lib.rs:10:5:`, then the
+/// message and, for `assert_eq!`/`assert_ne!`, the `left:` and `right:`
+/// values. Following `assert_eq!(actual, expected)`, `left` is shown as the
+/// actual value.
+fn panic_failure(
+    stdout: &str,
+    message: Option<&str>,
+    workspace_root: &Path,
+) -> (FailureOutput, Option<String>) {
+    let lines: Vec<&str> = stdout.lines().collect();
+    let header = lines
+        .iter()
+        .position(|line| line.starts_with("thread '") && line.contains(" panicked at "));
+    let Some(header) = header else {
+        // Tests returning `Err` print it as `Error: …`.
+        let error = lines.iter().rev().find(|line| line.starts_with("Error: "));
+        let message = error
+            .map(|line| line.to_string())
+            .or_else(|| message.map(str::to_string))
+            .unwrap_or_else(|| "test failed".to_string());
+        let console = stdout.trim_end();
+        return (
+            failure(message),
+            (!console.is_empty()).then(|| console.to_string()),
+        );
+    };
+
+    let console = lines[..header].join("\n");
+    let console = console.trim_end();
+    let location = lines[header]
+        .split_once(" panicked at ")
+        .map(|(_, at)| at.trim_end_matches(':'))
+        .unwrap_or_default();
+
+    let body = &lines[header + 1..];
+    let backtrace = body
+        .iter()
+        .position(|line| line.starts_with("stack backtrace:") || line.starts_with("note: "))
+        .unwrap_or(body.len());
+    let (body, trailer) = body.split_at(backtrace);
+    let left = body.iter().position(|line| line.starts_with("  left: "));
+    let right = body.iter().position(|line| line.starts_with(" right: "));
+
+    let mut failure = match (left, right) {
+        (Some(left), Some(right)) if left < right => {
+            let actual = value_lines(&body[left..right], "  left: ");
+            let expected = value_lines(&body[right..], " right: ");
+            let mut failure = failure(body[..left].join("\n"));
+            failure.actual_parsed = parse_debug(&actual);
+            failure.expected_parsed = parse_debug(&expected);
+            failure.actual = Some(actual);
+            failure.expected = Some(expected);
+            failure
+        }
+        _ => failure(body.join("\n")),
+    };
+
+    let mut stack = Vec::new();
+    if let Some((path, position)) = location.split_once(':') {
+        stack.push(format!(
+            "at {}:{position}",
+            workspace_root.join(path).display()
+        ));
+    }
+    stack.extend(
+        trailer
+            .iter()
+            .skip_while(|line| !line.starts_with("stack backtrace:"))
+            .skip(1)
+            .take_while(|line| !line.starts_with("note: "))
+            .map(|line| line.to_string()),
+    );
+    failure.stack_trace = (!stack.is_empty()).then(|| stack.join("\n"));
+
+    (failure, (!console.is_empty()).then(|| console.to_string()))
+}
+
+/// A value printed after `label`, which may run on over several lines.
+fn value_lines(lines: &[&str], label: &str) -> String {
+    let mut value = lines[0].strip_prefix(label).unwrap_or(lines[0]).to_string();
+    for line in &lines[1..] {
+        value.push('\n');
+        value.push_str(line);
+    }
+    value
+}
+
+fn failure(message: String) -> FailureOutput {
+    FailureOutput {
+        message,
+        expected: None,
+        actual: None,
+        expected_parsed: None,
+        actual_parsed: None,
+        diff: None,
+        source_snippet: None,
+        stack_trace: None,
+        attachments: Vec::new(),
+    }
+}
+
+/// Parse a value printed with `{:?}` into JSON for the structured diff:
+/// structs and maps become objects; tuples, tuple structs and sequences
+/// become arrays. Like Vitest's values, only objects and arrays are kept.
+fn parse_debug(s: &str) -> Option<serde_json::Value> {
+    let mut parser = DebugParser {
+        chars: s.trim().chars().collect(),
+        pos: 0,
+    };
+    let value = parser.value()?;
+    parser.skip_whitespace();
+    if parser.pos < parser.chars.len() {
+        return None;
+    }
+    matches!(
+        value,
+        serde_json::Value::Object(_) | serde_json::Value::Array(_)
+    )
+    .then_some(value)
+}
+
+struct DebugParser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl DebugParser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.pos += 1;
+        }
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        self.skip_whitespace();
+        let found = self.peek() == Some(c);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn value(&mut self) -> Option<serde_json::Value> {
+        use serde_json::Value;
+        self.skip_whitespace();
+        match self.peek()? {
+            '[' => {
+                self.pos += 1;
+                self.items(']').map(Value::Array)
+            }
+            '(' => {
+                self.pos += 1;
+                let items = self.items(')')?;
+                Some(if items.is_empty() {
+                    Value::Null
+                } else {
+                    Value::Array(items)
+                })
+            }
+            '{' => {
+                self.pos += 1;
+                self.map()
+            }
+            '"' | '\'' => self.string().map(Value::String),
+            _ => {
+                let word = self.word();
+                if word.is_empty() {
+                    return None;
+                }
+                if self.eat('{') {
+                    self.fields()
+                } else if self.eat('(') {
+                    // `Some(x)` and other one-field wrappers stand for what they wrap.
+                    let mut items = self.items(')')?;
+                    Some(if items.len() == 1 {
+                        items.remove(0)
+                    } else {
+                        Value::Array(items)
+                    })
+                } else {
+                    Some(scalar(&word))
+                }
+            }
+        }
+    }
+
+    /// Comma-separated values up to `close`.
+    fn items(&mut self, close: char) -> Option<Vec<serde_json::Value>> {
+        let mut items = Vec::new();
+        loop {
+            if self.eat(close) {
+                return Some(items);
+            }
+            items.push(self.value()?);
+            if !self.eat(',') && self.peek_past_whitespace() != Some(close) {
+                return None;
+            }
+        }
+    }
+
+    /// A struct's `field: value` pairs up to `}`, skipping a `..` for fields
+    /// left out.
+    fn fields(&mut self) -> Option<serde_json::Value> {
+        let mut object = serde_json::Map::new();
+        loop {
+            if self.eat('}') {
+                return Some(serde_json::Value::Object(object));
+            }
+            if self.eat('.') && self.eat('.') {
+                continue;
+            }
+            let key = self.word();
+            if key.is_empty() || !self.eat(':') {
+                return None;
+            }
+            object.insert(key, self.value()?);
+            if !self.eat(',') && self.peek_past_whitespace() != Some('}') {
+                return None;
+            }
+        }
+    }
+
+    /// A map's `key: value` entries or a set's values, up to `}`.
+    fn map(&mut self) -> Option<serde_json::Value> {
+        use serde_json::Value;
+        let mut object = serde_json::Map::new();
+        let mut set = Vec::new();
+        loop {
+            if self.eat('}') {
+                return Some(if set.is_empty() {
+                    Value::Object(object)
+                } else {
+                    Value::Array(set)
+                });
+            }
+            let key = self.value()?;
+            if self.eat(':') {
+                let key = match key {
+                    Value::String(s) => s,
+                    other => other.to_string(),
+                };
+                object.insert(key, self.value()?);
+            } else if object.is_empty() {
+                set.push(key);
+            } else {
+                return None;
+            }
+            if !self.eat(',') && self.peek_past_whitespace() != Some('}') {
+                return None;
+            }
+        }
+    }
+
+    fn peek_past_whitespace(&mut self) -> Option<char> {
+        self.skip_whitespace();
+        self.peek()
+    }
+
+    /// An identifier, path or number.
+    fn word(&mut self) -> String {
+        self.skip_whitespace();
+        let start = self.pos;
+        while let Some(c) = self.peek() {
+            let next = self.chars.get(self.pos + 1).copied();
+            match c {
+                // Paths go on past `::`; a lone `:` ends a field's name.
+                ':' if next == Some(':') => self.pos += 2,
+                // `..` is a struct's marker for fields left out.
+                '.' if next == Some('.') => break,
+                c if c.is_alphanumeric() || "_.+-#".contains(c) => self.pos += 1,
+                _ => break,
+            }
+        }
+        self.chars[start..self.pos].iter().collect()
+    }
+
+    /// A string or char literal, with its escapes undone.
+    fn string(&mut self) -> Option<String> {
+        let quote = self.peek()?;
+        self.pos += 1;
+        let mut out = String::new();
+        loop {
+            let c = self.peek()?;
+            self.pos += 1;
+            match c {
+                '\\' => {
+                    let escaped = self.peek()?;
+                    self.pos += 1;
+                    match escaped {
+                        'n' => out.push('\n'),
+                        't' => out.push('\t'),
+                        'r' => out.push('\r'),
+                        '0' => out.push('\0'),
+                        'u' if self.eat('{') => {
+                            let start = self.pos;
+                            while self.peek().is_some_and(|c| c != '}') {
+                                self.pos += 1;
+                            }
+                            let hex: String = self.chars[start..self.pos].iter().collect();
+                            self.pos += 1;
+                            out.push(
+                                u32::from_str_radix(&hex, 16)
+                                    .ok()
+                                    .and_then(char::from_u32)?,
+                            );
+                        }
+                        other => out.push(other),
+                    }
+                }
+                c if c == quote => return Some(out),
+                c => out.push(c),
+            }
+        }
+    }
+}
+
+/// A bare word: a bool, `None`, a number, or anything else (a unit variant,
+/// say) as a string.
+fn scalar(word: &str) -> serde_json::Value {
+    use serde_json::Value;
+    match word {
+        "true" => Value::Bool(true),
+        "false" => Value::Bool(false),
+        "None" => Value::Null,
+        _ => match serde_json::from_str::<Value>(word) {
+            Ok(number @ Value::Number(_)) => number,
+            _ => Value::String(word.to_string()),
+        },
+    }
+}
+
+/// The line and column of test function `name` (its full lens name) in
+/// `source`, looking for each suite's `mod` before the `fn`.
+fn fn_location(source: &str, name: &str) -> Option<(u32, u32)> {
+    let mut parts: Vec<&str> = name.split(" > ").collect();
+    let test = parts.pop()?;
+    let lines: Vec<&str> = source.lines().collect();
+    let mut from = 0;
+    for module in parts {
+        let needle = format!("mod {module}");
+        from += lines[from..].iter().position(|line| {
+            line.find(&needle).is_some_and(|i| {
+                let rest = &line[i + needle.len()..];
+                rest.starts_with([' ', '{']) || rest.is_empty()
+            })
+        })?;
+    }
+    let needle = format!("fn {test}");
+    lines[from..].iter().enumerate().find_map(|(i, line)| {
+        let column = line.find(&needle)?;
+        let rest = &line[column + needle.len()..];
+        rest.starts_with(['(', '<'])
+            .then(|| ((from + i + 1) as u32, (column + 4) as u32))
+    })
+}
+
+/// One `cargo test` invocation.
+#[derive(Debug, Default, PartialEq)]
+struct Invocation {
+    /// The cargo workspace's root, where cargo runs.
+    cwd: PathBuf,
+    /// Package and target selection.
+    args: Vec<String>,
+    /// Test name filters; everything in the selected targets runs when empty.
+    filters: Vec<String>,
+    /// Match the filters against whole names.
+    exact: bool,
+}
+
+/// Totals and files seen across the invocations of one run.
+struct RunState {
+    started: Instant,
+    passed: usize,
+    failed: usize,
+    skipped: usize,
+    files: Vec<String>,
+    /// Source files read to find tests' lines.
+    sources: HashMap<PathBuf, Option<String>>,
+}
+
+/// Cargo adapter that runs libtest with its JSON output, through
+/// `cargo nextest` when it's installed and `cargo test` otherwise.
+///
+/// Tests are placed under the file their module lives in, each package is a
+/// project named after it, and modules declared inline (`mod tests`) become
+/// suites. Doc tests go under the file rustdoc names.
+pub struct CargoRunner {
+    base: RunnerBase,
+    /// Coverage was asked for; cargo doesn't collect it, which runs say.
+    coverage: AtomicBool,
+    /// `cargo metadata` for each search root's workspace, refreshed on
+    /// discovery.
+    metadata: Mutex<Vec<Metadata>>,
+    /// Whether `cargo nextest` is installed, checked on first run.
+    nextest: OnceCell<bool>,
+}
+
+impl CargoRunner {
+    pub fn new(
+        workspace: PathBuf,
+        project_roots: Vec<PathBuf>,
+        ignore_patterns: Vec<String>,
+    ) -> Self {
+        Self {
+            base: RunnerBase::new(workspace, project_roots, ignore_patterns),
+            coverage: AtomicBool::new(false),
+            metadata: Mutex::new(Vec::new()),
+            nextest: OnceCell::new(),
+        }
+    }
+
+    /// Read `cargo metadata` for every search root, once per cargo workspace.
+    async fn load_metadata(&self) -> Result<Vec<Metadata>> {
+        let mut workspaces: Vec<Metadata> = Vec::new();
+        for root in &self.base.search_roots {
+            let output = Command::new("cargo")
+                .args(["metadata", "--no-deps", "--format-version", "1"])
+                .current_dir(root)
+                .output()
+                .await
+                .context("failed to run `cargo metadata`")?;
+            if !output.status.success() {
+                bail!(
+                    "cargo metadata failed in {}: {}",
+                    root.display(),
+                    String::from_utf8_lossy(&output.stderr).trim()
+                );
+            }
+            let metadata: Metadata = serde_json::from_slice(&output.stdout)
+                .context("failed to parse `cargo metadata` output")?;
+            if !workspaces
+                .iter()
+                .any(|ws| ws.workspace_root == metadata.workspace_root)
+            {
+                workspaces.push(metadata);
+            }
+        }
+        *self.metadata.lock().unwrap() = workspaces.clone();
+        Ok(workspaces)
+    }
+
+    /// The cached metadata, loaded if discovery hasn't yet.
+    async fn workspaces(&self) -> Result<Vec<Metadata>> {
+        let cached = self.metadata.lock().unwrap().clone();
+        if cached.is_empty() {
+            self.load_metadata().await
+        } else {
+            Ok(cached)
+        }
+    }
+
+    async fn has_nextest(&self) -> bool {
+        *self
+            .nextest
+            .get_or_init(|| async {
+                Command::new("cargo")
+                    .args(["nextest", "--version"])
+                    .stdout(std::process::Stdio::null())
+                    .stderr(std::process::Stdio::null())
+                    .status()
+                    .await
+                    .is_ok_and(|status| status.success())
+            })
+            .await
+    }
+
+    /// Every package under the search roots, one invocation per cargo workspace.
+    fn all_invocations(&self, workspaces: &[Metadata]) -> Vec<Invocation> {
+        workspaces
+            .iter()
+            .map(|ws| Invocation {
+                cwd: ws.workspace_root.clone(),
+                args: ws
+                    .packages
+                    .iter()
+                    .filter(|p| {
+                        self.base
+                            .search_roots
+                            .iter()
+                            .any(|r| p.dir().starts_with(r))
+                    })
+                    .flat_map(|p| ["-p".to_string(), p.name.clone()])
+                    .collect(),
+                ..Default::default()
+            })
+            .filter(|inv| !inv.args.is_empty())
+            .collect()
+    }
+
+    /// Invocations running `files`, one per package. With `names`, only
+    /// those tests (or suites, unless `exact`) in the files run. Doc tests
+    /// get an invocation of their own, as rustdoc only matches their names
+    /// in part.
+    fn file_invocations(
+        &self,
+        workspaces: &[Metadata],
+        files: &[PathBuf],
+        names: Option<&[String]>,
+        exact: bool,
+    ) -> Vec<Invocation> {
+        let mut invocations: Vec<Invocation> = Vec::new();
+        // Invocations that run everything in their targets.
+        let mut unfiltered = Vec::new();
+        let mut add = |located: &Located, selectors: Vec<Vec<String>>, filter: Option<String>| {
+            let doc = selectors.iter().any(|s| s[0] == "--doc");
+            let package = ["-p".to_string(), located.package.name.clone()];
+            let index = invocations
+                .iter()
+                .position(|inv| {
+                    inv.cwd == located.workspace.workspace_root
+                        && inv.args[..2] == package
+                        && inv.args.iter().any(|a| a == "--doc") == doc
+                })
+                .unwrap_or_else(|| {
+                    invocations.push(Invocation {
+                        cwd: located.workspace.workspace_root.clone(),
+                        args: package.to_vec(),
+                        filters: Vec::new(),
+                        exact: exact && !doc,
+                    });
+                    invocations.len() - 1
+                });
+            let invocation = &mut invocations[index];
+            for selector in selectors {
+                if !invocation
+                    .args
+                    .windows(selector.len())
+                    .any(|w| w == selector.as_slice())
+                {
+                    invocation.args.extend(selector);
+                }
+            }
+            match filter {
+                Some(filter) => invocation.filters.push(filter),
+                None => unfiltered.push(index),
+            }
+        };
+
+        for file in files {
+            let Some(located) = locate(workspaces, file) else {
+                self.base
+                    .log(&format!("[cargo] no target compiles {}", file.display()));
+                continue;
+            };
+            let selectors = || located.targets.iter().map(|t| t.selector()).collect();
+            match names {
+                Some(names) => {
+                    for name in names {
+                        if is_doc_test(name) {
+                            let path = file
+                                .strip_prefix(&located.workspace.workspace_root)
+                                .unwrap_or(file);
+                            let filter =
+                                format!("{} - {}", path.display(), name.replace(" > ", "::"));
+                            add(&located, vec![vec!["--doc".to_string()]], Some(filter));
+                        } else {
+                            let name = name.replace(" > ", "::");
+                            add(
+                                &located,
+                                selectors(),
+                                Some(module_path(&located.module, &name)),
+                            );
+                        }
+                    }
+                }
+                // A target's root file holds tests of its own beside the
+                // modules it declares, and there's no filter for just those.
+                None if located.module.is_empty() => add(&located, selectors(), None),
+                None => add(&located, selectors(), Some(format!("{}::", located.module))),
+            }
+        }
+
+        for index in unfiltered {
+            invocations[index].filters.clear();
+        }
+        invocations
+    }
+
+    /// Run `invocations` as one run, with `env` set for each.
+    async fn run(
+        &self,
+        invocations: Vec<Invocation>,
+        env: &[(&str, &str)],
+        tx: &mpsc::UnboundedSender<TestEvent>,
+    ) -> Result<()> {
+        let workspaces = self.workspaces().await?;
+        let nextest = self.has_nextest().await;
+        let mut state = RunState {
+            started: Instant::now(),
+            passed: 0,
+            failed: 0,
+            skipped: 0,
+            files: Vec::new(),
+            sources: HashMap::new(),
+        };
+
+        let _ = tx.send(TestEvent::RunStarted);
+        for invocation in &invocations {
+            // nextest doesn't run doc tests.
+            let nextest = nextest && !invocation.args.iter().any(|a| a == "--doc");
+            self.spawn_and_stream(invocation, env, nextest, &workspaces, &mut state, tx)
+                .await?;
+        }
+
+        for path in std::mem::take(&mut state.files) {
+            let _ = tx.send(TestEvent::FileFinished { path });
+        }
+        let _ = tx.send(TestEvent::RunFinished {
+            summary: RunSummary {
+                total: state.passed + state.failed + state.skipped,
+                passed: state.passed,
+                failed: state.failed,
+                skipped: state.skipped,
+                duration: state.started.elapsed().as_millis() as u64,
+                ..Default::default()
+            },
+        });
+        Ok(())
+    }
+
+    /// Re-run `invocations` whenever a file under the search roots changes.
+    async fn watch(
+        &self,
+        invocations: impl Fn(&[Metadata]) -> Vec<Invocation>,
+        tx: mpsc::UnboundedSender<TestEvent>,
+    ) -> Result<()> {
+        rerun_on_change(&self.base.search_roots, &WATCH_EXTENSIONS, || async {
+            let workspaces = self.workspaces().await?;
+            self.run(invocations(&workspaces), &[], &tx).await
+        })
+        .await
+    }
+
+    /// Spawn one `cargo test` (or `cargo nextest run`) and turn its libtest
+    /// JSON into events.
+    async fn spawn_and_stream(
+        &self,
+        invocation: &Invocation,
+        env: &[(&str, &str)],
+        nextest: bool,
+        workspaces: &[Metadata],
+        state: &mut RunState,
+        tx: &mpsc::UnboundedSender<TestEvent>,
+    ) -> Result<()> {
+        let mut cmd = Command::new("cargo");
+        // Results are paired with the binaries cargo names on stderr, which
+        // `term.quiet` would leave out.
+        cmd.env("CARGO_TERM_QUIET", "false");
+        if nextest {
+            cmd.env("NEXTEST_EXPERIMENTAL_LIBTEST_JSON", "1")
+                .args(["nextest", "run", "--no-fail-fast"])
+                .args(["--message-format", "libtest-json"])
+                .args(&invocation.args)
+                .arg("--");
+        } else {
+            // Lets a stable toolchain's libtest take `-Z unstable-options`;
+            // cargo doesn't rebuild for it.
+            cmd.env("RUSTC_BOOTSTRAP", "1")
+                .args(["test", "--no-fail-fast"])
+                .args(&invocation.args)
+                .args(["--", "-Z", "unstable-options", "--format", "json"])
+                .arg("--report-time");
+        }
+        cmd.args(&invocation.filters);
+        if invocation.exact {
+            cmd.arg("--exact");
+        }
+        cmd.envs(env.iter().copied());
+
+        // Log the full command for debugging (LENS_DEBUG=path)
+        self.base.log(&format!("[cmd] {:?}", cmd.as_std()));
+        self.base.log(&format!("[cwd] {:?}", invocation.cwd));
+        let _ = tx.send(TestEvent::CommandStarted {
+            command: command_line(&cmd),
+            cwd: invocation.cwd.clone(),
+        });
+        if self.coverage.load(Ordering::Relaxed) {
+            let _ = tx.send(TestEvent::Output {
+                line: "Coverage isn't collected for cargo runs.".to_string(),
+            });
+        }
+
+        // Put the child in its own process group so killing it (via ChildGuard)
+        // also takes out the test binaries cargo runs (prevents orphans).
+        #[cfg(unix)]
+        {
+            use std::os::unix::process::CommandExt;
+            cmd.as_std_mut().process_group(0);
+        }
+
+        let mut child = cmd
+            .current_dir(&invocation.cwd)
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped())
+            .spawn()
+            .context("failed to spawn cargo")?;
+
+        drop(child.stdin.take());
+        let stdout = child.stdout.take().context("missing stdout")?;
+        let stderr = child.stderr.take().context("missing stderr")?;
+
+        let mut child_guard = ChildGuard::new(child);
+
+        let workspace = workspaces
+            .iter()
+            .find(|ws| ws.workspace_root == invocation.cwd)
+            .cloned()
+            .context("cargo workspace not loaded")?;
+
+        // Cargo says which binary runs next on stderr; each one's results
+        // start with a suite event on stdout.
+        let (binary_tx, mut binary_rx) = mpsc::unbounded_channel();
+        let tx_err = tx.clone();
+        let log_err = self.base.log_file.clone();
+        let stderr_workspace = workspace.clone();
+        let stderr_handle = tokio::spawn(async move {
+            let mut errors = Vec::new();
+            let mut lines = BufReader::new(stderr).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                if let Some(ref lf) = log_err {
+                    write_log(lf, &format!("[stderr] {}", line));
+                }
+                if let Some(binary) = binary_line(&line, &stderr_workspace) {
+                    let _ = binary_tx.send(binary);
+                }
+                if line.starts_with("error") {
+                    errors.push(line.clone());
+                }
+                let _ = tx_err.send(TestEvent::Output { line });
+            }
+            errors
+        });
+
+        let mut binary: Option<Binary> = None;
+        let mut missed = 0;
+        let mut ran_tests = false;
+        let mut lines = BufReader::new(stdout).lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            if let Some(ref lf) = self.base.log_file {
+                write_log(lf, &format!("[stdout] {}", line));
+            }
+            let Ok(event) = serde_json::from_str::<LibtestEvent>(&line) else {
+                let _ = tx.send(TestEvent::Output { line });
+                continue;
+            };
+            if event.kind == "suite" && event.event == "started" {
+                ran_tests = true;
+                if !nextest {
+                    binary = next_binary(&mut binary_rx, &mut missed, BINARY_LINE_WAIT).await;
+                }
+                continue;
+            }
+            let (Some(full_name), "test") = (event.name.as_deref(), event.kind.as_str()) else {
+                continue;
+            };
+
+            let resolved = if nextest {
+                full_name
+                    .split_once('$')
+                    .and_then(|(id, name)| Some(test_path(&nextest_binary(workspaces, id)?, name)))
+            } else {
+                match &binary {
+                    Some(Binary::Tests(root)) => Some(test_path(root, full_name)),
+                    Some(Binary::Doc(root)) => doc_test_path(root, full_name),
+                    None => None,
+                }
+            };
+            let Some((path, name)) = resolved else {
+                continue;
+            };
+            let file = path.to_string_lossy().into_owned();
+
+            let status = match event.event.as_str() {
+                "started" => {
+                    if !state.files.contains(&file) {
+                        state.files.push(file.clone());
+                        let _ = tx.send(TestEvent::FileStarted { path: file.clone() });
+                    }
+                    let _ = tx.send(TestEvent::TestStarted { file, name });
+                    continue;
+                }
+                "ok" => TestStatus::Passed,
+                "failed" => TestStatus::Failed,
+                "ignored" => TestStatus::Skipped,
+                _ => continue,
+            };
+            match status {
+                TestStatus::Passed => state.passed += 1,
+                TestStatus::Failed => state.failed += 1,
+                _ => state.skipped += 1,
+            }
+
+            let mut failures = Vec::new();
+            if status == TestStatus::Failed {
+                let (failure, console) = panic_failure(
+                    event.stdout.as_deref().unwrap_or_default(),
+                    event.message.as_deref(),
+                    &workspace.workspace_root,
+                );
+                if let Some(content) = console {
+                    let _ = tx.send(TestEvent::ConsoleLog {
+                        file: file.clone(),
+                        test: Some(name.clone()),
+                        stream: ConsoleStream::Stdout,
+                        content,
+                    });
+                }
+                failures.push(failure);
+            }
+            let location = if is_doc_test(&name) {
+                None
+            } else {
+                state
+                    .sources
+                    .entry(path.clone())
+                    .or_insert_with(|| std::fs::read_to_string(&path).ok())
+                    .as_deref()
+                    .and_then(|source| fn_location(source, &name))
+            };
+            let _ = tx.send(TestEvent::TestFinished {
+                file,
+                name,
+                result: Box::new(TestResult {
+                    status,
+                    duration_ms: event.exec_time.map(|secs| (secs * 1000.0) as u64),
+                    failures,
+                }),
+                location,
+            });
+        }
+
+        let errors = stderr_handle.await.unwrap_or_default();
+        if let Some(ref mut child) = child_guard.child {
+            let status = child.wait().await.context("failed to wait for cargo")?;
+            // Failing tests also exit non-zero; that's already visible in the
+            // tree. Without any tests run, the build failed.
+            if !status.success() && !ran_tests {
+                let message = if errors.is_empty() {
+                    format!("cargo exited with code {}", status.code().unwrap_or(-1))
+                } else {
+                    errors.join("\n")
+                };
+                let _ = tx.send(TestEvent::Error { message });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// `name` inside `module`, joined with `::`.
+fn module_path(module: &str, name: &str) -> String {
+    if module.is_empty() {
+        name.to_string()
+    } else {
+        format!("{module}::{name}")
+    }
+}
+
+/// Whether a Rust source file has tests in it.
+fn has_tests(source: &str) -> bool {
+    source.contains("#[test]") || source.contains("::test]") || source.contains("#[cfg(test)]")
+}
+
+#[async_trait]
+impl TestRunner for CargoRunner {
+    async fn discover(&self, workspace: &Path) -> Result<Vec<DiscoveredFile>> {
+        let workspaces = self.load_metadata().await?;
+        let files = discover_files(
+            workspace,
+            &self.base.workspace,
+            &["*.rs"],
+            &self.base.ignore_patterns,
+        )?;
+        Ok(files
+            .into_iter()
+            .filter(|f| std::fs::read_to_string(&f.path).is_ok_and(|s| has_tests(&s)))
+            .filter_map(|f| {
+                let package = locate(&workspaces, &f.path)?.package;
+                Some(DiscoveredFile {
+                    project: Some((package.name.clone(), package.dir().to_path_buf())),
+                    ..f
+                })
+            })
+            .collect())
+    }
+
+    async fn run_all(&self, tx: mpsc::UnboundedSender<TestEvent>) -> Result<()> {
+        let workspaces = self.workspaces().await?;
+        self.run(self.all_invocations(&workspaces), &[], &tx).await
+    }
+
+    async fn run_files(
+        &self,
+        files: &[PathBuf],
+        tx: mpsc::UnboundedSender<TestEvent>,
+    ) -> Result<()> {
+        let workspaces = self.workspaces().await?;
+        let invocations = self.file_invocations(&workspaces, files, None, false);
+        self.run(invocations, &[], &tx).await
+    }
+
+    async fn run_file(&self, file: &Path, tx: mpsc::UnboundedSender<TestEvent>) -> Result<()> {
+        self.run_files(&[file.to_path_buf()], tx).await
+    }
+
+    async fn run_test(
+        &self,
+        file: &Path,
+        test_name: &str,
+        tx: mpsc::UnboundedSender<TestEvent>,
+    ) -> Result<()> {
+        let workspaces = self.workspaces().await?;
+        let names = [test_name.to_string()];
+        let invocations =
+            self.file_invocations(&workspaces, &[file.to_path_buf()], Some(&names), false);
+        self.run(invocations, &[], &tx).await
+    }
+
+    async fn run_tests(
+        &self,
+        files: &[PathBuf],
+        names: &[String],
+        tx: mpsc::UnboundedSender<TestEvent>,
+    ) -> Result<()> {
+        let workspaces = self.workspaces().await?;
+        let invocations = self.file_invocations(&workspaces, files, Some(names), true);
+        self.run(invocations, &[], &tx).await
+    }
+
+    async fn update_snapshots(
+        &self,
+        files: &[PathBuf],
+        test_name: Option<&str>,
+        tx: mpsc::UnboundedSender<TestEvent>,
+    ) -> Result<()> {
+        let workspaces = self.workspaces().await?;
+        let names = test_name.map(|name| vec![name.to_string()]);
+        let invocations = self.file_invocations(&workspaces, files, names.as_deref(), false);
+        // insta and expect-test rewrite their snapshots with these set.
+        let env = [
+            ("INSTA_UPDATE", "always"),
+            ("INSTA_FORCE_PASS", "1"),
+            ("UPDATE_EXPECT", "1"),
+        ];
+        self.run(invocations, &env, &tx).await
+    }
+
+    async fn run_file_watch(
+        &self,
+        file: &Path,
+        tx: mpsc::UnboundedSender<TestEvent>,
+    ) -> Result<()> {
+        let files = [file.to_path_buf()];
+        self.watch(|ws| self.file_invocations(ws, &files, None, false), tx)
+            .await
+    }
+
+    async fn run_test_watch(
+        &self,
+        file: &Path,
+        test_name: &str,
+        tx: mpsc::UnboundedSender<TestEvent>,
+    ) -> Result<()> {
+        let files = [file.to_path_buf()];
+        let names = [test_name.to_string()];
+        self.watch(
+            |ws| self.file_invocations(ws, &files, Some(&names), false),
+            tx,
+        )
+        .await
+    }
+
+    async fn run_all_watch(&self, tx: mpsc::UnboundedSender<TestEvent>) -> Result<()> {
+        self.watch(|ws| self.all_invocations(ws), tx).await
+    }
+
+    fn set_coverage(&self, enabled: bool) {
+        self.coverage.store(enabled, Ordering::Relaxed);
+    }
+
+    fn name(&self) -> &str {
+        "cargo test"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A workspace with one package: a library with a `util` module directory
+    /// and an integration test.
+    fn crate_fixture() -> (tempfile::TempDir, Vec<Metadata>) {
+        let dir = tempfile::tempdir().unwrap();
+        for file in [
+            "crates/a/src/lib.rs",
+            "crates/a/src/util/mod.rs",
+            "crates/a/src/util/deep.rs",
+            "crates/a/tests/api.rs",
+        ] {
+            let path = dir.path().join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, "").unwrap();
+        }
+        let root = dir.path().to_path_buf();
+        let target = |name: &str, kind: &str, src: &str| Target {
+            name: name.to_string(),
+            kind: vec![kind.to_string()],
+            src_path: root.join("crates/a").join(src),
+        };
+        let metadata = Metadata {
+            workspace_root: root.clone(),
+            packages: vec![Package {
+                name: "my-a".to_string(),
+                manifest_path: root.join("crates/a/Cargo.toml"),
+                targets: vec![
+                    target("my_a", "lib", "src/lib.rs"),
+                    target("api", "test", "tests/api.rs"),
+                ],
+            }],
+        };
+        (dir, vec![metadata])
+    }
+
+    #[test]
+    fn tests_go_under_the_file_of_their_module() {
+        let (dir, _) = crate_fixture();
+        let lib = dir.path().join("crates/a/src/lib.rs");
+        let src = dir.path().join("crates/a/src");
+        assert_eq!(
+            test_path(&lib, "tests::adds"),
+            (lib.clone(), "tests > adds".to_string())
+        );
+        assert_eq!(
+            test_path(&lib, "util::tests::works"),
+            (src.join("util/mod.rs"), "tests > works".to_string())
+        );
+        assert_eq!(
+            test_path(&lib, "util::deep::deep_test"),
+            (src.join("util/deep.rs"), "deep_test".to_string())
+        );
+    }
+
+    #[test]
+    fn files_run_their_module_in_their_target() {
+        let (dir, workspaces) = crate_fixture();
+        let runner = CargoRunner::new(dir.path().to_path_buf(), vec![], vec![]);
+        let src = dir.path().join("crates/a/src");
+
+        let invocations = runner.file_invocations(
+            &workspaces,
+            &[
+                src.join("util/mod.rs"),
+                dir.path().join("crates/a/tests/api.rs"),
+            ],
+            None,
+            false,
+        );
+        assert_eq!(
+            invocations,
+            [Invocation {
+                cwd: dir.path().to_path_buf(),
+                args: ["-p", "my-a", "--lib", "--test", "api"]
+                    .map(String::from)
+                    .to_vec(),
+                filters: Vec::new(),
+                exact: false,
+            }]
+        );
+
+        let names = ["tests > works".to_string(), "add (line 6)".to_string()];
+        let invocations =
+            runner.file_invocations(&workspaces, &[src.join("util/mod.rs")], Some(&names), true);
+        assert_eq!(invocations.len(), 2);
+        assert_eq!(invocations[0].args, ["-p", "my-a", "--lib"]);
+        assert_eq!(invocations[0].filters, ["util::tests::works"]);
+        assert!(invocations[0].exact);
+        assert_eq!(invocations[1].args, ["-p", "my-a", "--doc"]);
+        assert_eq!(
+            invocations[1].filters,
+            ["crates/a/src/util/mod.rs - add (line 6)"]
+        );
+        assert!(!invocations[1].exact);
+    }
+
+    #[test]
+    fn tree_names_filter_on_their_full_module_path() {
+        let (dir, workspaces) = crate_fixture();
+        let runner = CargoRunner::new(dir.path().to_path_buf(), vec![], vec![]);
+        let lib = dir.path().join("crates/a/src/lib.rs");
+
+        let (file, name) = test_path(&lib, "util::tests::works");
+        let invocations = runner.file_invocations(&workspaces, &[file], Some(&[name]), false);
+        assert_eq!(invocations[0].filters, ["util::tests::works"]);
+
+        let (file, name) =
+            doc_test_path(dir.path(), "crates/a/src/lib.rs - Calc::add (line 12)").unwrap();
+        assert_eq!(name, "Calc > add (line 12)");
+        let invocations = runner.file_invocations(&workspaces, &[file], Some(&[name]), false);
+        assert_eq!(
+            invocations[0].filters,
+            ["crates/a/src/lib.rs - Calc::add (line 12)"]
+        );
+    }
+
+    #[test]
+    fn cargo_s_running_lines_name_the_binary() {
+        let (dir, workspaces) = crate_fixture();
+        let workspace = &workspaces[0];
+        assert_eq!(
+            binary_line(
+                "     Running unittests src/lib.rs (target/debug/deps/my_a-62dd58e5dc3c973b)",
+                workspace
+            ),
+            Some(Some(Binary::Tests(dir.path().join("crates/a/src/lib.rs"))))
+        );
+        assert_eq!(
+            binary_line(
+                "     Running tests/api.rs (target/debug/deps/api-3b368b76ff876527)",
+                workspace
+            ),
+            Some(Some(Binary::Tests(
+                dir.path().join("crates/a/tests/api.rs")
+            )))
+        );
+        assert_eq!(
+            binary_line("   Doc-tests my_a", workspace),
+            Some(Some(Binary::Doc(dir.path().to_path_buf())))
+        );
+        assert_eq!(binary_line("   Compiling my-a v0.1.0", workspace), None);
+        assert_eq!(
+            nextest_binary(&workspaces, "my-a::api"),
+            Some(dir.path().join("crates/a/tests/api.rs"))
+        );
+    }
+
+    #[tokio::test]
+    async fn a_binary_without_a_running_line_does_not_wait_for_stderr() {
+        let (lines_tx, mut lines) = mpsc::unbounded_channel();
+        let mut missed = 0;
+        let wait = Duration::from_millis(20);
+
+        // Stderr stays open, as it does while cargo runs.
+        assert_eq!(next_binary(&mut lines, &mut missed, wait).await, None);
+        assert_eq!(missed, 1);
+
+        // The line for that binary turns up late; the next binary gets its own.
+        let late = Binary::Tests(PathBuf::from("src/lib.rs"));
+        let next = Binary::Tests(PathBuf::from("tests/api.rs"));
+        lines_tx.send(Some(late)).unwrap();
+        lines_tx.send(Some(next.clone())).unwrap();
+        assert_eq!(next_binary(&mut lines, &mut missed, wait).await, Some(next));
+        assert_eq!(missed, 0);
+    }
+
+    #[test]
+    fn assert_eq_panics_give_left_and_right() {
+        let stdout = "hello out\n\n\
+            thread 'tests::fails' (10705) panicked at crates/a/src/lib.rs:20:41:\n\
+            assertion `left == right` failed: points differ\n  \
+            left: P { x: 1, name: \"a\", tags: [\"t\"] }\n \
+            right: P { x: 2, name: \"a\", tags: [] }\n\
+            note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace\n";
+        let (failure, console) = panic_failure(stdout, None, Path::new("/ws"));
+
+        assert_eq!(console.as_deref(), Some("hello out"));
+        assert_eq!(
+            failure.message,
+            "assertion `left == right` failed: points differ"
+        );
+        assert_eq!(
+            failure.actual.as_deref(),
+            Some("P { x: 1, name: \"a\", tags: [\"t\"] }")
+        );
+        assert_eq!(
+            failure.expected_parsed,
+            Some(serde_json::json!({ "x": 2, "name": "a", "tags": [] }))
+        );
+        assert_eq!(
+            failure.stack_trace.as_deref(),
+            Some("at /ws/crates/a/src/lib.rs:20:41")
+        );
+    }
+
+    #[test]
+    fn debug_output_parses_into_json() {
+        assert_eq!(
+            parse_debug(
+                r#"Config { name: "a\"b", port: Some(80), mode: Fast, inner: Inner { .. } }"#
+            ),
+            Some(serde_json::json!({ "name": "a\"b", "port": 80, "mode": "Fast", "inner": {} }))
+        );
+        assert_eq!(
+            parse_debug(r#"{"k": [1, -2.5], "n": None}"#),
+            Some(serde_json::json!({ "k": [1, -2.5], "n": null }))
+        );
+        assert_eq!(
+            parse_debug("[(1, 'x'), (2, 'y')]"),
+            Some(serde_json::json!([[1, "x"], [2, "y"]]))
+        );
+        assert_eq!(parse_debug("{3, 1}"), Some(serde_json::json!([3, 1])));
+        assert_eq!(parse_debug("42"), None);
+        assert_eq!(parse_debug("Foo { a: }"), None);
+    }
+
+    #[test]
+    fn finds_test_functions_inside_their_modules() {
+        let source = "fn works() {}\n\
+            #[cfg(test)]\n\
+            mod tests {\n    \
+                #[test]\n    \
+                fn works() {}\n\
+            }\n";
+        assert_eq!(fn_location(source, "works"), Some((1, 4)));
+        assert_eq!(fn_location(source, "tests > works"), Some((5, 8)));
+        assert_eq!(fn_location(source, "missing"), None);
+    }
+}
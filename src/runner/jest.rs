@@ -0,0 +1,438 @@
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use tokio::process::Command;
+use tokio::sync::mpsc;
+
+use crate::app::TestEvent;
+
+use super::vitest::{ChildGuard, SUFFIXES, stream_output};
+use super::{
+    DiscoveredFile, RunnerBase, TestRunner, command_line, discover_files, escape_test_name,
+    full_names_pattern,
+};
+
+/// Jest's default `testMatch` also picks up anything under `__tests__/`.
+const TESTS_DIR_PATTERNS: [&str; 4] = [
+    "__tests__/**/*.ts",
+    "__tests__/**/*.tsx",
+    "__tests__/**/*.js",
+    "__tests__/**/*.jsx",
+];
+
+const CONFIG_NAMES: [&str; 6] = [
+    "jest.config.js",
+    "jest.config.ts",
+    "jest.config.mjs",
+    "jest.config.cjs",
+    "jest.config.mts",
+    "jest.config.json",
+];
+
+const REPORTER_SOURCE: &str = include_str!("../../reporters/jest-reporter.cjs");
+
+/// Returns true if `root` is configured for Jest, either through a
+/// `jest.config.*` file or a `jest` key in its `package.json`.
+pub fn is_jest_project(root: &Path) -> bool {
+    if CONFIG_NAMES.iter().any(|name| root.join(name).is_file()) {
+        return true;
+    }
+    std::fs::read_to_string(root.join("package.json"))
+        .ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+        .is_some_and(|pkg| pkg.get("jest").is_some())
+}
+
+/// Jest adapter that spawns jest with a custom reporter emitting the same
+/// NDJSON events as the Vitest reporter.
+pub struct JestRunner {
+    base: RunnerBase,
+    /// Collect coverage on every run.
+    coverage: AtomicBool,
+}
+
+impl JestRunner {
+    pub fn new(
+        workspace: PathBuf,
+        project_roots: Vec<PathBuf>,
+        ignore_patterns: Vec<String>,
+    ) -> Self {
+        Self {
+            base: RunnerBase::new(workspace, project_roots, ignore_patterns),
+            coverage: AtomicBool::new(false),
+        }
+    }
+
+    /// Write the embedded reporter to a temp file and return its path.
+    fn write_reporter(&self) -> Result<tempfile::NamedTempFile> {
+        let mut file = tempfile::Builder::new()
+            .prefix("lens-jest-reporter-")
+            .suffix(".cjs")
+            .tempfile()
+            .context("failed to create temp reporter file")?;
+
+        use std::io::Write;
+        file.write_all(REPORTER_SOURCE.as_bytes())
+            .context("failed to write reporter to temp file")?;
+
+        Ok(file)
+    }
+
+    /// Find all jest config files under the search roots.
+    fn find_jest_configs(&self) -> Vec<PathBuf> {
+        let mut configs = Vec::new();
+        for (root, name) in self
+            .base
+            .search_roots
+            .iter()
+            .flat_map(|root| CONFIG_NAMES.iter().map(move |name| (root, name)))
+        {
+            let pattern = root.join("**/").join(name).to_string_lossy().to_string();
+            if let Ok(entries) = glob::glob(&pattern) {
+                for entry in entries.flatten() {
+                    let path_str = entry.to_string_lossy();
+                    if !path_str.contains("node_modules") && !configs.contains(&entry) {
+                        configs.push(entry);
+                    }
+                }
+            }
+        }
+        configs
+    }
+
+    /// The config of the innermost project containing `file`.
+    fn find_config_for_file(&self, file: &Path) -> Option<PathBuf> {
+        self.find_jest_configs()
+            .into_iter()
+            .filter(|config| config.parent().is_some_and(|dir| file.starts_with(dir)))
+            .max_by_key(|config| config.components().count())
+    }
+
+    /// Build the project-selection args and working directory for a full run.
+    ///
+    /// A config at a lone search root is used as-is (it may aggregate projects
+    /// itself); otherwise every nested config is passed through `--projects`.
+    fn run_all_args(&self) -> (Vec<String>, PathBuf) {
+        let configs = self.find_jest_configs();
+        if let [root] = self.base.search_roots.as_slice()
+            && let Some(root_config) = configs.iter().find(|c| c.parent() == Some(root.as_path()))
+        {
+            let args = vec![
+                "--config".to_string(),
+                root_config.to_string_lossy().to_string(),
+            ];
+            return (args, root.clone());
+        }
+        if configs.is_empty() {
+            return (Vec::new(), self.base.default_cwd());
+        }
+        let mut args = vec!["--projects".to_string()];
+        args.extend(configs.iter().map(|c| c.to_string_lossy().to_string()));
+        (args, self.base.default_cwd())
+    }
+
+    /// Build the args and working directory for running specific files.
+    fn file_args(&self, files: &[PathBuf]) -> (Vec<String>, PathBuf) {
+        let mut args = Vec::new();
+        let mut cwd = self.base.default_cwd();
+        if let [file] = files
+            && let Some(config) = self.find_config_for_file(file)
+        {
+            if let Some(dir) = config.parent() {
+                cwd = dir.to_path_buf();
+            }
+            args.push("--config".to_string());
+            args.push(config.to_string_lossy().to_string());
+        } else {
+            let (project_args, _) = self.run_all_args();
+            args.extend(project_args);
+        }
+        args.push("--runTestsByPath".to_string());
+        args.extend(files.iter().map(|f| f.to_string_lossy().to_string()));
+        (args, cwd)
+    }
+
+    /// Spawn jest with the given args and stream NDJSON events from stdout.
+    ///
+    /// When `watch` is true, passes `--watchAll` so jest stays alive and re-runs
+    /// on file changes. Non-zero exit is not treated as an error in watch mode
+    /// (the process is killed on toggle-off).
+    async fn spawn_and_stream(
+        &self,
+        args: &[String],
+        tx: mpsc::UnboundedSender<TestEvent>,
+        watch: bool,
+        cwd: &Path,
+    ) -> Result<()> {
+        let reporter_file = self.write_reporter()?;
+
+        let mut cmd = Command::new("npx");
+        cmd.arg("jest");
+        if watch {
+            cmd.arg("--watchAll");
+        }
+        cmd.args(args)
+            .arg("--testLocationInResults")
+            .arg("--passWithNoTests")
+            .arg(format!(
+                "--reporters={}",
+                reporter_file.path().to_string_lossy()
+            ));
+        if self.coverage.load(Ordering::Relaxed) {
+            // The reporter reads the coverage map directly; the json-summary file
+            // only keeps jest from printing its table and stays out of `coverage/`.
+            cmd.arg("--coverage")
+                .arg("--coverageReporters=json-summary")
+                .arg(format!(
+                    "--coverageDirectory={}",
+                    self.base.workspace.join(".lens").join("coverage").display()
+                ));
+        }
+
+        // Log the full command for debugging (LENS_DEBUG=path)
+        self.base.log(&format!("[cmd] {:?}", cmd.as_std()));
+        self.base.log(&format!("[cwd] {:?}", cwd));
+        let _ = tx.send(TestEvent::CommandStarted {
+            command: command_line(&cmd),
+            cwd: cwd.to_path_buf(),
+        });
+
+        // Put the child in its own process group so killing it (via ChildGuard) also
+        // takes out any worker processes jest forks (prevents orphans).
+        #[cfg(unix)]
+        {
+            use std::os::unix::process::CommandExt;
+            cmd.as_std_mut().process_group(0);
+        }
+
+        let mut child = cmd
+            .current_dir(cwd)
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped())
+            .spawn()
+            .context("failed to spawn jest")?;
+
+        drop(child.stdin.take());
+        let stdout = child.stdout.take().context("missing stdout")?;
+        let stderr = child.stderr.take().context("missing stderr")?;
+
+        let mut child_guard = ChildGuard::new(child);
+
+        stream_output(stdout, stderr, &tx, self.base.log_file.clone()).await;
+
+        // Keep the temp file alive until jest exits
+        drop(reporter_file);
+
+        if !watch && let Some(ref mut child) = child_guard.child {
+            let status = child.wait().await.context("failed to wait for jest")?;
+            // Jest exits with 1 when tests fail; that's already visible in the tree.
+            if !status.success() && status.code() != Some(1) {
+                let _ = tx.send(TestEvent::Error {
+                    message: format!("jest exited with code {}", status.code().unwrap_or(-1)),
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl TestRunner for JestRunner {
+    async fn discover(&self, workspace: &Path) -> Result<Vec<DiscoveredFile>> {
+        let patterns: Vec<&str> = SUFFIXES
+            .iter()
+            .chain(TESTS_DIR_PATTERNS.iter())
+            .copied()
+            .collect();
+        discover_files(
+            workspace,
+            &self.base.workspace,
+            &patterns,
+            &self.base.ignore_patterns,
+        )
+    }
+
+    async fn run_all(&self, tx: mpsc::UnboundedSender<TestEvent>) -> Result<()> {
+        let (args, cwd) = self.run_all_args();
+        self.spawn_and_stream(&args, tx, false, &cwd).await
+    }
+
+    async fn run_files(
+        &self,
+        files: &[PathBuf],
+        tx: mpsc::UnboundedSender<TestEvent>,
+    ) -> Result<()> {
+        let (args, cwd) = self.file_args(files);
+        self.spawn_and_stream(&args, tx, false, &cwd).await
+    }
+
+    async fn run_file(&self, file: &Path, tx: mpsc::UnboundedSender<TestEvent>) -> Result<()> {
+        let (args, cwd) = self.file_args(&[file.to_path_buf()]);
+        self.spawn_and_stream(&args, tx, false, &cwd).await
+    }
+
+    async fn run_test(
+        &self,
+        file: &Path,
+        test_name: &str,
+        tx: mpsc::UnboundedSender<TestEvent>,
+    ) -> Result<()> {
+        let (mut args, cwd) = self.file_args(&[file.to_path_buf()]);
+        args.push("-t".to_string());
+        args.push(escape_test_name(test_name));
+        self.spawn_and_stream(&args, tx, false, &cwd).await
+    }
+
+    async fn run_tests(
+        &self,
+        files: &[PathBuf],
+        names: &[String],
+        tx: mpsc::UnboundedSender<TestEvent>,
+    ) -> Result<()> {
+        let (mut args, cwd) = self.file_args(files);
+        args.push("-t".to_string());
+        args.push(full_names_pattern(names, &[]));
+        self.spawn_and_stream(&args, tx, false, &cwd).await
+    }
+
+    async fn update_snapshots(
+        &self,
+        files: &[PathBuf],
+        test_name: Option<&str>,
+        tx: mpsc::UnboundedSender<TestEvent>,
+    ) -> Result<()> {
+        let (mut args, cwd) = self.file_args(files);
+        if let Some(name) = test_name {
+            args.push("-t".to_string());
+            args.push(escape_test_name(name));
+        }
+        // `--ci` (set automatically on CI machines) refuses to write new snapshots.
+        args.push("--ci=false".to_string());
+        args.push("--updateSnapshot".to_string());
+        self.spawn_and_stream(&args, tx, false, &cwd).await
+    }
+
+    async fn run_file_watch(
+        &self,
+        file: &Path,
+        tx: mpsc::UnboundedSender<TestEvent>,
+    ) -> Result<()> {
+        let (args, cwd) = self.file_args(&[file.to_path_buf()]);
+        self.spawn_and_stream(&args, tx, true, &cwd).await
+    }
+
+    async fn run_test_watch(
+        &self,
+        file: &Path,
+        test_name: &str,
+        tx: mpsc::UnboundedSender<TestEvent>,
+    ) -> Result<()> {
+        let (mut args, cwd) = self.file_args(&[file.to_path_buf()]);
+        args.push("-t".to_string());
+        args.push(escape_test_name(test_name));
+        self.spawn_and_stream(&args, tx, true, &cwd).await
+    }
+
+    async fn run_all_watch(&self, tx: mpsc::UnboundedSender<TestEvent>) -> Result<()> {
+        let (args, cwd) = self.run_all_args();
+        self.spawn_and_stream(&args, tx, true, &cwd).await
+    }
+
+    fn set_coverage(&self, enabled: bool) {
+        self.coverage.store(enabled, Ordering::Relaxed);
+    }
+
+    fn name(&self) -> &str {
+        "Jest"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // ── is_jest_project ─────────────────────────────────────────────────────
+
+    #[test]
+    fn detects_jest_config_file() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("jest.config.ts"), "export default {}").unwrap();
+        assert!(is_jest_project(dir.path()));
+    }
+
+    #[test]
+    fn detects_package_json_jest_key() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("package.json"),
+            r#"{ "name": "app", "jest": { "testEnvironment": "node" } }"#,
+        )
+        .unwrap();
+        assert!(is_jest_project(dir.path()));
+    }
+
+    #[test]
+    fn package_json_without_jest_key_is_not_jest() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("package.json"),
+            r#"{ "name": "app", "devDependencies": { "vitest": "^3" } }"#,
+        )
+        .unwrap();
+        assert!(!is_jest_project(dir.path()));
+    }
+
+    #[test]
+    fn empty_directory_is_not_jest() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(!is_jest_project(dir.path()));
+    }
+
+    // ── find_config_for_file ────────────────────────────────────────────────
+
+    #[test]
+    fn config_for_file_prefers_innermost_project() {
+        let dir = tempfile::tempdir().unwrap();
+        let app = dir.path().join("apps/todos");
+        std::fs::create_dir_all(app.join("src")).unwrap();
+        std::fs::write(dir.path().join("jest.config.ts"), "").unwrap();
+        std::fs::write(app.join("jest.config.ts"), "").unwrap();
+
+        let runner = JestRunner::new(dir.path().to_path_buf(), vec![], vec![]);
+        let config = runner.find_config_for_file(&app.join("src/app.spec.ts"));
+        assert_eq!(config, Some(app.join("jest.config.ts")));
+    }
+
+    // ── run_all_args ────────────────────────────────────────────────────────
+
+    #[test]
+    fn several_projects_run_together_from_the_workspace() {
+        let dir = tempfile::tempdir().unwrap();
+        let web = dir.path().join("apps/web");
+        let api = dir.path().join("apps/api");
+        for project in [&web, &api] {
+            std::fs::create_dir_all(project).unwrap();
+            std::fs::write(project.join("jest.config.ts"), "").unwrap();
+        }
+
+        let runner = JestRunner::new(
+            dir.path().to_path_buf(),
+            vec![web.clone(), api.clone()],
+            vec![],
+        );
+        let (args, cwd) = runner.run_all_args();
+        assert_eq!(cwd, dir.path());
+        assert_eq!(args[0], "--projects");
+        assert!(args.contains(&web.join("jest.config.ts").to_string_lossy().to_string()));
+        assert!(args.contains(&api.join("jest.config.ts").to_string_lossy().to_string()));
+
+        let runner = JestRunner::new(dir.path().to_path_buf(), vec![web.clone()], vec![]);
+        let (args, cwd) = runner.run_all_args();
+        assert_eq!(cwd, web);
+        assert_eq!(args[0], "--config");
+    }
+}
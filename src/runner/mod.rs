@@ -1,18 +1,32 @@
+pub mod bun;
+pub mod cargo;
+pub mod jest;
+pub mod node_test;
+pub mod playwright;
+pub mod pytest;
 pub mod vitest;
 
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
 use std::sync::Arc;
+use std::time::{Duration, SystemTime};
 
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, bail};
 use async_trait::async_trait;
+use serde::Deserialize;
 use tokio::sync::mpsc;
 
 use crate::app::TestEvent;
 
+use vitest::{LogFile, open_log_file, write_log};
+
 /// A discovered test file before any tests have been run.
 #[derive(Debug, Clone)]
 pub struct DiscoveredFile {
     pub path: PathBuf,
+    /// The named project the file belongs to and its directory, for runners
+    /// whose projects aren't just directories (a cargo package).
+    pub project: Option<(String, PathBuf)>,
 }
 
 /// Trait for framework-specific test runner adapters.
@@ -43,6 +57,24 @@ pub trait TestRunner: Send + Sync {
         Ok(())
     }
 
+    /// Run only the tests in `files` whose full name (suite and test names
+    /// joined with ` > `) is one of `names`.
+    async fn run_tests(
+        &self,
+        files: &[PathBuf],
+        names: &[String],
+        tx: mpsc::UnboundedSender<TestEvent>,
+    ) -> Result<()>;
+
+    /// Re-run `files` with snapshot updating enabled, rewriting snapshots that no
+    /// longer match. When `test_name` is given only that test or suite runs.
+    async fn update_snapshots(
+        &self,
+        files: &[PathBuf],
+        test_name: Option<&str>,
+        tx: mpsc::UnboundedSender<TestEvent>,
+    ) -> Result<()>;
+
     /// Run all tests in watch mode (re-runs on file changes).
     /// The process stays alive until the task is aborted.
     async fn run_all_watch(&self, tx: mpsc::UnboundedSender<TestEvent>) -> Result<()>;
@@ -59,22 +91,388 @@ pub trait TestRunner: Send + Sync {
         tx: mpsc::UnboundedSender<TestEvent>,
     ) -> Result<()>;
 
+    /// Collect coverage on subsequent runs, reported through `TestEvent::Coverage`.
+    fn set_coverage(&self, enabled: bool);
+
     /// Display name for this runner (e.g., "Vitest").
     #[allow(dead_code)]
     fn name(&self) -> &str;
 }
 
+/// A test framework lens can drive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RunnerKind {
+    Vitest,
+    Jest,
+    Playwright,
+    /// Node's built-in test runner, `node --test`.
+    #[serde(rename = "node")]
+    NodeTest,
+    Bun,
+    Cargo,
+    Pytest,
+}
+
+impl FromStr for RunnerKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "vitest" => Ok(Self::Vitest),
+            "jest" => Ok(Self::Jest),
+            "playwright" => Ok(Self::Playwright),
+            "node" => Ok(Self::NodeTest),
+            "bun" => Ok(Self::Bun),
+            "cargo" => Ok(Self::Cargo),
+            "pytest" => Ok(Self::Pytest),
+            other => bail!(
+                "unknown runner '{other}' \
+                 (expected vitest, jest, playwright, node, bun, cargo or pytest)"
+            ),
+        }
+    }
+}
+
 /// Detect and construct the appropriate runner for the given workspace.
+///
+/// `project_roots` narrows configs and runs to those directories; when empty the
+/// whole workspace is searched. Unless `kind` forces one, the first of these
+/// rules that holds picks the runner:
+///
+/// 1. Jest, when any search root has a `jest.config.*` or a `jest` key in its
+///    `package.json`. One root is enough because a project without tests of
+///    its own (common among Nx projects) has no Jest config either.
+/// 2. Playwright, when every search root has a `playwright.config.*` and no
+///    Vite or Vitest config.
+/// 3. node's test runner, when every search root's `test` script runs
+///    `node --test`.
+/// 4. Bun, when every search root has a `bunfig.toml` or a Bun lockfile and
+///    doesn't use Vitest.
+/// 5. cargo, when every search root has a `Cargo.toml` and no `package.json`.
+/// 6. pytest, when every search root configures pytest and has no
+///    `package.json`.
+/// 7. Vitest otherwise.
 pub fn detect(
     workspace: PathBuf,
-    project_root: Option<PathBuf>,
+    project_roots: Vec<PathBuf>,
     ignore_patterns: Vec<String>,
+    kind: Option<RunnerKind>,
 ) -> Arc<dyn TestRunner> {
-    Arc::new(vitest::VitestRunner::new(
-        workspace,
-        project_root,
-        ignore_patterns,
-    ))
+    let kind = kind.unwrap_or_else(|| {
+        let roots = if project_roots.is_empty() {
+            std::slice::from_ref(&workspace)
+        } else {
+            project_roots.as_slice()
+        };
+        if roots.iter().any(|root| jest::is_jest_project(root)) {
+            RunnerKind::Jest
+        } else if roots
+            .iter()
+            .all(|root| playwright::is_playwright_project(root))
+        {
+            RunnerKind::Playwright
+        } else if roots
+            .iter()
+            .all(|root| node_test::is_node_test_project(root))
+        {
+            RunnerKind::NodeTest
+        } else if roots.iter().all(|root| bun::is_bun_project(root)) {
+            RunnerKind::Bun
+        } else if roots.iter().all(|root| cargo::is_cargo_project(root)) {
+            RunnerKind::Cargo
+        } else if roots.iter().all(|root| pytest::is_pytest_project(root)) {
+            RunnerKind::Pytest
+        } else {
+            RunnerKind::Vitest
+        }
+    });
+    match kind {
+        RunnerKind::Bun => Arc::new(bun::BunRunner::new(
+            workspace,
+            project_roots,
+            ignore_patterns,
+        )),
+        RunnerKind::Cargo => Arc::new(cargo::CargoRunner::new(
+            workspace,
+            project_roots,
+            ignore_patterns,
+        )),
+        RunnerKind::Jest => Arc::new(jest::JestRunner::new(
+            workspace,
+            project_roots,
+            ignore_patterns,
+        )),
+        RunnerKind::NodeTest => Arc::new(node_test::NodeTestRunner::new(
+            workspace,
+            project_roots,
+            ignore_patterns,
+        )),
+        RunnerKind::Playwright => Arc::new(playwright::PlaywrightRunner::new(
+            workspace,
+            project_roots,
+            ignore_patterns,
+        )),
+        RunnerKind::Pytest => Arc::new(pytest::PytestRunner::new(
+            workspace,
+            project_roots,
+            ignore_patterns,
+        )),
+        RunnerKind::Vitest => Arc::new(vitest::VitestRunner::new(
+            workspace,
+            project_roots,
+            ignore_patterns,
+        )),
+    }
+}
+
+/// Escape regex metacharacters and turn lens's ` > ` nesting separator back
+/// into the space both Vitest and Jest use to join a test's full name.
+fn escape_test_name(name: &str) -> String {
+    let mut pattern = String::with_capacity(name.len());
+    for c in name.replace(" > ", " ").chars() {
+        if "\\^$.|?*+()[]{}".contains(c) {
+            pattern.push('\\');
+        }
+        pattern.push(c);
+    }
+    pattern
+}
+
+/// A `-t` pattern matching exactly the tests with these full names. Runners
+/// that put the test file's name (see [`file_name_prefixes`]) and a space in
+/// front of a full name pass the names it can have as `file_names`; the full
+/// name must follow one of them or start the string.
+fn full_names_pattern(names: &[String], file_names: &[String]) -> String {
+    let alternatives: Vec<String> = names.iter().map(|n| escape_test_name(n)).collect();
+    let alternatives = alternatives.join("|");
+    if file_names.is_empty() {
+        return format!("^(?:{alternatives})$");
+    }
+    let files: Vec<String> = file_names.iter().map(|f| escape_test_name(f)).collect();
+    format!("^(?:(?:{})? )?(?:{alternatives})$", files.join("|"))
+}
+
+/// The names `files` can go by relative to a runner's root, which is the
+/// workspace or a directory inside it: each file's workspace-relative path
+/// and every shorter trailing part of it, with `/` separators.
+fn file_name_prefixes(workspace: &Path, files: &[PathBuf]) -> Vec<String> {
+    let mut names = Vec::new();
+    for file in files {
+        let relative = file.strip_prefix(workspace).unwrap_or(file);
+        let parts: Vec<String> = relative
+            .components()
+            .filter_map(|c| match c {
+                std::path::Component::Normal(part) => Some(part.to_string_lossy().into_owned()),
+                _ => None,
+            })
+            .collect();
+        for start in 0..parts.len() {
+            let name = parts[start..].join("/");
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+    }
+    names
+}
+
+/// The command line `cmd` runs, quoted for a shell, with the environment
+/// variables it sets in front.
+fn command_line(cmd: &tokio::process::Command) -> String {
+    let cmd = cmd.as_std();
+    let env = cmd.get_envs().filter_map(|(key, value)| {
+        let value = shell_words::quote(&value?.to_string_lossy()).into_owned();
+        Some(format!("{}={value}", key.to_string_lossy()))
+    });
+    let args = std::iter::once(cmd.get_program())
+        .chain(cmd.get_args())
+        .map(|arg| shell_words::quote(&arg.to_string_lossy()).into_owned());
+    env.chain(args).collect::<Vec<_>>().join(" ")
+}
+
+/// Glob `patterns` anywhere under `root`, skipping `node_modules`, duplicates and
+/// files matching `ignore` (matched against the path relative to `workspace`).
+fn discover_files(
+    root: &Path,
+    workspace: &Path,
+    patterns: &[&str],
+    ignore: &[glob::Pattern],
+) -> Result<Vec<DiscoveredFile>> {
+    let mut files = Vec::new();
+    for suffix in patterns {
+        let pattern = root.join("**/").join(suffix).to_string_lossy().to_string();
+        for entry in glob::glob(&pattern)?.flatten() {
+            let rel = entry.strip_prefix(workspace).unwrap_or(&entry);
+            let rel_str = rel.to_string_lossy();
+            if entry.to_string_lossy().contains("node_modules")
+                || files.iter().any(|f: &DiscoveredFile| f.path == entry)
+                || ignore.iter().any(|p| p.matches(&rel_str))
+            {
+                continue;
+            }
+            files.push(DiscoveredFile {
+                path: entry,
+                project: None,
+            });
+        }
+    }
+    Ok(files)
+}
+
+/// What every runner is set up with: the workspace, the roots its tests are
+/// searched under, the files discovery skips and the debug log.
+struct RunnerBase {
+    workspace: PathBuf,
+    /// Root directories to search for configs and test files.
+    /// Defaults to the workspace, but can be narrowed to selected projects.
+    search_roots: Vec<PathBuf>,
+    log_file: Option<LogFile>,
+    /// Compiled glob patterns for files to skip during discovery.
+    ignore_patterns: Vec<glob::Pattern>,
+}
+
+impl RunnerBase {
+    fn new(workspace: PathBuf, project_roots: Vec<PathBuf>, ignore_patterns: Vec<String>) -> Self {
+        let search_roots = if project_roots.is_empty() {
+            vec![workspace.clone()]
+        } else {
+            project_roots
+        };
+        let ignore_patterns = ignore_patterns
+            .iter()
+            .filter_map(|p| glob::Pattern::new(p).ok())
+            .collect();
+        Self {
+            workspace,
+            search_roots,
+            log_file: open_log_file(),
+            ignore_patterns,
+        }
+    }
+
+    fn log(&self, msg: &str) {
+        if let Some(ref lf) = self.log_file {
+            write_log(lf, msg);
+        }
+    }
+
+    /// Working directory for runs spanning the search roots: the single
+    /// selected project, or the workspace when several are selected.
+    fn default_cwd(&self) -> PathBuf {
+        match self.search_roots.as_slice() {
+            [root] => root.clone(),
+            _ => self.workspace.clone(),
+        }
+    }
+
+    /// Files to run everything in, for runners that only look for tests under
+    /// their working directory: several selected projects are listed out.
+    async fn all_files(&self, runner: &dyn TestRunner) -> Result<Vec<PathBuf>> {
+        if let [_] = self.search_roots.as_slice() {
+            return Ok(Vec::new());
+        }
+        let mut files = Vec::new();
+        for root in &self.search_roots {
+            files.extend(runner.discover(root).await?.into_iter().map(|f| f.path));
+        }
+        Ok(files)
+    }
+}
+
+/// A project with one of these runs its tests through Vitest.
+const VITE_CONFIG_PREFIXES: [&str; 2] = ["vite.config.", "vitest.config."];
+
+/// Whether `root` has a Vite or Vitest config, which makes Vitest its runner
+/// for unit tests over Bun's or Playwright's.
+fn has_vite_config(root: &Path) -> bool {
+    std::fs::read_dir(root).is_ok_and(|entries| {
+        entries.flatten().any(|entry| {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            VITE_CONFIG_PREFIXES.iter().any(|p| name.starts_with(p))
+        })
+    })
+}
+
+/// How often [`rerun_on_change`] looks for changed files.
+const WATCH_POLL: Duration = Duration::from_millis(500);
+
+/// Directories [`rerun_on_change`] doesn't look in: dependencies, build output
+/// and the runners' own result folders, which every run rewrites.
+const WATCH_SKIP_DIRS: [&str; 6] = [
+    "node_modules",
+    "target",
+    "coverage",
+    "test-results",
+    "playwright-report",
+    "__pycache__",
+];
+
+/// Watch mode for runners without one of their own: call `run`, then call it
+/// again each time a file under `roots` with one of `extensions` is added,
+/// removed or modified. Only returns if a run fails; the task is aborted to
+/// stop watching.
+async fn rerun_on_change<F, Fut>(
+    roots: &[PathBuf],
+    extensions: &'static [&'static str],
+    mut run: F,
+) -> Result<()>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<()>>,
+{
+    let mut last = snapshot_tree(roots.to_vec(), extensions).await;
+    loop {
+        run().await?;
+        loop {
+            tokio::time::sleep(WATCH_POLL).await;
+            let current = snapshot_tree(roots.to_vec(), extensions).await;
+            if current != last {
+                last = current;
+                break;
+            }
+        }
+    }
+}
+
+/// Count and newest modification time of the files under `roots` with one of
+/// `extensions`, which changes whenever one is added, removed or saved. Other
+/// files aren't stat'ed.
+async fn snapshot_tree(
+    roots: Vec<PathBuf>,
+    extensions: &'static [&'static str],
+) -> (usize, Option<SystemTime>) {
+    tokio::task::spawn_blocking(move || {
+        let mut snapshot = (0, None);
+        let mut dirs = roots;
+        while let Some(dir) = dirs.pop() {
+            let Ok(entries) = std::fs::read_dir(&dir) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let name = entry.file_name();
+                let name = name.to_string_lossy();
+                let Ok(file_type) = entry.file_type() else {
+                    continue;
+                };
+                if file_type.is_dir() {
+                    if !name.starts_with('.') && !WATCH_SKIP_DIRS.contains(&name.as_ref()) {
+                        dirs.push(entry.path());
+                    }
+                } else if let Some((_, extension)) = name.rsplit_once('.')
+                    && extensions.contains(&extension)
+                    && let Ok(modified) = entry.metadata().and_then(|m| m.modified())
+                {
+                    snapshot.0 += 1;
+                    snapshot.1 = snapshot.1.max(Some(modified));
+                }
+            }
+        }
+        snapshot
+    })
+    .await
+    .unwrap_or_default()
 }
 
 /// Resolve an Nx project name to its root directory (relative to workspace).
@@ -99,3 +497,66 @@ pub fn resolve_nx_project(workspace: &Path, name: &str) -> Result<PathBuf> {
 
     Ok(workspace.join(root))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn command_line_quotes_arguments_and_shows_env() {
+        let mut cmd = tokio::process::Command::new("npx");
+        cmd.args(["vitest", "run", "-t", "adds two"])
+            .env("LENS_SERVER", "1");
+        assert_eq!(
+            command_line(&cmd),
+            "LENS_SERVER=1 npx vitest run -t 'adds two'"
+        );
+    }
+
+    #[tokio::test]
+    async fn tree_snapshot_sees_new_files_but_not_dependencies() {
+        let dir = tempfile::tempdir().unwrap();
+        let roots = vec![dir.path().to_path_buf()];
+        std::fs::write(dir.path().join("a.spec.ts"), "").unwrap();
+        let before = snapshot_tree(roots.clone(), &["ts"]).await;
+        assert_eq!(before.0, 1);
+
+        std::fs::create_dir(dir.path().join("node_modules")).unwrap();
+        std::fs::write(dir.path().join("node_modules/dep.ts"), "").unwrap();
+        std::fs::write(dir.path().join("notes.md"), "").unwrap();
+        assert_eq!(snapshot_tree(roots.clone(), &["ts"]).await, before);
+
+        std::fs::write(dir.path().join("b.spec.ts"), "").unwrap();
+        assert_eq!(snapshot_tree(roots, &["ts"]).await.0, 2);
+    }
+
+    #[test]
+    fn escape_test_name_escapes_regex_characters() {
+        assert_eq!(escape_test_name("adds (1 + 2)"), r"adds \(1 \+ 2\)");
+    }
+
+    #[test]
+    fn escape_test_name_joins_suite_path_with_spaces() {
+        assert_eq!(escape_test_name("math > adds"), "math adds");
+    }
+
+    #[test]
+    fn full_names_pattern_anchors_each_alternative() {
+        let names = ["math > adds".to_string(), "a.b".to_string()];
+        assert_eq!(full_names_pattern(&names, &[]), r"^(?:math adds|a\.b)$");
+    }
+
+    #[test]
+    fn full_names_pattern_doesn_t_match_a_nested_test_by_its_tail() {
+        // `adds` must not also run `math > adds`: only the file's name may
+        // come before a top-level test, never a suite's.
+        let names = ["adds".to_string(), "math > adds".to_string()];
+        let files =
+            file_name_prefixes(Path::new("/ws"), &[PathBuf::from("/ws/apps/web/a.test.ts")]);
+        assert_eq!(files, ["apps/web/a.test.ts", "web/a.test.ts", "a.test.ts"]);
+        assert_eq!(
+            full_names_pattern(&names, &files),
+            r"^(?:(?:apps/web/a\.test\.ts|web/a\.test\.ts|a\.test\.ts)? )?(?:adds|math adds)$"
+        );
+    }
+}
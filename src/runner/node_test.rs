@@ -0,0 +1,390 @@
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use tokio::process::Command;
+use tokio::sync::mpsc;
+
+use crate::app::TestEvent;
+
+use super::vitest::{ChildGuard, stream_output};
+use super::{
+    DiscoveredFile, RunnerBase, TestRunner, command_line, discover_files, full_names_pattern,
+};
+
+/// Node's default test file patterns, for each of `.js`, `.mjs` and `.cjs`.
+const PATTERNS: [&str; 18] = [
+    "*.test.js",
+    "*.test.mjs",
+    "*.test.cjs",
+    "*-test.js",
+    "*-test.mjs",
+    "*-test.cjs",
+    "*_test.js",
+    "*_test.mjs",
+    "*_test.cjs",
+    "test-*.js",
+    "test-*.mjs",
+    "test-*.cjs",
+    "test.js",
+    "test.mjs",
+    "test.cjs",
+    "test/**/*.js",
+    "test/**/*.mjs",
+    "test/**/*.cjs",
+];
+
+const REPORTER_SOURCE: &str = include_str!("../../reporters/node-test-reporter.mjs");
+
+/// Returns true if the `test` script in `root`'s `package.json` runs `node --test`.
+pub fn is_node_test_project(root: &Path) -> bool {
+    std::fs::read_to_string(root.join("package.json"))
+        .ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+        .and_then(|pkg| pkg["scripts"]["test"].as_str().map(str::to_owned))
+        .is_some_and(|script| script.contains("node --test"))
+}
+
+/// A `--test-name-pattern` matching tests by their full names. Node 22 and
+/// later match it against a test's own name and against that name after its
+/// ancestors', joined with spaces, so a full name picks only that test, and a
+/// matching suite runs everything inside it. Older versions only try own
+/// names, which leaves a nested test unmatched rather than running its
+/// namesakes.
+fn name_pattern(names: &[String]) -> String {
+    full_names_pattern(names, &[])
+}
+
+/// Adapter for node's built-in test runner. Spawns `node --test` with a custom
+/// reporter emitting the same NDJSON events as the Vitest reporter.
+pub struct NodeTestRunner {
+    base: RunnerBase,
+    /// Collect coverage on every run.
+    coverage: AtomicBool,
+}
+
+impl NodeTestRunner {
+    pub fn new(
+        workspace: PathBuf,
+        project_roots: Vec<PathBuf>,
+        ignore_patterns: Vec<String>,
+    ) -> Self {
+        Self {
+            base: RunnerBase::new(workspace, project_roots, ignore_patterns),
+            coverage: AtomicBool::new(false),
+        }
+    }
+
+    /// Write the embedded reporter to a temp file and return its path.
+    fn write_reporter(&self) -> Result<tempfile::NamedTempFile> {
+        let mut file = tempfile::Builder::new()
+            .prefix("lens-node-test-reporter-")
+            .suffix(".mjs")
+            .tempfile()
+            .context("failed to create temp reporter file")?;
+
+        use std::io::Write;
+        file.write_all(REPORTER_SOURCE.as_bytes())
+            .context("failed to write reporter to temp file")?;
+
+        Ok(file)
+    }
+
+    /// Spawn `node --test` with the given args and stream NDJSON events from stdout.
+    ///
+    /// When `watch` is true, passes `--watch` so node stays alive and re-runs
+    /// on file changes. Non-zero exit is not treated as an error in watch mode
+    /// (the process is killed on toggle-off).
+    async fn spawn_and_stream(
+        &self,
+        args: &[String],
+        files: &[PathBuf],
+        tx: mpsc::UnboundedSender<TestEvent>,
+        watch: bool,
+    ) -> Result<()> {
+        let reporter_file = self.write_reporter()?;
+        let cwd = self.base.default_cwd();
+
+        let mut cmd = Command::new("node");
+        cmd.arg("--test");
+        if watch {
+            cmd.arg("--watch");
+        }
+        if self.coverage.load(Ordering::Relaxed) {
+            cmd.arg("--experimental-test-coverage");
+        }
+        cmd.arg(format!(
+            "--test-reporter={}",
+            reporter_file.path().to_string_lossy()
+        ))
+        .arg("--test-reporter-destination=stdout")
+        .args(args)
+        .args(files);
+
+        // Log the full command for debugging (LENS_DEBUG=path)
+        self.base.log(&format!("[cmd] {:?}", cmd.as_std()));
+        self.base.log(&format!("[cwd] {:?}", cwd));
+        let _ = tx.send(TestEvent::CommandStarted {
+            command: command_line(&cmd),
+            cwd: cwd.clone(),
+        });
+
+        // Put the child in its own process group so killing it (via ChildGuard) also
+        // takes out the per-file processes node spawns (prevents orphans).
+        #[cfg(unix)]
+        {
+            use std::os::unix::process::CommandExt;
+            cmd.as_std_mut().process_group(0);
+        }
+
+        let mut child = cmd
+            .current_dir(&cwd)
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped())
+            .spawn()
+            .context("failed to spawn node")?;
+
+        drop(child.stdin.take());
+        let stdout = child.stdout.take().context("missing stdout")?;
+        let stderr = child.stderr.take().context("missing stderr")?;
+
+        let mut child_guard = ChildGuard::new(child);
+
+        stream_output(stdout, stderr, &tx, self.base.log_file.clone()).await;
+
+        // Keep the temp file alive until node exits
+        drop(reporter_file);
+
+        if !watch && let Some(ref mut child) = child_guard.child {
+            let status = child.wait().await.context("failed to wait for node")?;
+            // Node exits with 1 when tests fail; that's already visible in the tree.
+            if !status.success() && status.code() != Some(1) {
+                let _ = tx.send(TestEvent::Error {
+                    message: format!(
+                        "node --test exited with code {}",
+                        status.code().unwrap_or(-1)
+                    ),
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl TestRunner for NodeTestRunner {
+    async fn discover(&self, workspace: &Path) -> Result<Vec<DiscoveredFile>> {
+        discover_files(
+            workspace,
+            &self.base.workspace,
+            &PATTERNS,
+            &self.base.ignore_patterns,
+        )
+    }
+
+    async fn run_all(&self, tx: mpsc::UnboundedSender<TestEvent>) -> Result<()> {
+        let files = self.base.all_files(self).await?;
+        self.spawn_and_stream(&[], &files, tx, false).await
+    }
+
+    async fn run_files(
+        &self,
+        files: &[PathBuf],
+        tx: mpsc::UnboundedSender<TestEvent>,
+    ) -> Result<()> {
+        self.spawn_and_stream(&[], files, tx, false).await
+    }
+
+    async fn run_file(&self, file: &Path, tx: mpsc::UnboundedSender<TestEvent>) -> Result<()> {
+        self.spawn_and_stream(&[], &[file.to_path_buf()], tx, false)
+            .await
+    }
+
+    async fn run_test(
+        &self,
+        file: &Path,
+        test_name: &str,
+        tx: mpsc::UnboundedSender<TestEvent>,
+    ) -> Result<()> {
+        let args = [format!(
+            "--test-name-pattern={}",
+            name_pattern(&[test_name.to_string()])
+        )];
+        self.spawn_and_stream(&args, &[file.to_path_buf()], tx, false)
+            .await
+    }
+
+    async fn run_tests(
+        &self,
+        files: &[PathBuf],
+        names: &[String],
+        tx: mpsc::UnboundedSender<TestEvent>,
+    ) -> Result<()> {
+        let args = [format!("--test-name-pattern={}", name_pattern(names))];
+        self.spawn_and_stream(&args, files, tx, false).await
+    }
+
+    async fn update_snapshots(
+        &self,
+        files: &[PathBuf],
+        test_name: Option<&str>,
+        tx: mpsc::UnboundedSender<TestEvent>,
+    ) -> Result<()> {
+        // Snapshot testing needs node 22.3 or later; older versions reject the flag.
+        let mut args = vec!["--test-update-snapshots".to_string()];
+        if let Some(name) = test_name {
+            args.push(format!(
+                "--test-name-pattern={}",
+                name_pattern(&[name.to_string()])
+            ));
+        }
+        self.spawn_and_stream(&args, files, tx, false).await
+    }
+
+    async fn run_file_watch(
+        &self,
+        file: &Path,
+        tx: mpsc::UnboundedSender<TestEvent>,
+    ) -> Result<()> {
+        self.spawn_and_stream(&[], &[file.to_path_buf()], tx, true)
+            .await
+    }
+
+    async fn run_test_watch(
+        &self,
+        file: &Path,
+        test_name: &str,
+        tx: mpsc::UnboundedSender<TestEvent>,
+    ) -> Result<()> {
+        let args = [format!(
+            "--test-name-pattern={}",
+            name_pattern(&[test_name.to_string()])
+        )];
+        self.spawn_and_stream(&args, &[file.to_path_buf()], tx, true)
+            .await
+    }
+
+    async fn run_all_watch(&self, tx: mpsc::UnboundedSender<TestEvent>) -> Result<()> {
+        let files = self.base.all_files(self).await?;
+        self.spawn_and_stream(&[], &files, tx, true).await
+    }
+
+    fn set_coverage(&self, enabled: bool) {
+        self.coverage.store(enabled, Ordering::Relaxed);
+    }
+
+    fn name(&self) -> &str {
+        "node:test"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_node_test_script() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("package.json"),
+            r#"{ "scripts": { "test": "node --test --test-concurrency=1" } }"#,
+        )
+        .unwrap();
+        assert!(is_node_test_project(dir.path()));
+
+        std::fs::write(
+            dir.path().join("package.json"),
+            r#"{ "scripts": { "test": "vitest run" } }"#,
+        )
+        .unwrap();
+        assert!(!is_node_test_project(dir.path()));
+    }
+
+    #[tokio::test]
+    async fn discovers_node_default_patterns() {
+        let dir = tempfile::tempdir().unwrap();
+        for file in [
+            "src/math.test.mjs",
+            "src/parse_test.cjs",
+            "test/helpers/setup.js",
+            "src/math.ts",
+            "node_modules/dep/test/a.js",
+        ] {
+            let path = dir.path().join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, "").unwrap();
+        }
+
+        let runner = NodeTestRunner::new(dir.path().to_path_buf(), vec![], vec![]);
+        let mut found: Vec<PathBuf> = runner
+            .discover(dir.path())
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|f| f.path.strip_prefix(dir.path()).unwrap().to_path_buf())
+            .collect();
+        found.sort();
+        assert_eq!(
+            found,
+            [
+                PathBuf::from("src/math.test.mjs"),
+                PathBuf::from("src/parse_test.cjs"),
+                PathBuf::from("test/helpers/setup.js"),
+            ]
+        );
+    }
+
+    #[test]
+    fn name_pattern_matches_the_full_name() {
+        let names = ["math > adds (1 + 2)".to_string(), "parses".to_string()];
+        assert_eq!(name_pattern(&names), r"^(?:math adds \(1 \+ 2\)|parses)$");
+    }
+
+    #[tokio::test]
+    async fn a_test_runs_without_its_namesakes_in_other_suites() {
+        let major = std::process::Command::new("node")
+            .arg("--version")
+            .output()
+            .ok()
+            .and_then(|output| {
+                let version = String::from_utf8(output.stdout).ok()?;
+                version
+                    .trim()
+                    .strip_prefix('v')?
+                    .split('.')
+                    .next()?
+                    .parse::<u32>()
+                    .ok()
+            });
+        if major.is_none_or(|major| major < 22) {
+            eprintln!("skipping: needs node 22 or later");
+            return;
+        }
+
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("math.test.mjs");
+        std::fs::write(
+            &file,
+            "import { describe, it } from 'node:test';\n\
+             describe('math', () => { it('adds', () => {}); it('subtracts', () => {}); });\n\
+             describe('other', () => { it('adds', () => {}); });\n",
+        )
+        .unwrap();
+
+        let runner = NodeTestRunner::new(dir.path().to_path_buf(), vec![], vec![]);
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        runner.run_test(&file, "math > adds", tx).await.unwrap();
+        let mut passed = Vec::new();
+        while let Ok(event) = rx.try_recv() {
+            if let TestEvent::TestFinished { name, result, .. } = event
+                && result.status == crate::models::TestStatus::Passed
+            {
+                passed.push(name);
+            }
+        }
+        assert_eq!(passed, ["math > adds"]);
+    }
+}
@@ -0,0 +1,507 @@
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use tokio::process::Command;
+use tokio::sync::mpsc;
+
+use crate::app::TestEvent;
+
+use super::vitest::{ChildGuard, SUFFIXES, stream_output};
+use super::{
+    DiscoveredFile, RunnerBase, TestRunner, command_line, discover_files, escape_test_name,
+    file_name_prefixes, has_vite_config, rerun_on_change,
+};
+
+const CONFIG_NAMES: [&str; 5] = [
+    "playwright.config.ts",
+    "playwright.config.js",
+    "playwright.config.mts",
+    "playwright.config.mjs",
+    "playwright.config.cjs",
+];
+
+/// Files whose changes re-run watched tests: scripts, and the JSON they load.
+const WATCH_EXTENSIONS: [&str; 9] = ["ts", "tsx", "mts", "cts", "js", "jsx", "mjs", "cjs", "json"];
+
+const REPORTER_SOURCE: &str = include_str!("../../reporters/playwright-reporter.cjs");
+
+/// Returns true if `root` has a `playwright.config.*` and no Vite or Vitest
+/// config beside it.
+pub fn is_playwright_project(root: &Path) -> bool {
+    !has_vite_config(root) && CONFIG_NAMES.iter().any(|name| root.join(name).is_file())
+}
+
+/// The `testDir` a Playwright config sets as a string literal, resolved
+/// against the config's directory. Configs computing it aren't evaluated.
+fn configured_test_dir(config: &Path) -> Option<PathBuf> {
+    let source = std::fs::read_to_string(config).ok()?;
+    let rest = &source[source.find("testDir")? + "testDir".len()..];
+    let rest = rest.trim_start().strip_prefix(':')?.trim_start();
+    let quote = rest.chars().next().filter(|c| "'\"`".contains(*c))?;
+    let value = &rest[1..];
+    let dir = &value[..value.find(quote)?];
+    Some(config.parent()?.join(dir))
+}
+
+/// A `--grep` pattern for tests named like the tree names them. Playwright
+/// matches against the project, file, describe and test titles joined with
+/// spaces, the project's title empty when it has no name. A name's first
+/// segment is the browser project when there is one, so the file (one of
+/// `file_names`, see [`file_name_prefixes`]) goes after it or before the
+/// whole name. `exact` anchors the end; otherwise a suite's name also picks
+/// the tests inside it.
+fn grep_pattern(names: &[String], file_names: &[String], exact: bool) -> String {
+    let files = if file_names.is_empty() {
+        r"\S+".to_string()
+    } else {
+        let files: Vec<String> = file_names.iter().map(|f| escape_test_name(f)).collect();
+        format!("(?:{})", files.join("|"))
+    };
+    let alternatives: Vec<String> = names
+        .iter()
+        .flat_map(|name| {
+            let after_project = match name.split_once(" > ") {
+                Some((first, rest)) => format!(
+                    "{} {files} {}",
+                    escape_test_name(first),
+                    escape_test_name(rest)
+                ),
+                None => escape_test_name(name),
+            };
+            [after_project, format!("{files} {}", escape_test_name(name))]
+        })
+        .collect();
+    let end = if exact { "$" } else { "(?: |$)" };
+    format!("^\\s*(?:{}){end}", alternatives.join("|"))
+}
+
+/// Playwright adapter that spawns `playwright test` with a custom reporter
+/// emitting the same NDJSON events as the Vitest reporter. Each browser
+/// project becomes a level between a file and its tests.
+///
+/// Playwright has no watch mode of its own, so watching re-runs on file
+/// changes through [`rerun_on_change`].
+pub struct PlaywrightRunner {
+    base: RunnerBase,
+    /// Coverage was asked for; Playwright doesn't collect it, which runs say.
+    coverage: AtomicBool,
+}
+
+impl PlaywrightRunner {
+    pub fn new(
+        workspace: PathBuf,
+        project_roots: Vec<PathBuf>,
+        ignore_patterns: Vec<String>,
+    ) -> Self {
+        Self {
+            base: RunnerBase::new(workspace, project_roots, ignore_patterns),
+            coverage: AtomicBool::new(false),
+        }
+    }
+
+    /// Write the embedded reporter to a temp file and return its path.
+    fn write_reporter(&self) -> Result<tempfile::NamedTempFile> {
+        let mut file = tempfile::Builder::new()
+            .prefix("lens-playwright-reporter-")
+            .suffix(".cjs")
+            .tempfile()
+            .context("failed to create temp reporter file")?;
+
+        use std::io::Write;
+        file.write_all(REPORTER_SOURCE.as_bytes())
+            .context("failed to write reporter to temp file")?;
+
+        Ok(file)
+    }
+
+    /// Find all Playwright config files under the search roots.
+    fn find_configs(&self) -> Vec<PathBuf> {
+        let mut configs = Vec::new();
+        for (root, name) in self
+            .base
+            .search_roots
+            .iter()
+            .flat_map(|root| CONFIG_NAMES.iter().map(move |name| (root, name)))
+        {
+            let pattern = root.join("**/").join(name).to_string_lossy().to_string();
+            if let Ok(entries) = glob::glob(&pattern) {
+                for entry in entries.flatten() {
+                    let path_str = entry.to_string_lossy();
+                    if !path_str.contains("node_modules") && !configs.contains(&entry) {
+                        configs.push(entry);
+                    }
+                }
+            }
+        }
+        configs
+    }
+
+    /// The config of the innermost project containing `file`.
+    fn find_config_for_file(&self, file: &Path) -> Option<PathBuf> {
+        self.find_configs()
+            .into_iter()
+            .filter(|config| config.parent().is_some_and(|dir| file.starts_with(dir)))
+            .max_by_key(|config| config.components().count())
+    }
+
+    /// Where each config's tests live: its `testDir`, or its own directory.
+    /// Without any config, the search roots.
+    fn test_dirs(&self) -> Vec<PathBuf> {
+        let configs = self.find_configs();
+        if configs.is_empty() {
+            return self.base.search_roots.clone();
+        }
+        configs
+            .iter()
+            .filter_map(|config| {
+                configured_test_dir(config).or_else(|| config.parent().map(Path::to_path_buf))
+            })
+            .collect()
+    }
+
+    /// One `(--config args, cwd, files)` run per config the files belong to.
+    /// An empty `files` runs each config's whole suite.
+    fn runs(&self, files: &[PathBuf]) -> Vec<(Vec<String>, PathBuf, Vec<PathBuf>)> {
+        let mut runs: Vec<(Vec<String>, PathBuf, Vec<PathBuf>)> = Vec::new();
+        if files.is_empty() {
+            for config in self.find_configs() {
+                let cwd = config
+                    .parent()
+                    .map_or_else(|| self.base.default_cwd(), Path::to_path_buf);
+                let args = vec!["--config".to_string(), config.to_string_lossy().to_string()];
+                runs.push((args, cwd, Vec::new()));
+            }
+            if runs.is_empty() {
+                runs.push((Vec::new(), self.base.default_cwd(), Vec::new()));
+            }
+            return runs;
+        }
+        for file in files {
+            let (args, cwd) = match self.find_config_for_file(file) {
+                Some(config) => (
+                    vec!["--config".to_string(), config.to_string_lossy().to_string()],
+                    config
+                        .parent()
+                        .map_or_else(|| self.base.default_cwd(), Path::to_path_buf),
+                ),
+                None => (Vec::new(), self.base.default_cwd()),
+            };
+            match runs.iter_mut().find(|run| run.0 == args) {
+                Some(run) => run.2.push(file.clone()),
+                None => runs.push((args, cwd, vec![file.clone()])),
+            }
+        }
+        runs
+    }
+
+    /// Run `files` (or everything, when empty) one config at a time, with
+    /// `extra` args appended to each run.
+    async fn run_each(
+        &self,
+        files: &[PathBuf],
+        extra: &[String],
+        tx: &mpsc::UnboundedSender<TestEvent>,
+    ) -> Result<()> {
+        for (mut args, cwd, run_files) in self.runs(files) {
+            args.extend(extra.iter().cloned());
+            // Playwright takes files as regexes matched against their paths.
+            args.extend(
+                run_files
+                    .iter()
+                    .map(|f| escape_test_name(&f.to_string_lossy())),
+            );
+            self.spawn_and_stream(&args, tx.clone(), &cwd).await?;
+        }
+        Ok(())
+    }
+
+    /// The names `files` can have in Playwright's titles, relative to their
+    /// project's `testDir`.
+    fn file_names<P: AsRef<Path>>(&self, files: &[P]) -> Vec<String> {
+        let files: Vec<PathBuf> = files.iter().map(|f| f.as_ref().to_path_buf()).collect();
+        file_name_prefixes(&self.base.workspace, &files)
+    }
+
+    /// Re-run `files` with `extra` args whenever the tests' sources change.
+    async fn watch(
+        &self,
+        files: &[PathBuf],
+        extra: &[String],
+        tx: mpsc::UnboundedSender<TestEvent>,
+    ) -> Result<()> {
+        rerun_on_change(&self.base.search_roots, &WATCH_EXTENSIONS, || {
+            self.run_each(files, extra, &tx)
+        })
+        .await
+    }
+
+    /// Spawn `playwright test` with the given args and stream NDJSON events
+    /// from stdout.
+    async fn spawn_and_stream(
+        &self,
+        args: &[String],
+        tx: mpsc::UnboundedSender<TestEvent>,
+        cwd: &Path,
+    ) -> Result<()> {
+        let reporter_file = self.write_reporter()?;
+
+        let mut cmd = Command::new("npx");
+        cmd.args(["playwright", "test"])
+            .args(args)
+            .arg("--pass-with-no-tests")
+            .arg(format!(
+                "--reporter={}",
+                reporter_file.path().to_string_lossy()
+            ));
+
+        // Log the full command for debugging (LENS_DEBUG=path)
+        self.base.log(&format!("[cmd] {:?}", cmd.as_std()));
+        self.base.log(&format!("[cwd] {:?}", cwd));
+        let _ = tx.send(TestEvent::CommandStarted {
+            command: command_line(&cmd),
+            cwd: cwd.to_path_buf(),
+        });
+        if self.coverage.load(Ordering::Relaxed) {
+            let _ = tx.send(TestEvent::Output {
+                line: "Coverage isn't collected for Playwright runs.".to_string(),
+            });
+        }
+
+        // Put the child in its own process group so killing it (via ChildGuard) also
+        // takes out the workers and browsers Playwright starts (prevents orphans).
+        #[cfg(unix)]
+        {
+            use std::os::unix::process::CommandExt;
+            cmd.as_std_mut().process_group(0);
+        }
+
+        let mut child = cmd
+            .current_dir(cwd)
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped())
+            .spawn()
+            .context("failed to spawn playwright")?;
+
+        drop(child.stdin.take());
+        let stdout = child.stdout.take().context("missing stdout")?;
+        let stderr = child.stderr.take().context("missing stderr")?;
+
+        let mut child_guard = ChildGuard::new(child);
+
+        stream_output(stdout, stderr, &tx, self.base.log_file.clone()).await;
+
+        // Keep the temp file alive until playwright exits
+        drop(reporter_file);
+
+        if let Some(ref mut child) = child_guard.child {
+            let status = child
+                .wait()
+                .await
+                .context("failed to wait for playwright")?;
+            // Playwright exits with 1 when tests fail; that's already visible in the tree.
+            if !status.success() && status.code() != Some(1) {
+                let _ = tx.send(TestEvent::Error {
+                    message: format!(
+                        "playwright exited with code {}",
+                        status.code().unwrap_or(-1)
+                    ),
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl TestRunner for PlaywrightRunner {
+    async fn discover(&self, _workspace: &Path) -> Result<Vec<DiscoveredFile>> {
+        let mut files: Vec<DiscoveredFile> = Vec::new();
+        for dir in self.test_dirs() {
+            for file in discover_files(
+                &dir,
+                &self.base.workspace,
+                &SUFFIXES,
+                &self.base.ignore_patterns,
+            )? {
+                if !files.iter().any(|f| f.path == file.path) {
+                    files.push(file);
+                }
+            }
+        }
+        Ok(files)
+    }
+
+    async fn run_all(&self, tx: mpsc::UnboundedSender<TestEvent>) -> Result<()> {
+        self.run_each(&[], &[], &tx).await
+    }
+
+    async fn run_files(
+        &self,
+        files: &[PathBuf],
+        tx: mpsc::UnboundedSender<TestEvent>,
+    ) -> Result<()> {
+        self.run_each(files, &[], &tx).await
+    }
+
+    async fn run_file(&self, file: &Path, tx: mpsc::UnboundedSender<TestEvent>) -> Result<()> {
+        self.run_each(&[file.to_path_buf()], &[], &tx).await
+    }
+
+    async fn run_test(
+        &self,
+        file: &Path,
+        test_name: &str,
+        tx: mpsc::UnboundedSender<TestEvent>,
+    ) -> Result<()> {
+        let grep = [
+            "--grep".to_string(),
+            grep_pattern(&[test_name.to_string()], &self.file_names(&[file]), false),
+        ];
+        self.run_each(&[file.to_path_buf()], &grep, &tx).await
+    }
+
+    async fn run_tests(
+        &self,
+        files: &[PathBuf],
+        names: &[String],
+        tx: mpsc::UnboundedSender<TestEvent>,
+    ) -> Result<()> {
+        let grep = [
+            "--grep".to_string(),
+            grep_pattern(names, &self.file_names(files), true),
+        ];
+        self.run_each(files, &grep, &tx).await
+    }
+
+    async fn update_snapshots(
+        &self,
+        files: &[PathBuf],
+        test_name: Option<&str>,
+        tx: mpsc::UnboundedSender<TestEvent>,
+    ) -> Result<()> {
+        let mut extra = vec!["--update-snapshots".to_string()];
+        if let Some(name) = test_name {
+            extra.push("--grep".to_string());
+            extra.push(grep_pattern(
+                &[name.to_string()],
+                &self.file_names(files),
+                false,
+            ));
+        }
+        self.run_each(files, &extra, &tx).await
+    }
+
+    async fn run_file_watch(
+        &self,
+        file: &Path,
+        tx: mpsc::UnboundedSender<TestEvent>,
+    ) -> Result<()> {
+        self.watch(&[file.to_path_buf()], &[], tx).await
+    }
+
+    async fn run_test_watch(
+        &self,
+        file: &Path,
+        test_name: &str,
+        tx: mpsc::UnboundedSender<TestEvent>,
+    ) -> Result<()> {
+        let grep = [
+            "--grep".to_string(),
+            grep_pattern(&[test_name.to_string()], &self.file_names(&[file]), false),
+        ];
+        self.watch(&[file.to_path_buf()], &grep, tx).await
+    }
+
+    async fn run_all_watch(&self, tx: mpsc::UnboundedSender<TestEvent>) -> Result<()> {
+        self.watch(&[], &[], tx).await
+    }
+
+    fn set_coverage(&self, enabled: bool) {
+        self.coverage.store(enabled, Ordering::Relaxed);
+    }
+
+    fn name(&self) -> &str {
+        "Playwright"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_config_without_vite_beside_it() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(!is_playwright_project(dir.path()));
+        std::fs::write(dir.path().join("playwright.config.ts"), "").unwrap();
+        assert!(is_playwright_project(dir.path()));
+        std::fs::write(dir.path().join("vitest.config.ts"), "").unwrap();
+        assert!(!is_playwright_project(dir.path()));
+    }
+
+    #[test]
+    fn reads_literal_test_dir_from_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("playwright.config.ts");
+        std::fs::write(
+            &config,
+            "export default defineConfig({\n  testDir: './e2e',\n  retries: 2,\n});\n",
+        )
+        .unwrap();
+        assert_eq!(configured_test_dir(&config), Some(dir.path().join("./e2e")));
+
+        std::fs::write(
+            &config,
+            "export default { testDir: path.join(__dirname, 'e2e') }",
+        )
+        .unwrap();
+        assert_eq!(configured_test_dir(&config), None);
+    }
+
+    #[test]
+    fn grep_skips_the_file_after_the_project() {
+        let names = ["chromium > login > signs in".to_string()];
+        let files = ["e2e/a.spec.ts".to_string(), "a.spec.ts".to_string()];
+        let pattern = grep_pattern(&names, &files, true);
+        assert_eq!(
+            pattern,
+            r"^\s*(?:chromium (?:e2e/a\.spec\.ts|a\.spec\.ts) login signs in|(?:e2e/a\.spec\.ts|a\.spec\.ts) chromium login signs in)$"
+        );
+        assert_eq!(
+            grep_pattern(
+                &["adds (1 + 2)".to_string()],
+                &["a.spec.ts".to_string()],
+                false
+            ),
+            r"^\s*(?:adds \(1 \+ 2\)|(?:a\.spec\.ts) adds \(1 \+ 2\))(?: |$)"
+        );
+    }
+
+    #[test]
+    fn files_are_grouped_by_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let web = dir.path().join("apps/web-e2e");
+        let admin = dir.path().join("apps/admin-e2e");
+        for project in [&web, &admin] {
+            std::fs::create_dir_all(project).unwrap();
+            std::fs::write(project.join("playwright.config.ts"), "").unwrap();
+        }
+
+        let runner = PlaywrightRunner::new(dir.path().to_path_buf(), vec![], vec![]);
+        let files = [
+            web.join("a.spec.ts"),
+            admin.join("b.spec.ts"),
+            web.join("c.spec.ts"),
+        ];
+        let runs = runner.runs(&files);
+        assert_eq!(runs.len(), 2);
+        assert_eq!(runs[0].1, web);
+        assert_eq!(runs[0].2, [files[0].clone(), files[2].clone()]);
+        assert_eq!(runs[1].1, admin);
+        assert_eq!(runner.runs(&[]).len(), 2);
+    }
+}
@@ -0,0 +1,462 @@
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use tokio::process::Command;
+use tokio::sync::mpsc;
+
+use crate::app::TestEvent;
+
+use super::vitest::{ChildGuard, stream_output};
+use super::{
+    DiscoveredFile, RunnerBase, TestRunner, command_line, discover_files, rerun_on_change,
+};
+
+/// Pytest's default `python_files` patterns.
+const PATTERNS: [&str; 2] = ["test_*.py", "*_test.py"];
+
+/// Files whose changes re-run watched tests: sources and configuration.
+const WATCH_EXTENSIONS: [&str; 4] = ["py", "toml", "ini", "cfg"];
+
+/// Files holding pytest configuration, with the section that marks it when
+/// the file is shared with other tools.
+const CONFIG_FILES: [(&str, Option<&str>); 5] = [
+    ("pytest.ini", None),
+    ("conftest.py", None),
+    ("pyproject.toml", Some("[tool.pytest")),
+    ("setup.cfg", Some("[tool:pytest]")),
+    ("tox.ini", Some("[pytest]")),
+];
+
+/// Module name the plugin is written under; pytest loads it with `-p`.
+const PLUGIN_MODULE: &str = "lens_pytest_plugin";
+
+const PLUGIN_SOURCE: &str = include_str!("../../reporters/pytest-plugin.py");
+
+/// Returns true if `root` configures pytest and has no `package.json`.
+pub fn is_pytest_project(root: &Path) -> bool {
+    if root.join("package.json").is_file() {
+        return false;
+    }
+    CONFIG_FILES.iter().any(|(name, section)| match section {
+        None => root.join(name).is_file(),
+        Some(section) => {
+            std::fs::read_to_string(root.join(name)).is_ok_and(|content| content.contains(section))
+        }
+    })
+}
+
+/// The interpreter to run pytest with: the project's virtualenv when it has
+/// one in `.venv` or `venv`, else whatever `python3` is on the `PATH`.
+fn python(cwd: &Path) -> PathBuf {
+    let candidates = if cfg!(windows) {
+        [".venv/Scripts/python.exe", "venv/Scripts/python.exe"]
+    } else {
+        [".venv/bin/python", "venv/bin/python"]
+    };
+    candidates
+        .iter()
+        .map(|candidate| cwd.join(candidate))
+        .find(|path| path.is_file())
+        .unwrap_or_else(|| PathBuf::from(if cfg!(windows) { "python" } else { "python3" }))
+}
+
+/// Whether `path` is inside a hidden directory, a cache or an installed
+/// package under `root`, where pytest's patterns match files that aren't
+/// the project's tests.
+fn is_vendored(path: &Path, root: &Path) -> bool {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .components()
+        .any(|component| match component {
+            Component::Normal(name) => {
+                let name = name.to_string_lossy();
+                name.starts_with('.') || name == "__pycache__" || name == "site-packages"
+            }
+            _ => false,
+        })
+}
+
+/// Adapter for pytest. Spawns `python -m pytest` with an embedded plugin
+/// emitting the same NDJSON events as the Vitest reporter.
+pub struct PytestRunner {
+    base: RunnerBase,
+    /// Collect coverage on every run.
+    coverage: AtomicBool,
+}
+
+impl PytestRunner {
+    pub fn new(
+        workspace: PathBuf,
+        project_roots: Vec<PathBuf>,
+        ignore_patterns: Vec<String>,
+    ) -> Self {
+        Self {
+            base: RunnerBase::new(workspace, project_roots, ignore_patterns),
+            coverage: AtomicBool::new(false),
+        }
+    }
+
+    /// Write the embedded plugin into a temp directory, to be put on the
+    /// `PYTHONPATH`, and return the directory.
+    fn write_plugin(&self) -> Result<tempfile::TempDir> {
+        let dir = tempfile::Builder::new()
+            .prefix("lens-pytest-plugin-")
+            .tempdir()
+            .context("failed to create temp plugin directory")?;
+        std::fs::write(
+            dir.path().join(format!("{PLUGIN_MODULE}.py")),
+            PLUGIN_SOURCE,
+        )
+        .context("failed to write plugin to temp directory")?;
+        Ok(dir)
+    }
+
+    /// Paths to run everything in: pytest collects from its working directory
+    /// (or its configured `testpaths`) when given none, so only several
+    /// selected projects are listed out.
+    fn all_paths(&self) -> Vec<PathBuf> {
+        match self.base.search_roots.as_slice() {
+            [_] => Vec::new(),
+            roots => roots.to_vec(),
+        }
+    }
+
+    /// Spawn pytest on `paths` and stream NDJSON events from stdout. When
+    /// `names` is given, the plugin deselects every test whose full name isn't
+    /// one of them or inside one of them.
+    async fn spawn_and_stream(
+        &self,
+        paths: &[PathBuf],
+        names: Option<&[String]>,
+        update_snapshots: bool,
+        tx: &mpsc::UnboundedSender<TestEvent>,
+    ) -> Result<()> {
+        let plugin_dir = self.write_plugin()?;
+        let cwd = self.base.default_cwd();
+
+        let mut python_path = std::ffi::OsString::from(plugin_dir.path());
+        if let Some(existing) = std::env::var_os("PYTHONPATH") {
+            python_path = std::env::join_paths(
+                std::iter::once(PathBuf::from(python_path)).chain(std::env::split_paths(&existing)),
+            )
+            .context("failed to build PYTHONPATH")?;
+        }
+
+        let mut cmd = Command::new(python(&cwd));
+        cmd.env("PYTHONPATH", python_path);
+        if let Some(names) = names {
+            cmd.env("LENS_PYTEST_NAMES", serde_json::to_string(names)?);
+        }
+        if update_snapshots {
+            cmd.env("LENS_PYTEST_UPDATE_SNAPSHOTS", "1");
+        }
+        if self.coverage.load(Ordering::Relaxed) {
+            cmd.env("LENS_PYTEST_COVERAGE", "1");
+        }
+        cmd.args(["-m", "pytest", "-p", PLUGIN_MODULE]).args(paths);
+
+        // Log the full command for debugging (LENS_DEBUG=path)
+        self.base.log(&format!("[cmd] {:?}", cmd.as_std()));
+        self.base.log(&format!("[cwd] {:?}", cwd));
+        let _ = tx.send(TestEvent::CommandStarted {
+            command: command_line(&cmd),
+            cwd: cwd.clone(),
+        });
+
+        // Put the child in its own process group so killing it (via ChildGuard) also
+        // takes out any processes the tests start (prevents orphans). The plugin
+        // keeps pytest-xdist from starting workers: their results never reach it.
+        #[cfg(unix)]
+        {
+            use std::os::unix::process::CommandExt;
+            cmd.as_std_mut().process_group(0);
+        }
+
+        let mut child = cmd
+            .current_dir(&cwd)
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped())
+            .spawn()
+            .context("failed to spawn python")?;
+
+        drop(child.stdin.take());
+        let stdout = child.stdout.take().context("missing stdout")?;
+        let stderr = child.stderr.take().context("missing stderr")?;
+
+        let mut child_guard = ChildGuard::new(child);
+
+        stream_output(stdout, stderr, tx, self.base.log_file.clone()).await;
+
+        if let Some(ref mut child) = child_guard.child {
+            let status = child.wait().await.context("failed to wait for pytest")?;
+            // Pytest exits with 1 when tests fail and 5 when none were
+            // collected; both are already visible in the tree.
+            if !status.success() && !matches!(status.code(), Some(1 | 5)) {
+                let _ = tx.send(TestEvent::Error {
+                    message: format!("pytest exited with code {}", status.code().unwrap_or(-1)),
+                });
+            }
+        }
+
+        // Keep the plugin alive until pytest exits
+        drop(plugin_dir);
+
+        Ok(())
+    }
+
+    /// Pytest has no watch mode, so runs `paths` again on every change.
+    async fn watch(
+        &self,
+        paths: &[PathBuf],
+        names: Option<&[String]>,
+        tx: mpsc::UnboundedSender<TestEvent>,
+    ) -> Result<()> {
+        rerun_on_change(&self.base.search_roots, &WATCH_EXTENSIONS, || {
+            self.spawn_and_stream(paths, names, false, &tx)
+        })
+        .await
+    }
+}
+
+#[async_trait]
+impl TestRunner for PytestRunner {
+    async fn discover(&self, workspace: &Path) -> Result<Vec<DiscoveredFile>> {
+        let mut files = discover_files(
+            workspace,
+            &self.base.workspace,
+            &PATTERNS,
+            &self.base.ignore_patterns,
+        )?;
+        files.retain(|file| !is_vendored(&file.path, workspace));
+        Ok(files)
+    }
+
+    async fn run_all(&self, tx: mpsc::UnboundedSender<TestEvent>) -> Result<()> {
+        self.spawn_and_stream(&self.all_paths(), None, false, &tx)
+            .await
+    }
+
+    async fn run_files(
+        &self,
+        files: &[PathBuf],
+        tx: mpsc::UnboundedSender<TestEvent>,
+    ) -> Result<()> {
+        self.spawn_and_stream(files, None, false, &tx).await
+    }
+
+    async fn run_file(&self, file: &Path, tx: mpsc::UnboundedSender<TestEvent>) -> Result<()> {
+        self.spawn_and_stream(&[file.to_path_buf()], None, false, &tx)
+            .await
+    }
+
+    async fn run_test(
+        &self,
+        file: &Path,
+        test_name: &str,
+        tx: mpsc::UnboundedSender<TestEvent>,
+    ) -> Result<()> {
+        let names = [test_name.to_string()];
+        self.spawn_and_stream(&[file.to_path_buf()], Some(&names), false, &tx)
+            .await
+    }
+
+    async fn run_tests(
+        &self,
+        files: &[PathBuf],
+        names: &[String],
+        tx: mpsc::UnboundedSender<TestEvent>,
+    ) -> Result<()> {
+        self.spawn_and_stream(files, Some(names), false, &tx).await
+    }
+
+    async fn update_snapshots(
+        &self,
+        files: &[PathBuf],
+        test_name: Option<&str>,
+        tx: mpsc::UnboundedSender<TestEvent>,
+    ) -> Result<()> {
+        // Pytest has no snapshots of its own; the plugin turns on syrupy's
+        // `--snapshot-update` when syrupy is installed.
+        let names = test_name.map(|name| vec![name.to_string()]);
+        self.spawn_and_stream(files, names.as_deref(), true, &tx)
+            .await
+    }
+
+    async fn run_file_watch(
+        &self,
+        file: &Path,
+        tx: mpsc::UnboundedSender<TestEvent>,
+    ) -> Result<()> {
+        self.watch(&[file.to_path_buf()], None, tx).await
+    }
+
+    async fn run_test_watch(
+        &self,
+        file: &Path,
+        test_name: &str,
+        tx: mpsc::UnboundedSender<TestEvent>,
+    ) -> Result<()> {
+        let names = [test_name.to_string()];
+        self.watch(&[file.to_path_buf()], Some(&names), tx).await
+    }
+
+    async fn run_all_watch(&self, tx: mpsc::UnboundedSender<TestEvent>) -> Result<()> {
+        self.watch(&self.all_paths(), None, tx).await
+    }
+
+    fn set_coverage(&self, enabled: bool) {
+        self.coverage.store(enabled, Ordering::Relaxed);
+    }
+
+    fn name(&self) -> &str {
+        "pytest"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_pytest_configuration() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("pyproject.toml"),
+            "[project]\nname = \"a\"\n",
+        )
+        .unwrap();
+        assert!(!is_pytest_project(dir.path()));
+
+        std::fs::write(
+            dir.path().join("pyproject.toml"),
+            "[tool.pytest.ini_options]\ntestpaths = [\"tests\"]\n",
+        )
+        .unwrap();
+        assert!(is_pytest_project(dir.path()));
+
+        std::fs::write(dir.path().join("package.json"), "{}").unwrap();
+        assert!(!is_pytest_project(dir.path()));
+    }
+
+    #[tokio::test]
+    async fn discovers_pytest_default_patterns_outside_environments() {
+        let dir = tempfile::tempdir().unwrap();
+        for file in [
+            "tests/test_math.py",
+            "src/parse_test.py",
+            "src/math.py",
+            "tests/conftest.py",
+            ".venv/lib/python3.12/site-packages/dep/test_dep.py",
+            "venv/lib/python3.12/site-packages/dep/test_dep.py",
+        ] {
+            let path = dir.path().join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, "").unwrap();
+        }
+
+        let runner = PytestRunner::new(dir.path().to_path_buf(), vec![], vec![]);
+        let mut found: Vec<PathBuf> = runner
+            .discover(dir.path())
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|f| f.path.strip_prefix(dir.path()).unwrap().to_path_buf())
+            .collect();
+        found.sort();
+        assert_eq!(
+            found,
+            [
+                PathBuf::from("src/parse_test.py"),
+                PathBuf::from("tests/test_math.py"),
+            ]
+        );
+    }
+
+    #[test]
+    fn prefers_the_project_virtualenv() {
+        let dir = tempfile::tempdir().unwrap();
+        let fallback = if cfg!(windows) { "python" } else { "python3" };
+        assert_eq!(python(dir.path()), PathBuf::from(fallback));
+
+        let venv = if cfg!(windows) {
+            ".venv/Scripts/python.exe"
+        } else {
+            ".venv/bin/python"
+        };
+        let path = dir.path().join(venv);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, "").unwrap();
+        assert_eq!(python(dir.path()), path);
+    }
+
+    fn has_pytest() -> bool {
+        let has_pytest = std::process::Command::new("python3")
+            .args(["-c", "import pytest"])
+            .output()
+            .is_ok_and(|output| output.status.success());
+        if !has_pytest {
+            eprintln!("skipping: pytest is not installed");
+        }
+        has_pytest
+    }
+
+    const CLASS_TESTS: &str = "class TestMath:\n    def test_adds(self):\n        assert 1 + 1 == 2\n\n    def test_fails(self):\n        assert 1 + 2 == 4\n";
+
+    #[tokio::test]
+    async fn plugin_reports_class_methods_and_compared_values() {
+        if !has_pytest() {
+            return;
+        }
+
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("test_math.py"), CLASS_TESTS).unwrap();
+
+        let runner = PytestRunner::new(dir.path().to_path_buf(), vec![], vec![]);
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        runner.run_all(tx).await.unwrap();
+        let mut finished = Vec::new();
+        let mut suites = Vec::new();
+        while let Ok(event) = rx.try_recv() {
+            match event {
+                TestEvent::TestFinished { name, result, .. } => finished.push((name, result)),
+                TestEvent::SuiteLocation { name, location, .. } => suites.push((name, location)),
+                _ => {}
+            }
+        }
+
+        assert_eq!(suites, [("TestMath".to_string(), (1, 1))]);
+        let names: Vec<&str> = finished.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, ["TestMath > test_adds", "TestMath > test_fails"]);
+        let failure = finished[1].1.failure().unwrap();
+        assert_eq!(failure.expected.as_deref(), Some("4"));
+        assert_eq!(failure.actual.as_deref(), Some("3"));
+    }
+
+    #[tokio::test]
+    async fn a_class_method_runs_by_its_full_name() {
+        if !has_pytest() {
+            return;
+        }
+
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("test_math.py");
+        std::fs::write(&file, CLASS_TESTS).unwrap();
+
+        let runner = PytestRunner::new(dir.path().to_path_buf(), vec![], vec![]);
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        runner
+            .run_test(&file, "TestMath > test_adds", tx)
+            .await
+            .unwrap();
+        let mut names = Vec::new();
+        while let Ok(event) = rx.try_recv() {
+            if let TestEvent::TestFinished { name, .. } = event {
+                names.push(name);
+            }
+        }
+        assert_eq!(names, ["TestMath > test_adds"]);
+    }
+}
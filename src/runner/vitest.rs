@@ -1,18 +1,25 @@
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use anyhow::{Context, Result};
 use async_trait::async_trait;
 use serde::Deserialize;
-use tokio::io::{AsyncBufReadExt, BufReader};
-use tokio::process::Command;
-use tokio::sync::mpsc;
+use serde_json::json;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::process::{ChildStderr, ChildStdin, ChildStdout, Command};
+use tokio::sync::{Mutex, mpsc, oneshot};
 
 use crate::app::TestEvent;
-use crate::models::{FailureOutput, RunSummary, TestResult, TestStatus};
+use crate::app::coverage::{FileCoverage, Metric, line_ranges};
+use crate::models::{Attachment, ConsoleStream, FailureOutput, RunSummary, TestResult, TestStatus};
 
-use super::{DiscoveredFile, TestRunner};
+use super::{
+    DiscoveredFile, RunnerBase, TestRunner, command_line, discover_files, escape_test_name,
+    file_name_prefixes, full_names_pattern,
+};
 
-const SUFFIXES: [&str; 8] = [
+pub(super) const SUFFIXES: [&str; 8] = [
     "*.test.ts",
     "*.test.tsx",
     "*.test.js",
@@ -24,15 +31,15 @@ const SUFFIXES: [&str; 8] = [
 ];
 
 /// Guard that kills the child process (and its entire process group) on drop.
-struct ChildGuard {
-    child: Option<tokio::process::Child>,
+pub(super) struct ChildGuard {
+    pub(super) child: Option<tokio::process::Child>,
     /// Process group ID saved at spawn time so we can kill the whole group.
     #[cfg(unix)]
     pgid: Option<u32>,
 }
 
 impl ChildGuard {
-    fn new(child: tokio::process::Child) -> Self {
+    pub(super) fn new(child: tokio::process::Child) -> Self {
         #[cfg(unix)]
         let pgid = child.id();
         Self {
@@ -60,9 +67,9 @@ impl Drop for ChildGuard {
 const REPORTER_SOURCE: &str = include_str!("../../reporters/vitest-reporter.mjs");
 
 /// Open a debug log file if `LENS_DEBUG` env var is set.
-type LogFile = std::sync::Arc<std::sync::Mutex<std::fs::File>>;
+pub(super) type LogFile = std::sync::Arc<std::sync::Mutex<std::fs::File>>;
 
-fn open_log_file() -> Option<LogFile> {
+pub(super) fn open_log_file() -> Option<LogFile> {
     let path = std::env::var("LENS_DEBUG").ok()?;
     let f = std::fs::OpenOptions::new()
         .create(true)
@@ -76,48 +83,66 @@ fn open_log_file() -> Option<LogFile> {
     Some(lf)
 }
 
-fn write_log(lf: &LogFile, msg: &str) {
+pub(super) fn write_log(lf: &LogFile, msg: &str) {
     use std::io::Write;
     if let Ok(mut f) = lf.lock() {
         let _ = writeln!(f, "{}", msg);
     }
 }
 
+/// The run currently receiving events from the persistent server.
+struct RunSink {
+    tx: mpsc::UnboundedSender<TestEvent>,
+    /// Fired when the run finishes (or the reporter rejects the command).
+    done: Option<oneshot::Sender<()>>,
+}
+
+type SharedSink = Arc<std::sync::Mutex<Option<RunSink>>>;
+
+/// A long-lived `vitest --standalone` process. Runs are triggered by writing
+/// `LENS_RUN:{...}` commands to its stdin, so they skip vite's cold start.
+struct VitestServer {
+    stdin: ChildStdin,
+    sink: SharedSink,
+    /// Cleared when the process's stdout closes.
+    alive: Arc<AtomicBool>,
+    /// Set once the reporter reports the server accepts commands.
+    ready: Arc<AtomicBool>,
+    /// Whether the server was started with coverage collection enabled.
+    coverage: bool,
+    _child: ChildGuard,
+    // Referenced by the vitest command line; must outlive the process.
+    _reporter_file: tempfile::NamedTempFile,
+    _workspace_config: Option<tempfile::NamedTempFile>,
+}
+
 /// Vitest adapter that spawns vitest with a custom NDJSON reporter.
 /// For Nx workspaces, finds vite/vitest configs and runs vitest directly
 /// with `--config` to bypass nx's output buffering.
+///
+/// Non-watch runs go through a persistent vitest server started on first use;
+/// if the installed vitest can't run one, each run spawns its own process.
 pub struct VitestRunner {
-    workspace: PathBuf,
-    /// Root directory to search for configs and test files.
-    /// Defaults to workspace, but can be narrowed to a single project.
-    search_root: PathBuf,
-    log_file: Option<LogFile>,
-    /// Compiled glob patterns for files to skip during discovery.
-    ignore_patterns: Vec<glob::Pattern>,
+    base: RunnerBase,
+    /// Held for the duration of a run, which also serializes runs on the server.
+    server: Mutex<Option<VitestServer>>,
+    /// Set when the server exited before becoming ready (e.g. vitest < 2.1).
+    server_unsupported: AtomicBool,
+    /// Collect v8 coverage on every run.
+    coverage: AtomicBool,
 }
 
 impl VitestRunner {
     pub fn new(
         workspace: PathBuf,
-        project_root: Option<PathBuf>,
+        project_roots: Vec<PathBuf>,
         ignore_patterns: Vec<String>,
     ) -> Self {
-        let search_root = project_root.unwrap_or_else(|| workspace.clone());
-        let ignore_patterns = ignore_patterns
-            .iter()
-            .filter_map(|p| glob::Pattern::new(p).ok())
-            .collect();
         Self {
-            workspace,
-            search_root,
-            log_file: open_log_file(),
-            ignore_patterns,
-        }
-    }
-
-    fn log(&self, msg: &str) {
-        if let Some(ref lf) = self.log_file {
-            write_log(lf, msg);
+            base: RunnerBase::new(workspace, project_roots, ignore_patterns),
+            server: Mutex::new(None),
+            server_unsupported: AtomicBool::new(false),
+            coverage: AtomicBool::new(false),
         }
     }
 
@@ -136,6 +161,30 @@ impl VitestRunner {
         Ok(file)
     }
 
+    /// Where coverage reports go, so a coverage run never touches the project's
+    /// own `coverage/` directory. Lens reads coverage from the reporter, not this.
+    fn coverage_dir(&self) -> String {
+        self.base
+            .workspace
+            .join(".lens")
+            .join("coverage")
+            .to_string_lossy()
+            .replace('\\', "/")
+    }
+
+    /// CLI flags enabling v8 coverage when no generated workspace config is used.
+    fn coverage_args(&self) -> Vec<String> {
+        if !self.coverage.load(Ordering::Relaxed) {
+            return Vec::new();
+        }
+        vec![
+            "--coverage.enabled".to_string(),
+            "--coverage.provider=v8".to_string(),
+            "--coverage.reporter=json-summary".to_string(),
+            format!("--coverage.reportsDirectory={}", self.coverage_dir()),
+        ]
+    }
+
     /// Generate a temporary workspace config that lists all project directories
     /// in `test.projects`, enabling single-process vitest execution.
     fn write_workspace_config(
@@ -159,9 +208,19 @@ impl VitestRunner {
             .collect::<Vec<_>>()
             .join(",\n");
 
+        let coverage = if self.coverage.load(Ordering::Relaxed) {
+            format!(
+                "{{ enabled: true, provider: 'v8', reporter: ['json-summary'], reportsDirectory: '{}' }}",
+                self.coverage_dir()
+            )
+        } else {
+            "{ enabled: false }".to_string()
+        };
+
         let content = format!(
-            "export default {{\n  test: {{\n    reporters: ['{}'],\n    coverage: {{ enabled: false }},\n    projects: [\n{}\n    ]\n  }}\n}}\n",
+            "export default {{\n  test: {{\n    reporters: ['{}'],\n    coverage: {},\n    projects: [\n{}\n    ]\n  }}\n}}\n",
             reporter_path.replace('\\', "/"),
+            coverage,
             projects_json,
         );
 
@@ -178,7 +237,7 @@ impl VitestRunner {
         Ok(file)
     }
 
-    /// Find all vite/vitest config files in the workspace (these define test projects).
+    /// Find all vite/vitest config files under the search roots (these define test projects).
     fn find_vitest_configs(&self) -> Vec<PathBuf> {
         let mut configs = Vec::new();
         let names = [
@@ -191,13 +250,13 @@ impl VitestRunner {
             "vitest.config.ts",
             "vitest.config.mts",
         ];
-        for name in &names {
-            let pattern = self
-                .search_root
-                .join("**/")
-                .join(name)
-                .to_string_lossy()
-                .to_string();
+        for (root, name) in self
+            .base
+            .search_roots
+            .iter()
+            .flat_map(|root| names.iter().map(move |name| (root, name)))
+        {
+            let pattern = root.join("**/").join(name).to_string_lossy().to_string();
             if let Ok(entries) = glob::glob(&pattern) {
                 for entry in entries.flatten() {
                     let path_str = entry.to_string_lossy();
@@ -235,20 +294,25 @@ impl VitestRunner {
         let mut cmd = Command::new("npx");
         cmd.arg("vitest");
         cmd.arg(if watch { "watch" } else { "run" });
-        cmd.args(args)
-            .arg("--disableConsoleIntercept")
-            .arg("--includeTaskLocation");
+        // Console interception stays on: the reporter's `onUserConsoleLog` is
+        // what attributes each log to the test that wrote it.
+        cmd.args(args).arg("--includeTaskLocation");
 
         if let Some(ws_config) = workspace_config {
             cmd.arg("-c").arg(ws_config);
         } else if let Some(ref rf) = reporter_file {
             cmd.arg(format!("--reporter={}", rf.path().to_string_lossy()));
+            cmd.args(self.coverage_args());
         }
 
         // Log the full command for debugging (LENS_DEBUG=path)
-        let effective_cwd = cwd.unwrap_or(&self.workspace);
-        self.log(&format!("[cmd] {:?}", cmd.as_std()));
-        self.log(&format!("[cwd] {:?}", effective_cwd));
+        let effective_cwd = cwd.unwrap_or(&self.base.workspace);
+        self.base.log(&format!("[cmd] {:?}", cmd.as_std()));
+        self.base.log(&format!("[cwd] {:?}", effective_cwd));
+        let _ = tx.send(TestEvent::CommandStarted {
+            command: command_line(&cmd),
+            cwd: effective_cwd.to_path_buf(),
+        });
 
         // Put the child in its own process group so killing it (via ChildGuard) also
         // takes out any worker processes vitest forks (prevents orphans).
@@ -278,46 +342,7 @@ impl VitestRunner {
         // future is dropped (e.g. task aborted, app closed mid-run).
         let mut child_guard = ChildGuard::new(child);
 
-        // Read stderr in background for error reporting
-        let tx_err = tx.clone();
-        let log_err = self.log_file.clone();
-        let stderr_handle = tokio::spawn(async move {
-            let reader = BufReader::new(stderr);
-            let mut lines = reader.lines();
-            while let Ok(Some(line)) = lines.next_line().await {
-                if let Some(ref lf) = log_err {
-                    write_log(lf, &format!("[stderr] {}", line));
-                }
-                let _ = tx_err.send(TestEvent::Output { line });
-            }
-        });
-
-        // Parse NDJSON from stdout
-        let reader = BufReader::new(stdout);
-        let mut lines = reader.lines();
-
-        while let Ok(Some(line)) = lines.next_line().await {
-            let line = line.trim().to_string();
-            if line.is_empty() {
-                continue;
-            }
-
-            self.log(&format!("[stdout] {}", line));
-
-            match serde_json::from_str::<VitestEvent>(&line) {
-                Ok(event) => {
-                    if let Some(test_event) = event.into_test_event() {
-                        let _ = tx.send(test_event);
-                    }
-                }
-                Err(_) => {
-                    // Non-JSON output from vitest (e.g. banner), forward as raw output
-                    let _ = tx.send(TestEvent::Output { line });
-                }
-            }
-        }
-
-        stderr_handle.await.ok();
+        stream_output(stdout, stderr, &tx, self.base.log_file.clone()).await;
 
         // Keep the temp file alive until vitest exits
         drop(reporter_file);
@@ -334,6 +359,146 @@ impl VitestRunner {
         Ok(())
     }
 
+    /// Start a standalone vitest server covering every project under the search root.
+    ///
+    /// `--standalone` keeps vitest idle until the reporter receives a command, and
+    /// `LENS_SERVER` tells the reporter to ignore file-change reruns.
+    fn start_server(&self, tx: &mpsc::UnboundedSender<TestEvent>) -> Result<VitestServer> {
+        let configs = self.find_vitest_configs();
+        let reporter_file = self.write_reporter()?;
+        let reporter_path = reporter_file.path().to_string_lossy().to_string();
+
+        let mut cmd = Command::new("npx");
+        cmd.args(["vitest", "watch", "--standalone"])
+            .arg("--includeTaskLocation")
+            .env("LENS_SERVER", "1");
+
+        let workspace_config = if configs.is_empty() {
+            cmd.arg(format!("--reporter={}", reporter_path));
+            cmd.args(self.coverage_args());
+            None
+        } else {
+            let ws_config = self.write_workspace_config(&configs, &reporter_path)?;
+            cmd.arg("-c").arg(ws_config.path());
+            Some(ws_config)
+        };
+
+        self.base.log(&format!("[server cmd] {:?}", cmd.as_std()));
+        self.base
+            .log(&format!("[server cwd] {:?}", self.base.workspace));
+        let _ = tx.send(TestEvent::CommandStarted {
+            command: command_line(&cmd),
+            cwd: self.base.workspace.clone(),
+        });
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::process::CommandExt;
+            cmd.as_std_mut().process_group(0);
+        }
+
+        let mut child = cmd
+            .current_dir(&self.base.workspace)
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped())
+            .spawn()
+            .context("failed to spawn vitest server")?;
+
+        let stdin = child.stdin.take().context("missing stdin")?;
+        let stdout = child.stdout.take().context("missing stdout")?;
+        let stderr = child.stderr.take().context("missing stderr")?;
+
+        let sink: SharedSink = Arc::default();
+        let alive = Arc::new(AtomicBool::new(true));
+        let ready = Arc::new(AtomicBool::new(false));
+
+        tokio::spawn(route_server_stdout(
+            stdout,
+            Arc::clone(&sink),
+            Arc::clone(&alive),
+            Arc::clone(&ready),
+            self.base.log_file.clone(),
+        ));
+        tokio::spawn(route_server_stderr(
+            stderr,
+            Arc::clone(&sink),
+            self.base.log_file.clone(),
+        ));
+
+        Ok(VitestServer {
+            stdin,
+            sink,
+            alive,
+            ready,
+            coverage: self.coverage.load(Ordering::Relaxed),
+            _child: ChildGuard::new(child),
+            _reporter_file: reporter_file,
+            _workspace_config: workspace_config,
+        })
+    }
+
+    /// Send a run command to the persistent server and wait for the run to finish.
+    ///
+    /// Returns `Ok(false)` without running anything when the server isn't
+    /// available, in which case the caller spawns a one-off vitest process.
+    async fn run_via_server(
+        &self,
+        command: serde_json::Value,
+        tx: &mpsc::UnboundedSender<TestEvent>,
+    ) -> Result<bool> {
+        if self.server_unsupported.load(Ordering::Relaxed) {
+            return Ok(false);
+        }
+
+        // Coverage is fixed when vitest starts, so toggling it restarts the server.
+        let coverage = self.coverage.load(Ordering::Relaxed);
+        let mut guard = self.server.lock().await;
+        if !guard
+            .as_ref()
+            .is_some_and(|s| s.alive.load(Ordering::Relaxed) && s.coverage == coverage)
+        {
+            *guard = Some(self.start_server(tx)?);
+        }
+        let Some(server) = guard.as_mut() else {
+            return Ok(false);
+        };
+
+        let (done_tx, done_rx) = oneshot::channel();
+        if let Ok(mut sink) = server.sink.lock() {
+            *sink = Some(RunSink {
+                tx: tx.clone(),
+                done: Some(done_tx),
+            });
+        }
+
+        let line = format!("LENS_RUN:{}\n", command);
+        self.base
+            .log(&format!("[server stdin] {}", line.trim_end()));
+        let written = async {
+            server.stdin.write_all(line.as_bytes()).await?;
+            server.stdin.flush().await
+        }
+        .await;
+
+        if written.is_ok() {
+            let _ = done_rx.await;
+        }
+        if let Ok(mut sink) = server.sink.lock() {
+            *sink = None;
+        }
+
+        if !server.ready.load(Ordering::Relaxed) && !server.alive.load(Ordering::Relaxed) {
+            self.base
+                .log("[server] exited before becoming ready, falling back to one-off runs");
+            self.server_unsupported.store(true, Ordering::Relaxed);
+            *guard = None;
+            return Ok(false);
+        }
+        written.context("failed to send command to vitest server")?;
+        Ok(true)
+    }
+
     fn find_config_for_file(&self, file: &Path) -> Option<PathBuf> {
         let configs = self.find_vitest_configs();
         for config in configs {
@@ -345,33 +510,161 @@ impl VitestRunner {
     }
 }
 
-#[async_trait]
-impl TestRunner for VitestRunner {
-    async fn discover(&self, workspace: &Path) -> Result<Vec<DiscoveredFile>> {
-        let mut files = Vec::new();
-        for suffix in &SUFFIXES {
-            let pattern = workspace
-                .join("**/")
-                .join(suffix)
-                .to_string_lossy()
-                .to_string();
-            for entry in glob::glob(&pattern)?.flatten() {
-                let rel = entry.strip_prefix(&self.workspace).unwrap_or(&entry);
-                let rel_str = rel.to_string_lossy();
-                if entry.to_string_lossy().contains("node_modules")
-                    || files.iter().any(|f: &DiscoveredFile| f.path == entry)
-                    || self.ignore_patterns.iter().any(|p| p.matches(&rel_str))
-                {
-                    continue;
+/// Route the persistent server's NDJSON output to the run in flight, if any.
+/// Output produced between runs is dropped.
+async fn route_server_stdout(
+    stdout: ChildStdout,
+    sink: SharedSink,
+    alive: Arc<AtomicBool>,
+    ready: Arc<AtomicBool>,
+    log_file: Option<LogFile>,
+) {
+    let mut lines = BufReader::new(stdout).lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        let line = line.trim().to_string();
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(ref lf) = log_file {
+            write_log(lf, &format!("[server stdout] {}", line));
+        }
+
+        let (event, ends_run) = match serde_json::from_str::<VitestEvent>(&line) {
+            Ok(VitestEvent::ServerReady) => {
+                ready.store(true, Ordering::Relaxed);
+                continue;
+            }
+            Ok(event) => {
+                let ends_run = matches!(
+                    event,
+                    VitestEvent::RunFinished { .. } | VitestEvent::Error { .. }
+                );
+                (event.into_test_event(), ends_run)
+            }
+            Err(_) => (Some(TestEvent::Output { line }), false),
+        };
+
+        if let Ok(mut sink) = sink.lock()
+            && let Some(run) = sink.as_mut()
+        {
+            if let Some(event) = event {
+                let _ = run.tx.send(event);
+            }
+            if ends_run && let Some(done) = run.done.take() {
+                let _ = done.send(());
+            }
+        }
+    }
+
+    alive.store(false, Ordering::Relaxed);
+    if let Some(ref lf) = log_file {
+        write_log(lf, "[server] exited");
+    }
+
+    // Unblock the run in flight. A server that never became ready is reported by
+    // the caller falling back to one-off runs instead.
+    if let Ok(mut sink) = sink.lock()
+        && let Some(run) = sink.as_mut()
+    {
+        if ready.load(Ordering::Relaxed) {
+            let _ = run.tx.send(TestEvent::Error {
+                message: "vitest server exited".into(),
+            });
+        }
+        if let Some(done) = run.done.take() {
+            let _ = done.send(());
+        }
+    }
+}
+
+/// Forward the persistent server's stderr to the run in flight, if any.
+async fn route_server_stderr(stderr: ChildStderr, sink: SharedSink, log_file: Option<LogFile>) {
+    let mut lines = BufReader::new(stderr).lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        if let Some(ref lf) = log_file {
+            write_log(lf, &format!("[server stderr] {}", line));
+        }
+        if let Ok(sink) = sink.lock()
+            && let Some(run) = sink.as_ref()
+        {
+            let _ = run.tx.send(TestEvent::Output { line });
+        }
+    }
+}
+
+/// Forward a runner's stdout/stderr to the app until stdout closes.
+///
+/// Stdout lines are parsed as lens NDJSON events; anything that isn't (e.g. a
+/// banner) is forwarded as raw output. Stderr is forwarded line by line.
+pub(super) async fn stream_output(
+    stdout: ChildStdout,
+    stderr: ChildStderr,
+    tx: &mpsc::UnboundedSender<TestEvent>,
+    log_file: Option<LogFile>,
+) {
+    // Read stderr in background for error reporting
+    let tx_err = tx.clone();
+    let log_err = log_file.clone();
+    let stderr_handle = tokio::spawn(async move {
+        let reader = BufReader::new(stderr);
+        let mut lines = reader.lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            if let Some(ref lf) = log_err {
+                write_log(lf, &format!("[stderr] {}", line));
+            }
+            let _ = tx_err.send(TestEvent::Output { line });
+        }
+    });
+
+    // Parse NDJSON from stdout
+    let reader = BufReader::new(stdout);
+    let mut lines = reader.lines();
+
+    while let Ok(Some(line)) = lines.next_line().await {
+        let line = line.trim().to_string();
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(ref lf) = log_file {
+            write_log(lf, &format!("[stdout] {}", line));
+        }
+
+        match serde_json::from_str::<VitestEvent>(&line) {
+            Ok(event) => {
+                if let Some(test_event) = event.into_test_event() {
+                    let _ = tx.send(test_event);
                 }
-                files.push(DiscoveredFile { path: entry });
+            }
+            Err(_) => {
+                // Non-JSON output from the runner (e.g. banner), forward as raw output
+                let _ = tx.send(TestEvent::Output { line });
             }
         }
+    }
+
+    stderr_handle.await.ok();
+}
 
-        Ok(files)
+#[async_trait]
+impl TestRunner for VitestRunner {
+    async fn discover(&self, workspace: &Path) -> Result<Vec<DiscoveredFile>> {
+        discover_files(
+            workspace,
+            &self.base.workspace,
+            &SUFFIXES,
+            &self.base.ignore_patterns,
+        )
     }
 
     async fn run_all(&self, tx: mpsc::UnboundedSender<TestEvent>) -> Result<()> {
+        if self
+            .run_via_server(json!({ "type": "run-all" }), &tx)
+            .await?
+        {
+            return Ok(());
+        }
         let configs = self.find_vitest_configs();
         if configs.is_empty() {
             // No configs found, run vitest from workspace root (non-Nx)
@@ -401,6 +694,10 @@ impl TestRunner for VitestRunner {
             .iter()
             .map(|f| f.to_string_lossy().to_string())
             .collect();
+        let command = json!({ "type": "run-files", "files": file_args });
+        if self.run_via_server(command, &tx).await? {
+            return Ok(());
+        }
         let file_arg_strs: Vec<&str> = file_args.iter().map(String::as_str).collect();
         let configs = self.find_vitest_configs();
         if configs.is_empty() {
@@ -422,6 +719,10 @@ impl TestRunner for VitestRunner {
 
     async fn run_file(&self, file: &Path, tx: mpsc::UnboundedSender<TestEvent>) -> Result<()> {
         let file_abs = file.to_string_lossy().to_string();
+        let command = json!({ "type": "run-file", "file": file_abs });
+        if self.run_via_server(command, &tx).await? {
+            return Ok(());
+        }
         if let Some(config) = self.find_config_for_file(file) {
             let reporter_file = self.write_reporter()?;
             let reporter_path = reporter_file.path().to_string_lossy().to_string();
@@ -446,6 +747,11 @@ impl TestRunner for VitestRunner {
         tx: mpsc::UnboundedSender<TestEvent>,
     ) -> Result<()> {
         let file_abs = file.to_string_lossy().to_string();
+        let command = json!({ "type": "run-test", "file": file_abs, "name": test_name });
+        let pattern = escape_test_name(test_name);
+        if self.run_via_server(command, &tx).await? {
+            return Ok(());
+        }
         if let Some(config) = self.find_config_for_file(file) {
             let reporter_file = self.write_reporter()?;
             let reporter_path = reporter_file.path().to_string_lossy().to_string();
@@ -453,7 +759,7 @@ impl TestRunner for VitestRunner {
             let ws_path = workspace_config.path().to_path_buf();
             let result = self
                 .spawn_and_stream(
-                    &[&file_abs, "-t", test_name],
+                    &[&file_abs, "-t", &pattern],
                     tx,
                     false,
                     Some(&ws_path),
@@ -464,11 +770,82 @@ impl TestRunner for VitestRunner {
             drop(reporter_file);
             result
         } else {
-            self.spawn_and_stream(&[&file_abs, "-t", test_name], tx, false, None, None)
+            self.spawn_and_stream(&[&file_abs, "-t", &pattern], tx, false, None, None)
                 .await
         }
     }
 
+    async fn run_tests(
+        &self,
+        files: &[PathBuf],
+        names: &[String],
+        tx: mpsc::UnboundedSender<TestEvent>,
+    ) -> Result<()> {
+        let file_args: Vec<String> = files
+            .iter()
+            .map(|f| f.to_string_lossy().to_string())
+            .collect();
+        let pattern = full_names_pattern(names, &file_name_prefixes(&self.base.workspace, files));
+        let command = json!({ "type": "run-tests", "files": file_args, "pattern": pattern });
+        if self.run_via_server(command, &tx).await? {
+            return Ok(());
+        }
+        let mut args: Vec<&str> = file_args.iter().map(String::as_str).collect();
+        args.extend(["-t", &pattern]);
+        let configs = self.find_vitest_configs();
+        if configs.is_empty() {
+            self.spawn_and_stream(&args, tx, false, None, None).await
+        } else {
+            let reporter_file = self.write_reporter()?;
+            let reporter_path = reporter_file.path().to_string_lossy().to_string();
+            let workspace_config = self.write_workspace_config(&configs, &reporter_path)?;
+            let ws_path = workspace_config.path().to_path_buf();
+            let result = self
+                .spawn_and_stream(&args, tx, false, Some(&ws_path), None)
+                .await;
+            drop(workspace_config);
+            drop(reporter_file);
+            result
+        }
+    }
+
+    async fn update_snapshots(
+        &self,
+        files: &[PathBuf],
+        test_name: Option<&str>,
+        tx: mpsc::UnboundedSender<TestEvent>,
+    ) -> Result<()> {
+        let file_args: Vec<String> = files
+            .iter()
+            .map(|f| f.to_string_lossy().to_string())
+            .collect();
+        let command = json!({ "type": "update-snapshots", "files": file_args, "name": test_name });
+        if self.run_via_server(command, &tx).await? {
+            return Ok(());
+        }
+        let mut args: Vec<&str> = file_args.iter().map(String::as_str).collect();
+        let pattern = test_name.map(escape_test_name);
+        if let Some(pattern) = &pattern {
+            args.extend(["-t", pattern]);
+        }
+        args.push("--update");
+        let configs = self.find_vitest_configs();
+        if configs.is_empty() {
+            self.spawn_and_stream(&args, tx, false, None, None).await
+        } else {
+            let reporter_file = self.write_reporter()?;
+            let reporter_path = reporter_file.path().to_string_lossy().to_string();
+            let workspace_config = self.write_workspace_config(&configs, &reporter_path)?;
+            let ws_path = workspace_config.path().to_path_buf();
+            let result = self
+                .spawn_and_stream(&args, tx, false, Some(&ws_path), None)
+                .await;
+            drop(workspace_config);
+            drop(reporter_file);
+            result
+        }
+    }
+
     async fn run_file_watch(
         &self,
         file: &Path,
@@ -499,25 +876,20 @@ impl TestRunner for VitestRunner {
         tx: mpsc::UnboundedSender<TestEvent>,
     ) -> Result<()> {
         let file_abs = file.to_string_lossy().to_string();
+        let pattern = escape_test_name(test_name);
         if let Some(config) = self.find_config_for_file(file) {
             let reporter_file = self.write_reporter()?;
             let reporter_path = reporter_file.path().to_string_lossy().to_string();
             let workspace_config = self.write_workspace_config(&[config], &reporter_path)?;
             let ws_path = workspace_config.path().to_path_buf();
             let result = self
-                .spawn_and_stream(
-                    &[&file_abs, "-t", test_name],
-                    tx,
-                    true,
-                    Some(&ws_path),
-                    None,
-                )
+                .spawn_and_stream(&[&file_abs, "-t", &pattern], tx, true, Some(&ws_path), None)
                 .await;
             drop(workspace_config);
             drop(reporter_file);
             result
         } else {
-            self.spawn_and_stream(&[&file_abs, "-t", test_name], tx, true, None, None)
+            self.spawn_and_stream(&[&file_abs, "-t", &pattern], tx, true, None, None)
                 .await
         }
     }
@@ -542,6 +914,10 @@ impl TestRunner for VitestRunner {
         }
     }
 
+    fn set_coverage(&self, enabled: bool) {
+        self.coverage.store(enabled, Ordering::Relaxed);
+    }
+
     fn name(&self) -> &str {
         "Vitest"
     }
@@ -572,8 +948,12 @@ enum VitestEvent {
         name: String,
         state: String,
         duration: Option<f64>,
-        error: Option<VitestError>,
+        #[serde(default)]
+        errors: Vec<VitestError>,
         location: Option<VitestLocation>,
+        /// Files saved for the test (Playwright traces, screenshots, …).
+        #[serde(default)]
+        attachments: Vec<Attachment>,
     },
     SuiteLocation {
         file: String,
@@ -582,6 +962,12 @@ enum VitestEvent {
     },
     ConsoleLog {
         file: String,
+        /// Full name of the test that logged it; absent for output logged
+        /// outside a test (module scope, `beforeAll`, …).
+        #[serde(default)]
+        test: Option<String>,
+        #[serde(default)]
+        stream: ConsoleStream,
         content: String,
     },
     FileFinished {
@@ -593,7 +979,25 @@ enum VitestEvent {
         failed: usize,
         skipped: usize,
         duration: u64,
+        #[serde(default)]
+        snapshots: VitestSnapshots,
+    },
+    /// Snapshot files the run created, rewrote or deleted.
+    SnapshotsWritten {
+        files: Vec<String>,
+    },
+    /// Per-file coverage, sent after the run's tests finish.
+    Coverage {
+        files: Vec<VitestFileCoverage>,
+    },
+    Output {
+        line: String,
     },
+    Error {
+        message: String,
+    },
+    /// The persistent server finished starting up and accepts `LENS_RUN` commands.
+    ServerReady,
 }
 
 #[derive(Debug, Deserialize)]
@@ -602,6 +1006,26 @@ struct VitestLocation {
     column: u32,
 }
 
+#[derive(Debug, Default, Deserialize)]
+struct VitestSnapshots {
+    #[serde(default)]
+    written: usize,
+    #[serde(default)]
+    updated: usize,
+    #[serde(default)]
+    obsolete: usize,
+}
+
+#[derive(Debug, Deserialize)]
+struct VitestFileCoverage {
+    file: String,
+    lines: Metric,
+    branches: Metric,
+    functions: Metric,
+    #[serde(default)]
+    uncovered_lines: Vec<u32>,
+}
+
 #[derive(Debug, Deserialize)]
 struct VitestError {
     message: Option<String>,
@@ -611,6 +1035,26 @@ struct VitestError {
     stack: Option<String>,
 }
 
+impl VitestError {
+    fn into_failure(self) -> FailureOutput {
+        let expected = self.expected.map(|s| strip_ansi(&s));
+        let actual = self.actual.map(|s| strip_ansi(&s));
+        let expected_parsed = expected.as_deref().and_then(parse_value_string);
+        let actual_parsed = actual.as_deref().and_then(parse_value_string);
+        FailureOutput {
+            message: strip_ansi(&self.message.unwrap_or_default()),
+            expected,
+            actual,
+            expected_parsed,
+            actual_parsed,
+            diff: self.diff.map(|s| strip_ansi(&s)),
+            source_snippet: None,
+            stack_trace: self.stack.map(|s| strip_ansi(&s)),
+            attachments: Vec::new(),
+        }
+    }
+}
+
 impl VitestEvent {
     fn into_test_event(self) -> Option<TestEvent> {
         match self {
@@ -623,8 +1067,9 @@ impl VitestEvent {
                 name,
                 state,
                 duration,
-                error,
+                errors,
                 location,
+                attachments,
             } => {
                 let status = match state.as_str() {
                     "passed" => TestStatus::Passed,
@@ -633,26 +1078,16 @@ impl VitestEvent {
                     _ => TestStatus::Pending,
                 };
 
-                let failure = if status == TestStatus::Failed {
-                    error.map(|e| {
-                        let expected = e.expected.map(|s| strip_ansi(&s));
-                        let actual = e.actual.map(|s| strip_ansi(&s));
-                        let expected_parsed = expected.as_deref().and_then(parse_value_string);
-                        let actual_parsed = actual.as_deref().and_then(parse_value_string);
-                        FailureOutput {
-                            message: strip_ansi(&e.message.unwrap_or_default()),
-                            expected,
-                            actual,
-                            expected_parsed,
-                            actual_parsed,
-                            diff: e.diff.map(|s| strip_ansi(&s)),
-                            source_snippet: None,
-                            stack_trace: e.stack.map(|s| strip_ansi(&s)),
-                        }
-                    })
+                let mut failures: Vec<FailureOutput> = if status == TestStatus::Failed {
+                    errors.into_iter().map(VitestError::into_failure).collect()
                 } else {
-                    None
+                    Vec::new()
                 };
+                // Attachments belong to the test, so they go on the error it
+                // failed on.
+                if let Some(first) = failures.first_mut() {
+                    first.attachments = attachments;
+                }
 
                 Some(TestEvent::TestFinished {
                     file,
@@ -660,7 +1095,7 @@ impl VitestEvent {
                     result: Box::new(TestResult {
                         status,
                         duration_ms: duration.map(|d| d as u64),
-                        failure,
+                        failures,
                     }),
                     location: location.map(|l| (l.line, l.column)),
                 })
@@ -674,9 +1109,17 @@ impl VitestEvent {
                 name,
                 location: (location.line, location.column),
             }),
-            VitestEvent::ConsoleLog { file, content } => {
-                Some(TestEvent::ConsoleLog { file, content })
-            }
+            VitestEvent::ConsoleLog {
+                file,
+                test,
+                stream,
+                content,
+            } => Some(TestEvent::ConsoleLog {
+                file,
+                test,
+                stream,
+                content,
+            }),
             VitestEvent::FileFinished { file } => Some(TestEvent::FileFinished { path: file }),
             VitestEvent::RunFinished {
                 total,
@@ -684,6 +1127,7 @@ impl VitestEvent {
                 failed,
                 skipped,
                 duration,
+                snapshots,
             } => Some(TestEvent::RunFinished {
                 summary: RunSummary {
                     total,
@@ -691,14 +1135,35 @@ impl VitestEvent {
                     failed,
                     skipped,
                     duration,
+                    snapshots_written: snapshots.written,
+                    snapshots_updated: snapshots.updated,
+                    snapshots_obsolete: snapshots.obsolete,
                 },
             }),
+            VitestEvent::SnapshotsWritten { files } => Some(TestEvent::SnapshotsWritten { files }),
+            VitestEvent::Coverage { files } => Some(TestEvent::Coverage {
+                files: files
+                    .into_iter()
+                    .map(|f| {
+                        let coverage = FileCoverage {
+                            lines: f.lines,
+                            branches: f.branches,
+                            functions: f.functions,
+                            uncovered: line_ranges(&f.uncovered_lines),
+                        };
+                        (f.file, coverage)
+                    })
+                    .collect(),
+            }),
+            VitestEvent::Output { line } => Some(TestEvent::Output { line }),
+            VitestEvent::Error { message } => Some(TestEvent::Error { message }),
+            VitestEvent::ServerReady => None,
         }
     }
 }
 
 /// Strip ANSI escape sequences from a string.
-fn strip_ansi(s: &str) -> String {
+pub(super) fn strip_ansi(s: &str) -> String {
     let mut out = String::with_capacity(s.len());
     let mut chars = s.chars();
     while let Some(c) = chars.next() {
@@ -836,6 +1301,169 @@ mod tests {
     use super::*;
     use serde_json::json;
 
+    // ── VitestEvent ─────────────────────────────────────────────────────────
+
+    #[test]
+    fn output_event_becomes_output_line() {
+        let event: VitestEvent =
+            serde_json::from_str(r#"{"type":"output","line":"[REPORTER] Initialized"}"#).unwrap();
+        assert!(matches!(
+            event.into_test_event(),
+            Some(TestEvent::Output { line }) if line == "[REPORTER] Initialized"
+        ));
+    }
+
+    #[test]
+    fn error_event_becomes_error() {
+        let event: VitestEvent =
+            serde_json::from_str(r#"{"type":"error","message":"Unknown command: x"}"#).unwrap();
+        assert!(matches!(
+            event.into_test_event(),
+            Some(TestEvent::Error { message }) if message == "Unknown command: x"
+        ));
+    }
+
+    #[test]
+    fn console_log_event_keeps_test_and_stream() {
+        let event: VitestEvent = serde_json::from_str(
+            r#"{"type":"console-log","file":"/ws/a.test.ts","test":"math > adds","stream":"stderr","content":"oops"}"#,
+        )
+        .unwrap();
+        assert!(matches!(
+            event.into_test_event(),
+            Some(TestEvent::ConsoleLog { test: Some(test), stream: ConsoleStream::Stderr, content, .. })
+                if test == "math > adds" && content == "oops"
+        ));
+
+        // Older reporters send neither.
+        let event: VitestEvent =
+            serde_json::from_str(r#"{"type":"console-log","file":"/ws/a.test.ts","content":"hi"}"#)
+                .unwrap();
+        assert!(matches!(
+            event.into_test_event(),
+            Some(TestEvent::ConsoleLog {
+                test: None,
+                stream: ConsoleStream::Stdout,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn server_ready_event_is_not_forwarded() {
+        let event: VitestEvent = serde_json::from_str(r#"{"type":"server-ready"}"#).unwrap();
+        assert!(event.into_test_event().is_none());
+    }
+
+    #[test]
+    fn failed_test_keeps_every_error() {
+        let event: VitestEvent = serde_json::from_str(
+            r#"{"type":"test-finished","file":"/ws/a.test.ts","name":"soft","state":"failed",
+                "errors":[
+                    {"message":"expected 1 to be 2","expected":"2","actual":"1","stack":"at a.test.ts:3:5"},
+                    {"message":"expected 'a' to be 'b'","expected":"\"b\"","actual":"\"a\""}
+                ]}"#,
+        )
+        .unwrap();
+        let Some(TestEvent::TestFinished { result, .. }) = event.into_test_event() else {
+            panic!("expected TestFinished");
+        };
+        assert_eq!(result.failures.len(), 2);
+        assert_eq!(result.failures[0].message, "expected 1 to be 2");
+        assert_eq!(
+            result.failures[0].stack_trace.as_deref(),
+            Some("at a.test.ts:3:5")
+        );
+        assert_eq!(result.failures[1].expected.as_deref(), Some("\"b\""));
+    }
+
+    #[test]
+    fn attachments_go_on_the_first_error() {
+        let event: VitestEvent = serde_json::from_str(
+            r#"{"type":"test-finished","file":"/ws/e2e/login.spec.ts","name":"chromium > logs in",
+                "state":"failed","errors":[{"message":"timed out"},{"message":"soft"}],
+                "attachments":[{"name":"trace","content_type":"application/zip","path":"/ws/test-results/trace.zip"}]}"#,
+        )
+        .unwrap();
+        let Some(TestEvent::TestFinished { result, .. }) = event.into_test_event() else {
+            panic!("expected TestFinished");
+        };
+        assert_eq!(result.failures[0].attachments.len(), 1);
+        assert_eq!(
+            result.failures[0].attachments[0].path,
+            PathBuf::from("/ws/test-results/trace.zip")
+        );
+        assert!(result.failures[1].attachments.is_empty());
+    }
+
+    #[test]
+    fn passed_test_drops_errors() {
+        let event: VitestEvent = serde_json::from_str(
+            r#"{"type":"test-finished","file":"a.test.ts","name":"ok","state":"passed","errors":[{"message":"x"}]}"#,
+        )
+        .unwrap();
+        let Some(TestEvent::TestFinished { result, .. }) = event.into_test_event() else {
+            panic!("expected TestFinished");
+        };
+        assert!(result.failures.is_empty());
+    }
+
+    #[test]
+    fn run_finished_carries_snapshot_counts() {
+        let event: VitestEvent = serde_json::from_str(
+            r#"{"type":"run-finished","total":3,"passed":3,"failed":0,"skipped":0,"duration":12,
+                "snapshots":{"written":1,"updated":2,"obsolete":4}}"#,
+        )
+        .unwrap();
+        let Some(TestEvent::RunFinished { summary }) = event.into_test_event() else {
+            panic!("expected RunFinished");
+        };
+        assert_eq!(summary.snapshots_written, 1);
+        assert_eq!(summary.snapshots_updated, 2);
+        assert_eq!(summary.snapshots_obsolete, 4);
+    }
+
+    #[test]
+    fn run_finished_without_snapshots_defaults_to_zero() {
+        let event: VitestEvent = serde_json::from_str(
+            r#"{"type":"run-finished","total":0,"passed":0,"failed":0,"skipped":0,"duration":0}"#,
+        )
+        .unwrap();
+        let Some(TestEvent::RunFinished { summary }) = event.into_test_event() else {
+            panic!("expected RunFinished");
+        };
+        assert_eq!(summary.snapshots_written, 0);
+    }
+
+    #[test]
+    fn coverage_event_collapses_uncovered_lines() {
+        let event: VitestEvent = serde_json::from_str(
+            r#"{"type":"coverage","files":[{"file":"/ws/src/a.ts",
+                "lines":{"covered":7,"total":10},"branches":{"covered":1,"total":2},
+                "functions":{"covered":2,"total":2},"uncovered_lines":[4,5,6,9]}]}"#,
+        )
+        .unwrap();
+        let Some(TestEvent::Coverage { files }) = event.into_test_event() else {
+            panic!("expected Coverage");
+        };
+        let (file, coverage) = &files[0];
+        assert_eq!(file, "/ws/src/a.ts");
+        assert_eq!(coverage.lines.covered, 7);
+        assert_eq!(coverage.uncovered, [(4, 6), (9, 9)]);
+    }
+
+    #[test]
+    fn snapshots_written_event_lists_files() {
+        let event: VitestEvent = serde_json::from_str(
+            r#"{"type":"snapshots-written","files":["/ws/__snapshots__/a.test.ts.snap"]}"#,
+        )
+        .unwrap();
+        assert!(matches!(
+            event.into_test_event(),
+            Some(TestEvent::SnapshotsWritten { files }) if files == ["/ws/__snapshots__/a.test.ts.snap"]
+        ));
+    }
+
     // ── parse_value_string ──────────────────────────────────────────────────
 
     #[test]
@@ -0,0 +1,143 @@
+use ratatui::prelude::*;
+
+/// Split `text` into spans styled by its SGR escape sequences (colors, bold,
+/// …), starting from `base`. Other escape sequences, such as cursor movement,
+/// are dropped.
+pub fn spans(text: &str, base: Style) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    let mut style = base;
+    let mut run = String::new();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\x1b' {
+            run.push(c);
+            continue;
+        }
+        if chars.next_if_eq(&'[').is_none() {
+            continue;
+        }
+        // A CSI sequence: parameters up to a final byte in `@`..=`~`.
+        let mut params = String::new();
+        let mut end = None;
+        for c in chars.by_ref() {
+            if ('@'..='~').contains(&c) {
+                end = Some(c);
+                break;
+            }
+            params.push(c);
+        }
+        if end == Some('m') {
+            if !run.is_empty() {
+                spans.push(Span::styled(std::mem::take(&mut run), style));
+            }
+            style = apply_sgr(style, &params, base);
+        }
+    }
+    if !run.is_empty() {
+        spans.push(Span::styled(run, style));
+    }
+    spans
+}
+
+fn apply_sgr(mut style: Style, params: &str, base: Style) -> Style {
+    // An empty parameter, as in `ESC[m`, means 0.
+    let mut codes = params.split(';').map(|p| p.parse::<u16>().unwrap_or(0));
+    while let Some(code) = codes.next() {
+        style = match code {
+            0 => base,
+            1 => style.add_modifier(Modifier::BOLD),
+            2 => style.add_modifier(Modifier::DIM),
+            3 => style.add_modifier(Modifier::ITALIC),
+            4 => style.add_modifier(Modifier::UNDERLINED),
+            7 => style.add_modifier(Modifier::REVERSED),
+            9 => style.add_modifier(Modifier::CROSSED_OUT),
+            22 => style.remove_modifier(Modifier::BOLD | Modifier::DIM),
+            23 => style.remove_modifier(Modifier::ITALIC),
+            24 => style.remove_modifier(Modifier::UNDERLINED),
+            27 => style.remove_modifier(Modifier::REVERSED),
+            29 => style.remove_modifier(Modifier::CROSSED_OUT),
+            30..=37 => style.fg(standard(code - 30)),
+            38 => extended(&mut codes).map_or(style, |color| style.fg(color)),
+            39 => style.fg(base.fg.unwrap_or(Color::Reset)),
+            40..=47 => style.bg(standard(code - 40)),
+            48 => extended(&mut codes).map_or(style, |color| style.bg(color)),
+            49 => style.bg(base.bg.unwrap_or(Color::Reset)),
+            90..=97 => style.fg(standard(code - 90 + 8)),
+            100..=107 => style.bg(standard(code - 100 + 8)),
+            _ => style,
+        };
+    }
+    style
+}
+
+/// The color of a `38;5;n` or `38;2;r;g;b` sequence (or their `48` forms).
+fn extended(codes: &mut impl Iterator<Item = u16>) -> Option<Color> {
+    let mut byte = || codes.next().map(|c| c.min(255) as u8);
+    match byte()? {
+        5 => Some(Color::Indexed(byte()?)),
+        2 => Some(Color::Rgb(byte()?, byte()?, byte()?)),
+        _ => None,
+    }
+}
+
+/// The 16 standard terminal colors, normal then bright.
+fn standard(index: u16) -> Color {
+    const COLORS: [Color; 16] = [
+        Color::Black,
+        Color::Red,
+        Color::Green,
+        Color::Yellow,
+        Color::Blue,
+        Color::Magenta,
+        Color::Cyan,
+        Color::Gray,
+        Color::DarkGray,
+        Color::LightRed,
+        Color::LightGreen,
+        Color::LightYellow,
+        Color::LightBlue,
+        Color::LightMagenta,
+        Color::LightCyan,
+        Color::White,
+    ];
+    COLORS[index as usize]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn styled(text: &str) -> Vec<(String, Style)> {
+        spans(text, Style::default())
+            .into_iter()
+            .map(|s| (s.content.into_owned(), s.style))
+            .collect()
+    }
+
+    #[test]
+    fn applies_colors_and_resets() {
+        assert_eq!(
+            styled("\x1b[1;31mFAIL\x1b[0m src/a.test.ts \x1b[38;5;208mslow\x1b[m"),
+            [
+                (
+                    "FAIL".to_string(),
+                    Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)
+                ),
+                (" src/a.test.ts ".to_string(), Style::default()),
+                ("slow".to_string(), Style::default().fg(Color::Indexed(208))),
+            ]
+        );
+        assert_eq!(
+            styled("\x1b[48;2;1;2;3mx")[0].1,
+            Style::default().bg(Color::Rgb(1, 2, 3))
+        );
+    }
+
+    #[test]
+    fn drops_other_escape_sequences() {
+        assert_eq!(
+            styled("\x1b[2K\x1b[1Gdone"),
+            [("done".to_string(), Style::default())]
+        );
+    }
+}
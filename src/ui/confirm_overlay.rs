@@ -0,0 +1,51 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+};
+
+use super::theme::Theme;
+use crate::app::SnapshotUpdate;
+
+pub fn draw(frame: &mut Frame, update: &SnapshotUpdate, theme: &Theme) {
+    let area = frame.area();
+    let width = 60.min(area.width);
+    let height = 5.min(area.height);
+    let popup = Rect {
+        x: area.x + area.width.saturating_sub(width) / 2,
+        y: area.y + area.height.saturating_sub(height) / 2,
+        width,
+        height,
+    };
+
+    frame.render_widget(Clear, popup);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.peach))
+        .style(Style::default().bg(theme.mantle))
+        .title(Span::styled(
+            " Update snapshots ",
+            Style::default().fg(theme.peach).bold(),
+        ))
+        .title_bottom(Span::styled(
+            " y/Enter confirm · any other key cancels ",
+            Style::default().fg(theme.overlay0),
+        ))
+        .title_alignment(Alignment::Center);
+
+    let text = Line::from(vec![
+        Span::styled(
+            "Rewrite failing snapshots for ",
+            Style::default().fg(theme.text),
+        ),
+        Span::styled(update.label.clone(), Style::default().fg(theme.blue).bold()),
+        Span::styled("?", Style::default().fg(theme.text)),
+    ]);
+    frame.render_widget(
+        Paragraph::new(text)
+            .block(block)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true }),
+        popup,
+    );
+}
@@ -0,0 +1,96 @@
+use std::path::Path;
+
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Paragraph},
+};
+
+use super::{test_tree::coverage_color, theme::Theme};
+use crate::app::{
+    App,
+    coverage::{FileCoverage, Metric},
+};
+
+/// Most uncovered ranges listed before the rest are summarised.
+const MAX_RANGES: usize = 8;
+
+/// Coverage of the source file behind the selected test file, if any.
+pub fn selected(app: &App) -> Option<(&Path, &FileCoverage)> {
+    if !app.coverage_enabled {
+        return None;
+    }
+    let (test_file, _) = app.tree.file_and_full_name(app.selected_node_id()?)?;
+    app.coverage.source_for_test(test_file)
+}
+
+/// Rows the panel needs, borders included.
+pub fn height(coverage: &FileCoverage) -> u16 {
+    let ranges = coverage.uncovered.len().clamp(1, MAX_RANGES + 1);
+    ranges as u16 + 4
+}
+
+pub fn draw(frame: &mut Frame, source: &Path, coverage: &FileCoverage, theme: &Theme, area: Rect) {
+    let block = Block::default()
+        .title(format!(" Coverage · {} ", source.display()))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.surface2));
+
+    let mut summary = Vec::new();
+    for (label, metric) in [
+        ("Lines", coverage.lines),
+        ("Branches", coverage.branches),
+        ("Functions", coverage.functions),
+    ] {
+        summary.extend(metric_spans(label, metric, theme));
+    }
+
+    let mut lines = vec![Line::from(summary), Line::raw("")];
+    if coverage.uncovered.is_empty() {
+        lines.push(Line::styled(
+            " All lines covered",
+            Style::default().fg(theme.green),
+        ));
+    } else {
+        lines.push(Line::styled(
+            " Uncovered lines",
+            Style::default().fg(theme.subtext0).bold(),
+        ));
+        let shown = if coverage.uncovered.len() > MAX_RANGES + 1 {
+            MAX_RANGES
+        } else {
+            coverage.uncovered.len()
+        };
+        for &(first, last) in &coverage.uncovered[..shown] {
+            let range = if first == last {
+                format!("   {first}")
+            } else {
+                format!("   {first}–{last}")
+            };
+            lines.push(Line::styled(range, Style::default().fg(theme.red)));
+        }
+        let hidden = coverage.uncovered.len() - shown;
+        if hidden > 0 {
+            lines.push(Line::styled(
+                format!("   … {hidden} more"),
+                Style::default().fg(theme.overlay0),
+            ));
+        }
+    }
+
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+fn metric_spans(label: &str, metric: Metric, theme: &Theme) -> Vec<Span<'static>> {
+    let pct = metric.pct();
+    vec![
+        Span::styled(format!(" {label} "), Style::default().fg(theme.subtext0)),
+        Span::styled(
+            format!("{pct:.0}%"),
+            Style::default().fg(coverage_color(pct, theme)).bold(),
+        ),
+        Span::styled(
+            format!(" ({}/{})  ", metric.covered, metric.total),
+            Style::default().fg(theme.overlay0),
+        ),
+    ]
+}
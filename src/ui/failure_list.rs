@@ -3,15 +3,15 @@ use ratatui::{
     widgets::{Block, Borders, List, ListItem},
 };
 
-use super::theme;
 use crate::app::{App, Panel};
 
 pub fn draw(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = &app.theme;
     let focused = app.active_panel == Panel::FailedList;
     let border_style = if focused {
-        Style::default().fg(theme::BLUE)
+        Style::default().fg(theme.blue)
     } else {
-        Style::default().fg(theme::SURFACE2)
+        Style::default().fg(theme.surface2)
     };
 
     let block = Block::default()
@@ -34,12 +34,12 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect) {
             let selected = absolute_i == app.selected_failed_index && focused;
 
             let item = ListItem::new(Line::from(vec![
-                Span::styled("✘ ", Style::default().fg(theme::RED)),
-                Span::styled(&node.name, Style::default().fg(theme::RED)),
+                Span::styled("✘ ", Style::default().fg(theme.red)),
+                Span::styled(&node.name, Style::default().fg(theme.red)),
             ]));
 
             if selected {
-                item.style(Style::default().bg(theme::SURFACE1))
+                item.style(Style::default().bg(theme.surface1))
             } else {
                 item
             }
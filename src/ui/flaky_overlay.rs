@@ -0,0 +1,74 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Clear, List, ListItem, ListState},
+};
+
+use crate::app::App;
+
+pub fn draw(frame: &mut Frame, app: &App) {
+    let theme = &app.theme;
+    let area = frame.area();
+    let width = (area.width * 3 / 4).max(40).min(area.width);
+    let height = (area.height * 2 / 3).max(8).min(area.height);
+    let popup = Rect {
+        x: area.x + area.width.saturating_sub(width) / 2,
+        y: area.y + area.height.saturating_sub(height) / 2,
+        width,
+        height,
+    };
+
+    frame.render_widget(Clear, popup);
+
+    let flaky = app.history.flaky_tests();
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.yellow))
+        .style(Style::default().bg(theme.mantle))
+        .title(Span::styled(
+            format!(" Flaky tests ({}) ", flaky.len()),
+            Style::default().fg(theme.yellow).bold(),
+        ))
+        .title_bottom(Span::styled(
+            " j/k move · Enter reveal · Esc close ",
+            Style::default().fg(theme.overlay0),
+        ))
+        .title_alignment(Alignment::Center);
+
+    if flaky.is_empty() {
+        let inner = block.inner(popup);
+        frame.render_widget(block, popup);
+        frame.render_widget(
+            Line::styled(
+                " No flaky tests recorded yet.",
+                Style::default().fg(theme.subtext0),
+            ),
+            inner,
+        );
+        return;
+    }
+
+    let items: Vec<ListItem> = flaky
+        .iter()
+        .map(|(key, flakiness)| {
+            let (file, name) = key.split_once("::").unwrap_or(("", key));
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!(" {:>3.0}% ", flakiness.score() * 100.0),
+                    Style::default().fg(theme.yellow).bold(),
+                ),
+                Span::styled(
+                    format!("{:>3}/{:<3} ", flakiness.flips, flakiness.comparisons),
+                    Style::default().fg(theme.overlay0),
+                ),
+                Span::styled(name.to_string(), Style::default().fg(theme.text)),
+                Span::styled(format!("  {file}"), Style::default().fg(theme.subtext0)),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(Style::default().bg(theme.surface1));
+    let mut state = ListState::default().with_selected(Some(app.flaky_selected));
+    frame.render_stateful_widget(list, popup, &mut state);
+}
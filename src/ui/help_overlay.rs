@@ -3,55 +3,80 @@ use ratatui::{
     widgets::{Block, Borders, Clear, Paragraph},
 };
 
-use super::theme;
+use super::theme::Theme;
+use crate::app::keymap::Keymap;
 
-/// Keybinding rows: (key label, description).
-const BINDINGS: &[(&str, &str)] = &[
-    // ── Navigation ──────────────────────────────────────────────────────────
-    ("Navigation", ""),
-    ("k / ↑", "move up"),
-    ("j / ↓", "move down"),
-    ("Ctrl+u / PgUp", "scroll up half-page"),
-    ("Ctrl+d / PgDn", "scroll down half-page"),
-    ("h / ←", "collapse"),
-    ("l / →", "expand"),
-    ("H", "collapse all"),
-    ("L", "expand all"),
-    ("g / Home", "jump to start"),
-    ("G / End", "jump to end"),
-    ("{ / }", "prev / next file"),
-    ("[ / ]", "prev / next error"),
-    ("Tab / Shift+Tab", "cycle panels"),
-    ("", ""),
-    // ── Actions ─────────────────────────────────────────────────────────────
-    ("Actions", ""),
-    ("Enter", "run selected test / suite"),
-    ("a", "run filtered files (or all)"),
-    ("A", "run all files"),
-    ("r", "rerun failed"),
-    ("w", "toggle watch mode"),
-    ("e", "open in editor"),
-    ("/", "filter files"),
-    ("f", "filter by current file"),
-    ("F", "filter by current directory"),
-    ("y", "yank file path"),
-    ("Y", "yank failure location"),
-    ("c", "copy output"),
-    ("?", "toggle this help"),
-    ("q / Ctrl+c", "quit"),
-];
+/// Draw the key reference, generated from the effective keymap so configured
+/// `[keys]` show up as they are.
+pub fn draw(frame: &mut Frame, keymap: &Keymap, theme: &Theme) {
+    let sections = keymap.help();
+    let rows = sections.iter().flat_map(|(_, rows)| rows);
+    let key_width = rows
+        .clone()
+        .map(|(keys, _)| keys.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max(16);
+    let desc_width = rows
+        .map(|(_, desc)| desc.chars().count())
+        .max()
+        .unwrap_or(0);
+    // Key col + sep (2) + desc col + padding (3)
+    let column_width = (key_width + desc_width + 5) as u16;
 
-pub fn draw(frame: &mut Frame) {
-    // Width: key col (18) + sep (3) + desc col (28) + borders (2) + padding (2) = 53
-    let width: u16 = 55;
-    // Each section header emits an extra blank line below it for breathing room.
-    let header_count = BINDINGS
+    // One block of lines per section: header, blank line, rows.
+    let blocks: Vec<Vec<Line>> = sections
         .iter()
-        .filter(|&&(k, d)| !k.is_empty() && d.is_empty())
-        .count() as u16;
-    let height: u16 = BINDINGS.len() as u16 + header_count + 2; // +2 for top/bottom border
+        .map(|(section, rows)| {
+            let mut lines = vec![
+                Line::from(Span::styled(
+                    format!(" {}", section.title()),
+                    Style::default().fg(theme.overlay0).bold(),
+                )),
+                Line::raw(""),
+            ];
+            lines.extend(rows.iter().map(|(keys, desc)| {
+                Line::from(vec![
+                    Span::styled(
+                        format!(" {:<key_width$}", keys),
+                        Style::default().fg(theme.blue),
+                    ),
+                    Span::styled("  ", Style::default()),
+                    Span::raw(*desc),
+                ])
+            }));
+            lines
+        })
+        .collect();
 
+    // Sections are separated by a blank line. When one column doesn't fit
+    // the terminal, the sections are split over two side by side.
     let area = frame.area();
+    let total: usize = blocks
+        .iter()
+        .map(|b| b.len() + 1)
+        .sum::<usize>()
+        .saturating_sub(1);
+    let columns = if total + 2 > area.height as usize && blocks.len() > 1 {
+        let mut first = Vec::new();
+        let mut second = Vec::new();
+        let mut height = 0;
+        for block in blocks {
+            if height < total / 2 {
+                height += block.len() + 1;
+                first.push(block);
+            } else {
+                second.push(block);
+            }
+        }
+        vec![join_blocks(first), join_blocks(second)]
+    } else {
+        vec![join_blocks(blocks)]
+    };
+
+    let width = column_width * columns.len() as u16 + 2; // +2 for left/right border
+    let height = columns.iter().map(Vec::len).max().unwrap_or(0) as u16 + 2; // +2 for top/bottom border
+
     let x = area.x + area.width.saturating_sub(width) / 2;
     let y = area.y + area.height.saturating_sub(height) / 2;
     let popup = Rect {
@@ -65,42 +90,32 @@ pub fn draw(frame: &mut Frame) {
 
     let block = Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(theme::BLUE))
-        .style(Style::default().bg(theme::MANTLE))
+        .border_style(Style::default().fg(theme.blue))
+        .style(Style::default().bg(theme.mantle))
         .title(Span::styled(
             " Help ",
-            Style::default().fg(theme::BLUE).bold(),
+            Style::default().fg(theme.blue).bold(),
         ))
         .title_alignment(Alignment::Center);
 
     let inner = block.inner(popup);
     frame.render_widget(block, popup);
 
-    let lines: Vec<Line> = BINDINGS
-        .iter()
-        .flat_map(|&(key, desc)| {
-            if desc.is_empty() && !key.is_empty() {
-                // Section header — emit the title then a blank line below for padding.
-                vec![
-                    Line::from(Span::styled(
-                        format!(" {}", key),
-                        Style::default().fg(theme::OVERLAY0).bold(),
-                    )),
-                    Line::raw(""),
-                ]
-            } else if key.is_empty() {
-                // Explicit spacer row between sections.
-                vec![Line::raw("")]
-            } else {
-                vec![Line::from(vec![
-                    Span::styled(format!(" {:<16}", key), Style::default().fg(theme::BLUE)),
-                    Span::styled("  ", Style::default()),
-                    Span::raw(desc),
-                ])]
-            }
-        })
-        .collect();
+    let column_areas =
+        Layout::horizontal(vec![Constraint::Length(column_width); columns.len()]).split(inner);
+    for (lines, column_area) in columns.into_iter().zip(column_areas.iter()) {
+        let paragraph = Paragraph::new(lines).style(Style::default().bg(theme.mantle));
+        frame.render_widget(paragraph, *column_area);
+    }
+}
 
-    let paragraph = Paragraph::new(lines).style(Style::default().bg(theme::MANTLE));
-    frame.render_widget(paragraph, inner);
+fn join_blocks(blocks: Vec<Vec<Line<'_>>>) -> Vec<Line<'_>> {
+    let mut lines = Vec::new();
+    for (i, block) in blocks.into_iter().enumerate() {
+        if i > 0 {
+            lines.push(Line::raw(""));
+        }
+        lines.extend(block);
+    }
+    lines
 }
@@ -2,10 +2,14 @@ use ratatui::prelude::*;
 
 use crate::app::App;
 
+use super::confirm_overlay;
+use super::coverage_panel;
 use super::failure_list;
+use super::flaky_overlay;
 use super::help_overlay;
 use super::notifications;
 use super::output_panel;
+use super::runner_log_overlay;
 use super::search_box;
 use super::status_bar;
 use super::test_tree;
@@ -13,14 +17,71 @@ use super::test_tree;
 pub fn draw(frame: &mut Frame, app: &mut App) {
     let [main_area, status_area] =
         Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).areas(frame.area());
+    app.areas.screen = frame.area();
 
-    let [left_area, right_area] =
-        Layout::horizontal([Constraint::Percentage(40), Constraint::Percentage(60)])
-            .areas(main_area);
+    let layout = app.layout;
+    let output_region = if layout.zoomed {
+        app.areas.tree = Rect::default();
+        app.areas.failed = Rect::default();
+        main_area
+    } else {
+        let split = [
+            Constraint::Percentage(layout.tests_pct),
+            Constraint::Percentage(100 - layout.tests_pct),
+        ];
+        let [tests_area, output_region] = if layout.vertical(frame.area().width) {
+            Layout::vertical(split).areas(main_area)
+        } else {
+            Layout::horizontal(split).areas(main_area)
+        };
+        let [tree_area, failed_area] = Layout::vertical([
+            Constraint::Percentage(layout.tree_pct),
+            Constraint::Percentage(100 - layout.tree_pct),
+        ])
+        .areas(tests_area);
+        draw_test_panels(frame, app, tree_area, failed_area);
+        output_region
+    };
+
+    // While coverage is on, the selected file's source coverage sits below the output.
+    let output_area = match coverage_panel::selected(app) {
+        Some((source, coverage)) => {
+            let [output_area, coverage_area] = Layout::vertical([
+                Constraint::Min(5),
+                Constraint::Length(coverage_panel::height(coverage)),
+            ])
+            .areas(output_region);
+            coverage_panel::draw(frame, source, coverage, &app.theme, coverage_area);
+            output_area
+        }
+        None => output_region,
+    };
+
+    app.areas.output = output_area;
+    output_panel::draw(frame, app, output_area);
+
+    status_bar::draw(frame, app, status_area);
+    notifications::draw(frame, app);
+
+    if app.show_flaky {
+        flaky_overlay::draw(frame, app);
+    }
+
+    if app.show_runner_log {
+        runner_log_overlay::draw(frame, app);
+    }
+
+    if let Some(ref update) = app.confirm_snapshot_update {
+        confirm_overlay::draw(frame, update, &app.theme);
+    }
 
-    let [tree_area, failed_area] =
-        Layout::vertical([Constraint::Percentage(60), Constraint::Percentage(40)]).areas(left_area);
+    if app.show_help {
+        help_overlay::draw(frame, &app.keymap, &app.theme);
+    }
+}
 
+/// The tree (under the filter box while filtering) and the failure list.
+fn draw_test_panels(frame: &mut Frame, app: &mut App, tree_area: Rect, failed_area: Rect) {
     app.failed_viewport_height = failed_area.height.saturating_sub(2) as usize;
 
     if app.filter_active || !app.filter.value().is_empty() {
@@ -29,22 +90,23 @@ pub fn draw(frame: &mut Frame, app: &mut App) {
 
         app.tree_viewport_height = filtered_tree_area.height.saturating_sub(2) as usize;
 
-        search_box::draw(frame, &app.filter, app.filter_active, search_area);
+        search_box::draw(
+            frame,
+            &app.filter,
+            app.filter_active,
+            app.filter_flat,
+            &app.theme,
+            search_area,
+        );
+        app.areas.tree = filtered_tree_area;
         test_tree::draw(frame, app, filtered_tree_area);
     } else {
         app.tree_viewport_height = tree_area.height.saturating_sub(2) as usize;
 
+        app.areas.tree = tree_area;
         test_tree::draw(frame, app, tree_area);
     }
 
+    app.areas.failed = failed_area;
     failure_list::draw(frame, app, failed_area);
-
-    app.output_scroll_offset = output_panel::draw(frame, app, app.output_scroll_offset, right_area);
-
-    status_bar::draw(frame, app, status_area);
-    notifications::draw(frame, app);
-
-    if app.show_help {
-        help_overlay::draw(frame);
-    }
 }
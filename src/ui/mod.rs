@@ -1,10 +1,16 @@
+mod ansi;
+mod confirm_overlay;
+mod coverage_panel;
 mod failure_list;
+mod flaky_overlay;
 mod help_overlay;
 mod layout;
 pub mod notifications;
 mod output_panel;
+mod runner_log_overlay;
 mod search_box;
 mod status_bar;
+mod syntax;
 mod test_tree;
 pub mod theme;
 
@@ -5,9 +5,8 @@ use ratatui::{
 
 use crate::app::{App, NotificationKind};
 
-use super::theme;
-
 pub fn draw(frame: &mut Frame, app: &App) {
+    let theme = &app.theme;
     let Some(notification) = app.notifier.recent() else {
         return;
     };
@@ -31,8 +30,8 @@ pub fn draw(frame: &mut Frame, app: &App) {
     };
 
     let color = match notification.kind {
-        NotificationKind::Info => theme::BLUE,
-        NotificationKind::Error => theme::RED,
+        NotificationKind::Info => theme.blue,
+        NotificationKind::Error => theme.red,
     };
 
     let block = Block::default()
@@ -41,7 +40,7 @@ pub fn draw(frame: &mut Frame, app: &App) {
 
     let paragraph = Paragraph::new(text.as_str())
         .block(block)
-        .style(Style::default().fg(color).bg(theme::SURFACE0));
+        .style(Style::default().fg(color).bg(theme.surface0));
 
     frame.render_widget(Clear, area);
     frame.render_widget(paragraph, area);
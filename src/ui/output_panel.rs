@@ -1,42 +1,112 @@
+use std::ops::Range;
+
 use ratatui::{
     prelude::*,
     widgets::{Block, Borders, Gauge, Paragraph},
 };
 
-use super::theme;
-use crate::app::{App, Panel};
-use crate::models::{NodeKind, TestStatus};
+use super::{syntax, theme::Theme};
+use crate::app::search::{Reveal, find_matches};
+use crate::app::{Action, App, Panel};
+use crate::models::{ConsoleLine, ConsoleStream, NodeKind, TestStatus};
 
-pub fn draw(frame: &mut Frame, app: &App, scroll_offset: u16, area: Rect) -> u16 {
+pub fn draw(frame: &mut Frame, app: &mut App, area: Rect) {
+    let theme = &app.theme;
     let focused = app.active_panel == Panel::Output;
     let border_style = if focused {
-        Style::default().fg(theme::BLUE)
+        Style::default().fg(theme.blue)
     } else {
-        Style::default().fg(theme::SURFACE2)
+        Style::default().fg(theme.surface2)
     };
 
-    let block = Block::default()
-        .title(" Output ")
+    let mut block = Block::default()
         .borders(Borders::ALL)
         .border_style(border_style);
 
-    let inner = block.inner(area);
-    frame.render_widget(block, area);
-
     // Split inner area: progress bar on top, output content below
     let [progress_area, content_area] =
-        Layout::vertical([Constraint::Length(1), Constraint::Min(1)]).areas(inner);
+        Layout::vertical([Constraint::Length(1), Constraint::Min(1)]).areas(block.inner(area));
+
+    let content = build_content(app);
+
+    // Wrapped rows before each line, and in total, to scroll by.
+    let width = content_area.width.max(1) as usize;
+    let mut row_starts = Vec::with_capacity(content.lines.len());
+    let mut rows = 0;
+    for line in &content.lines {
+        row_starts.push(rows);
+        rows += line.width().div_ceil(width).max(1);
+    }
+
+    let search = &app.output_search;
+    let hits = find_hits(&content, search.query());
+    let mut current = search.current.min(hits.len().saturating_sub(1));
+    let mut scroll = app.output_scroll_offset as usize;
+    if let Some(reveal) = search.reveal
+        && !hits.is_empty()
+    {
+        if reveal == Reveal::FromTop {
+            current = hits
+                .iter()
+                .position(|(line, _)| row_starts[*line] >= scroll)
+                .unwrap_or(0);
+        }
+        let row = row_starts[hits[current].0];
+        let height = content_area.height as usize;
+        if row < scroll || row >= scroll + height {
+            // A couple of lines above the match for context.
+            scroll = row.saturating_sub(2);
+        }
+    }
+    let max_scroll = rows.saturating_sub(content_area.height as usize);
+    let effective_scroll = scroll.min(max_scroll) as u16;
+
+    let mut title = vec![Span::raw(" Output ")];
+    if search.active || !search.query().is_empty() {
+        title.push(Span::styled(
+            format!("/{}", search.query()),
+            Style::default().fg(theme.text),
+        ));
+        let (counter, color) = match hits.len() {
+            0 if search.query().is_empty() => (String::new(), theme.overlay0),
+            0 => (" no matches ".to_string(), theme.red),
+            n => (format!(" {}/{n} ", current + 1), theme.overlay0),
+        };
+        title.push(Span::styled(counter, Style::default().fg(color)));
+        if search.active {
+            let x = area.x + 1 + " Output /".len() as u16 + search.input.visual_cursor() as u16;
+            frame.set_cursor_position((x.min(area.right().saturating_sub(2)), area.y));
+        }
+    }
+    block = block.title(Line::from(title));
+    frame.render_widget(block, area);
 
     // Progress bar
     let percent = (app.progress_percent() * 100.0).min(100.0) as u16;
     let gauge = Gauge::default()
-        .gauge_style(Style::default().fg(theme::GREEN).bg(theme::SURFACE0))
+        .gauge_style(Style::default().fg(theme.green).bg(theme.surface0))
         .percent(percent)
         .label("");
     frame.render_widget(gauge, progress_area);
 
-    // Output panel: show selected node's failure info + console output
-    let content = if let Some(node_id) = app.selected_node_id() {
+    let content = highlight_hits(content, &hits, current, theme);
+    let paragraph = Paragraph::new(content)
+        .wrap(ratatui::widgets::Wrap { trim: false })
+        .scroll((effective_scroll, 0));
+
+    frame.render_widget(paragraph, content_area);
+
+    let count = hits.len();
+    app.output_scroll_offset = effective_scroll;
+    app.output_search.count = count;
+    app.output_search.current = current;
+    app.output_search.reveal = None;
+}
+
+/// The selected node's failure info and console output.
+fn build_content(app: &App) -> Text<'_> {
+    let theme = &app.theme;
+    if let Some(node_id) = app.selected_node_id() {
         if let Some(node) = app.tree.get(node_id) {
             let mut lines: Vec<Line> = Vec::new();
             let mut breadcrumbs = Vec::new();
@@ -55,7 +125,7 @@ pub fn draw(frame: &mut Frame, app: &App, scroll_offset: u16, area: Rect) -> u16
             if !breadcrumbs.is_empty() {
                 lines.push(Line::from(vec![Span::styled(
                     breadcrumbs.join(" > "),
-                    Style::default().fg(theme::OVERLAY0).bold(),
+                    Style::default().fg(theme.overlay0).bold(),
                 )]));
                 lines.push(Line::from(""));
             }
@@ -63,18 +133,30 @@ pub fn draw(frame: &mut Frame, app: &App, scroll_offset: u16, area: Rect) -> u16
             if node.kind == NodeKind::Test {
                 if node.status == TestStatus::Failed {
                     if let Some(ref result) = node.result {
-                        if let Some(ref failure) = result.failure {
-                            let failure_text = build_failure_text(failure, &node.name);
+                        if !result.failures.is_empty() {
+                            let failure_text =
+                                build_failure_text(&result.failures, &node.name, theme);
                             lines.extend(failure_text.lines);
+                            if result.failures.iter().any(|f| !f.attachments.is_empty())
+                                && let Some(key) = app.keymap.key_for(&Action::OpenAttachment)
+                            {
+                                lines.push(Line::from(Span::styled(
+                                    format!("  {key}: open attachment"),
+                                    Style::default().fg(theme.overlay0),
+                                )));
+                            }
                         } else {
                             lines.push(Line::from("No failure output available."));
                         }
                     }
                 } else {
                     lines.push(Line::from(vec![
-                        Span::styled(&node.name, Style::default().fg(node.status.color())),
+                        Span::styled(&node.name, Style::default().fg(node.status.color(theme))),
                         Span::raw(" "),
-                        Span::styled(node.status.icon(), Style::default().fg(node.status.color())),
+                        Span::styled(
+                            node.status.icon(),
+                            Style::default().fg(node.status.color(theme)),
+                        ),
                     ]));
                 }
             } else {
@@ -85,32 +167,32 @@ pub fn draw(frame: &mut Frame, app: &App, scroll_offset: u16, area: Rect) -> u16
                     lines.push(Line::from(vec![
                         Span::styled(
                             TestStatus::Passed.icon(),
-                            Style::default().fg(TestStatus::Passed.color()),
+                            Style::default().fg(TestStatus::Passed.color(theme)),
                         ),
                         Span::raw(" "),
                         Span::styled(
                             format!("{}", p),
-                            Style::default().fg(TestStatus::Passed.color()),
+                            Style::default().fg(TestStatus::Passed.color(theme)),
                         ),
                         Span::raw("   "),
                         Span::styled(
                             TestStatus::Failed.icon(),
-                            Style::default().fg(TestStatus::Failed.color()),
+                            Style::default().fg(TestStatus::Failed.color(theme)),
                         ),
                         Span::raw(" "),
                         Span::styled(
                             format!("{}", f),
-                            Style::default().fg(TestStatus::Failed.color()),
+                            Style::default().fg(TestStatus::Failed.color(theme)),
                         ),
                         Span::raw("   "),
                         Span::styled(
                             TestStatus::Skipped.icon(),
-                            Style::default().fg(TestStatus::Skipped.color()),
+                            Style::default().fg(TestStatus::Skipped.color(theme)),
                         ),
                         Span::raw(" "),
                         Span::styled(
                             format!("{}", s),
-                            Style::default().fg(TestStatus::Skipped.color()),
+                            Style::default().fg(TestStatus::Skipped.color(theme)),
                         ),
                     ]));
                 }
@@ -120,35 +202,26 @@ pub fn draw(frame: &mut Frame, app: &App, scroll_offset: u16, area: Rect) -> u16
                 for (i, fid) in failed_ids.iter().enumerate() {
                     if let Some(failed_node) = app.tree.get(*fid)
                         && let Some(ref result) = failed_node.result
-                        && let Some(ref failure) = result.failure
+                        && !result.failures.is_empty()
                     {
                         if i == 0 {
                             lines.push(Line::from(""));
                         }
-                        let failure_text = build_failure_text(failure, &failed_node.name);
+                        let failure_text =
+                            build_failure_text(&result.failures, &failed_node.name, theme);
                         lines.extend(failure_text.lines);
                         lines.push(Line::from(""));
                     }
                 }
             }
 
-            // Console output from the file this test belongs to
-            let console_output = get_file_console_output(&app.tree, node_id);
-            if !console_output.is_empty() {
+            // The test's own console output, or its file's. A quiet test in a
+            // file that logged still gets the heading, for the hint to switch.
+            let whole_file = node.kind != NodeKind::Test || app.show_file_console;
+            let console_output = app.tree.console_output(node_id, app.show_file_console);
+            if !app.tree.console_output(node_id, true).is_empty() {
                 lines.push(Line::from(""));
-                lines.push(Line::from(Span::styled(
-                    "━━ Console Output ━━",
-                    Style::default().fg(theme::YELLOW),
-                )));
-                lines.push(Line::from(""));
-                for log_line in console_output {
-                    for sub_line in log_line.lines() {
-                        lines.push(Line::from(Span::styled(
-                            sub_line.to_string(),
-                            Style::default().fg(theme::SUBTEXT0),
-                        )));
-                    }
-                }
+                lines.extend(console_lines(app, console_output, whole_file, node.kind));
             }
 
             Text::from(lines)
@@ -157,37 +230,135 @@ pub fn draw(frame: &mut Frame, app: &App, scroll_offset: u16, area: Rect) -> u16
         }
     } else {
         Text::from("Select a test to view its output.")
-    };
-
-    let max_scroll = content
-        .height()
-        .saturating_sub(content_area.height as usize) as u16;
-    let effective_scroll = scroll_offset.min(max_scroll);
+    }
+}
 
-    let paragraph = Paragraph::new(content)
-        .wrap(ratatui::widgets::Wrap { trim: false })
-        .scroll((effective_scroll, 0));
+/// Every match of `query` in the content: (line index, byte range in the line's text).
+fn find_hits(content: &Text<'_>, query: &str) -> Vec<(usize, Range<usize>)> {
+    if query.is_empty() {
+        return Vec::new();
+    }
+    content
+        .lines
+        .iter()
+        .enumerate()
+        .flat_map(|(index, line)| {
+            let text: String = line.spans.iter().map(|s| s.content.as_ref()).collect();
+            find_matches(&text, query)
+                .into_iter()
+                .map(move |range| (index, range))
+        })
+        .collect()
+}
 
-    frame.render_widget(paragraph, content_area);
+/// Restyle the matched text, the current match (an index into `hits`) in a
+/// color of its own. Matches can cross span boundaries.
+fn highlight_hits<'a>(
+    mut content: Text<'a>,
+    hits: &[(usize, Range<usize>)],
+    current: usize,
+    theme: &Theme,
+) -> Text<'a> {
+    for (index, (line_index, _)) in hits.iter().enumerate() {
+        // Each line is rebuilt once, with all of its matches.
+        if index > 0 && hits[index - 1].0 == *line_index {
+            continue;
+        }
+        let matches: Vec<(&Range<usize>, bool)> = hits
+            .iter()
+            .enumerate()
+            .skip(index)
+            .take_while(|(_, (line, _))| line == line_index)
+            .map(|(i, (_, range))| (range, i == current))
+            .collect();
 
-    effective_scroll
+        let line = &mut content.lines[*line_index];
+        let mut spans = Vec::with_capacity(line.spans.len() + matches.len() * 2);
+        let mut offset = 0;
+        for span in std::mem::take(&mut line.spans) {
+            let text = span.content.as_ref();
+            let end = offset + text.len();
+            let mut cut = 0;
+            for (range, is_current) in matches
+                .iter()
+                .filter(|(range, _)| range.start < end && range.end > offset)
+            {
+                let start = range.start.max(offset) - offset;
+                let stop = range.end.min(end) - offset;
+                if start > cut {
+                    spans.push(Span::styled(text[cut..start].to_string(), span.style));
+                }
+                let bg = if *is_current {
+                    theme.peach
+                } else {
+                    theme.yellow
+                };
+                spans.push(Span::styled(
+                    text[start..stop].to_string(),
+                    span.style.fg(theme.base).bg(bg),
+                ));
+                cut = stop;
+            }
+            if cut < text.len() {
+                spans.push(Span::styled(text[cut..].to_string(), span.style));
+            }
+            offset = end;
+        }
+        line.spans = spans;
+    }
+    content
 }
 
-/// Walk up the tree to find the ancestor file node and return its console output.
-fn get_file_console_output(tree: &crate::models::TestTree, node_id: usize) -> &[String] {
-    let mut current = Some(node_id);
-    while let Some(id) = current {
-        if let Some(node) = tree.get(id) {
-            if node.kind == NodeKind::File {
-                return &node.console_output;
-            }
-            current = node.parent;
+/// The console section: a heading, then each line colored by the stream it
+/// was written to. A test's heading says how to switch between its own
+/// output and its file's.
+fn console_lines<'a>(
+    app: &App,
+    output: &'a [ConsoleLine],
+    whole_file: bool,
+    kind: NodeKind,
+) -> Vec<Line<'a>> {
+    let theme = &app.theme;
+    let title = if whole_file {
+        "━━ Console Output (whole file) ━━"
+    } else {
+        "━━ Console Output ━━"
+    };
+    let mut heading = vec![Span::styled(title, Style::default().fg(theme.yellow))];
+    if kind == NodeKind::Test
+        && let Some(key) = app.keymap.key_for(&Action::ToggleFileConsole)
+    {
+        let other = if whole_file {
+            "this test"
         } else {
-            break;
-        }
+            "whole file"
+        };
+        heading.push(Span::styled(
+            format!("  {key}: {other}"),
+            Style::default().fg(theme.overlay0),
+        ));
     }
 
-    &[]
+    let mut lines = vec![Line::from(heading), Line::from("")];
+    if output.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "No console output.",
+            Style::default().fg(theme.overlay0),
+        )));
+    }
+    for log_line in output {
+        let color = match log_line.stream {
+            ConsoleStream::Stdout => theme.subtext0,
+            ConsoleStream::Stderr => theme.peach,
+        };
+        for sub_line in log_line.text.lines() {
+            lines.push(Line::from(Span::styled(
+                sub_line,
+                Style::default().fg(color),
+            )));
+        }
+    }
+    lines
 }
 
 fn count_descendants(tree: &crate::models::TestTree, node_id: usize) -> (usize, usize, usize) {
@@ -476,100 +647,106 @@ fn push_json_value_lines<'a>(
 }
 
 fn build_failure_text<'a>(
-    failure: &'a crate::models::FailureOutput,
+    failures: &'a [crate::models::FailureOutput],
     test_name: &'a str,
+    theme: &Theme,
 ) -> Text<'a> {
-    let mut lines: Vec<Line> = vec![
-        Line::from(vec![
-            Span::styled("✘ failed: ", Style::default().fg(theme::RED)),
-            Span::styled(test_name, Style::default().fg(theme::RED).bold()),
-        ]),
-        Line::from(""),
-        Line::from(Span::styled(
-            &failure.message,
-            Style::default().fg(theme::TEXT),
-        )),
-        Line::from(""),
-    ];
+    let mut lines: Vec<Line> = vec![Line::from(vec![
+        Span::styled("✘ failed: ", Style::default().fg(theme.red)),
+        Span::styled(test_name, Style::default().fg(theme.red).bold()),
+    ])];
+
+    for (i, failure) in failures.iter().enumerate() {
+        lines.push(Line::from(""));
+        // Number errors only when a test produced more than one (e.g. `expect.soft`).
+        if failures.len() > 1 {
+            lines.push(Line::from(Span::styled(
+                format!("── Error {}/{} ──", i + 1, failures.len()),
+                Style::default().fg(theme.peach).bold(),
+            )));
+        }
+        push_error_lines(&mut lines, failure, theme);
+    }
+
+    Text::from(lines)
+}
+
+/// Message, expected/actual (structured when both sides parse), diff and stack for one error.
+fn push_error_lines<'a>(
+    lines: &mut Vec<Line<'a>>,
+    failure: &'a crate::models::FailureOutput,
+    theme: &Theme,
+) {
+    lines.push(Line::from(Span::styled(
+        &failure.message,
+        Style::default().fg(theme.text),
+    )));
+    lines.push(Line::from(""));
 
     // Expected / Actual
     match (&failure.expected_parsed, &failure.actual_parsed) {
         (Some(serde_json::Value::Object(exp_map)), Some(serde_json::Value::Object(act_map))) => {
             lines.push(Line::from(Span::styled(
                 "  Expected:",
-                Style::default().fg(theme::GREEN),
+                Style::default().fg(theme.green),
             )));
             push_json_object_lines(
-                &mut lines,
+                lines,
                 exp_map,
                 Some(act_map),
-                theme::GREEN,
-                theme::YELLOW,
+                theme.green,
+                theme.yellow,
                 "  ",
             );
             lines.push(Line::from(""));
             lines.push(Line::from(Span::styled(
                 "  Actual:",
-                Style::default().fg(theme::RED),
+                Style::default().fg(theme.red),
             )));
-            push_json_object_lines(
-                &mut lines,
-                act_map,
-                Some(exp_map),
-                theme::RED,
-                theme::YELLOW,
-                "  ",
-            );
+            push_json_object_lines(lines, act_map, Some(exp_map), theme.red, theme.yellow, "  ");
         }
         (Some(serde_json::Value::Array(exp_arr)), Some(serde_json::Value::Array(act_arr))) => {
             lines.push(Line::from(Span::styled(
                 "  Expected:",
-                Style::default().fg(theme::GREEN),
+                Style::default().fg(theme.green),
             )));
             push_json_array_lines(
-                &mut lines,
+                lines,
                 exp_arr,
                 Some(act_arr),
-                theme::GREEN,
-                theme::YELLOW,
+                theme.green,
+                theme.yellow,
                 "  ",
             );
             lines.push(Line::from(""));
             lines.push(Line::from(Span::styled(
                 "  Actual:",
-                Style::default().fg(theme::RED),
+                Style::default().fg(theme.red),
             )));
-            push_json_array_lines(
-                &mut lines,
-                act_arr,
-                Some(exp_arr),
-                theme::RED,
-                theme::YELLOW,
-                "  ",
-            );
+            push_json_array_lines(lines, act_arr, Some(exp_arr), theme.red, theme.yellow, "  ");
         }
         _ => {
             if let Some(expected) = failure.expected.as_deref() {
                 lines.push(Line::from(Span::styled(
                     "  Expected:",
-                    Style::default().fg(theme::GREEN),
+                    Style::default().fg(theme.green),
                 )));
                 for text_line in expected.lines() {
                     lines.push(Line::from(Span::styled(
                         format!("    {text_line}"),
-                        Style::default().fg(theme::GREEN),
+                        Style::default().fg(theme.green),
                     )));
                 }
             }
             if let Some(actual) = failure.actual.as_deref() {
                 lines.push(Line::from(Span::styled(
                     "  Actual:",
-                    Style::default().fg(theme::RED),
+                    Style::default().fg(theme.red),
                 )));
                 for text_line in actual.lines() {
                     lines.push(Line::from(Span::styled(
                         format!("    {text_line}"),
-                        Style::default().fg(theme::RED),
+                        Style::default().fg(theme.red),
                     )));
                 }
             }
@@ -584,9 +761,9 @@ fn build_failure_text<'a>(
         lines.push(Line::from(""));
         for diff_line in diff.lines() {
             let style = if diff_line.starts_with('+') {
-                Style::default().fg(theme::GREEN)
+                Style::default().fg(theme.green)
             } else if diff_line.starts_with('-') {
-                Style::default().fg(theme::RED)
+                Style::default().fg(theme.red)
             } else {
                 Style::default()
             };
@@ -594,6 +771,11 @@ fn build_failure_text<'a>(
         }
     }
 
+    if let Some(ref snippet) = failure.source_snippet {
+        lines.push(Line::from(""));
+        push_snippet_lines(lines, snippet, theme);
+    }
+
     // Stack trace (filter out noise)
     if let Some(ref stack) = failure.stack_trace {
         let filtered: Vec<&str> = stack
@@ -605,13 +787,62 @@ fn build_failure_text<'a>(
             for stack_line in filtered {
                 lines.push(Line::from(Span::styled(
                     stack_line,
-                    Style::default().fg(theme::OVERLAY0),
+                    Style::default().fg(theme.overlay0),
                 )));
             }
         }
     }
 
-    Text::from(lines)
+    if !failure.attachments.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            "  Attachments:",
+            Style::default().fg(theme.blue),
+        )));
+        for attachment in &failure.attachments {
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("    {}  ", attachment.name),
+                    Style::default().fg(theme.text),
+                ),
+                Span::styled(
+                    attachment.path.display().to_string(),
+                    Style::default().fg(theme.overlay0),
+                ),
+            ]));
+        }
+    }
+}
+
+/// Render a code frame from `source::build_snippet`: gutter dimmed, failing
+/// line marked in red, code syntax-highlighted and the caret row in red.
+fn push_snippet_lines<'a>(lines: &mut Vec<Line<'a>>, snippet: &'a str, theme: &Theme) {
+    for snippet_line in snippet.lines() {
+        let Some((gutter, code)) = snippet_line.split_once(" | ") else {
+            lines.push(Line::from(Span::styled(
+                snippet_line,
+                Style::default().fg(theme.overlay0),
+            )));
+            continue;
+        };
+        let failing = gutter.starts_with('>');
+        let is_caret = gutter.trim().is_empty();
+        let gutter_color = if failing || is_caret {
+            theme.red
+        } else {
+            theme.overlay0
+        };
+        let mut spans = vec![Span::styled(
+            format!("  {gutter} │ "),
+            Style::default().fg(gutter_color),
+        )];
+        if is_caret {
+            spans.push(Span::styled(code, Style::default().fg(theme.red).bold()));
+        } else {
+            spans.extend(syntax::highlight(code, theme));
+        }
+        lines.push(Line::from(spans));
+    }
 }
 
 #[cfg(test)]
@@ -637,6 +868,7 @@ mod tests {
             diff: None,
             source_snippet: None,
             stack_trace: None,
+            attachments: Vec::new(),
         }
     }
 
@@ -955,7 +1187,7 @@ mod tests {
     #[test]
     fn build_failure_text_array_shows_expected_and_actual() {
         let failure = make_failure(Some(json!([1, 2, 3])), Some(json!([1, 2, 99])));
-        let text = build_failure_text(&failure, "my test");
+        let text = build_failure_text(std::slice::from_ref(&failure), "my test", &Theme::default());
         let all_text: Vec<String> = text.lines.iter().map(line_text).collect();
         assert!(all_text.iter().any(|l| l.contains("Expected")));
         assert!(all_text.iter().any(|l| l.contains("Actual")));
@@ -969,7 +1201,7 @@ mod tests {
             Some(json!({"a": 1, "b": 2})),
             Some(json!({"a": 1, "b": 99})),
         );
-        let text = build_failure_text(&failure, "my test");
+        let text = build_failure_text(std::slice::from_ref(&failure), "my test", &Theme::default());
         let all_text: Vec<String> = text.lines.iter().map(line_text).collect();
         assert!(all_text.iter().any(|l| l.contains("Expected")));
         assert!(all_text.iter().any(|l| l.contains("Actual")));
@@ -988,8 +1220,9 @@ mod tests {
             diff: None,
             source_snippet: None,
             stack_trace: None,
+            attachments: Vec::new(),
         };
-        let text = build_failure_text(&failure, "my test");
+        let text = build_failure_text(std::slice::from_ref(&failure), "my test", &Theme::default());
         let all_text: Vec<String> = text.lines.iter().map(line_text).collect();
         // falls through to raw string path
         assert!(all_text.iter().any(|l| l.contains("exp")));
@@ -1007,8 +1240,9 @@ mod tests {
             diff: None,
             source_snippet: None,
             stack_trace: None,
+            attachments: Vec::new(),
         };
-        let text = build_failure_text(&failure, "t");
+        let text = build_failure_text(std::slice::from_ref(&failure), "t", &Theme::default());
         let all_text: Vec<String> = text.lines.iter().map(line_text).collect();
         assert!(all_text.iter().any(|l| l.contains("line one")));
         assert!(all_text.iter().any(|l| l.contains("line two")));
@@ -1029,14 +1263,71 @@ mod tests {
             diff: None,
             source_snippet: None,
             stack_trace: None,
+            attachments: Vec::new(),
         };
-        let text = build_failure_text(&failure, "t");
+        let text = build_failure_text(std::slice::from_ref(&failure), "t", &Theme::default());
         let all_text: Vec<String> = text.lines.iter().map(line_text).collect();
         assert!(all_text.iter().any(|l| l.contains("Array [")));
         assert!(all_text.iter().any(|l| l.contains("\"a\",")));
         assert!(all_text.iter().any(|l| l.contains("\"b\",")));
     }
 
+    #[test]
+    fn build_failure_text_numbers_each_error() {
+        let mut second = make_failure(None, None);
+        second.message = "second assertion".into();
+        second.stack_trace = Some("at b.test.ts:9:3".into());
+        let failures = [make_failure(None, None), second];
+        let text = build_failure_text(&failures, "t", &Theme::default());
+        let all_text: Vec<String> = text.lines.iter().map(line_text).collect();
+        assert_eq!(
+            all_text
+                .iter()
+                .filter(|l| l.starts_with("✘ failed"))
+                .count(),
+            1
+        );
+        assert!(all_text.iter().any(|l| l.contains("Error 1/2")));
+        assert!(all_text.iter().any(|l| l.contains("Error 2/2")));
+        assert!(all_text.iter().any(|l| l == "assertion failed"));
+        assert!(all_text.iter().any(|l| l == "second assertion"));
+        assert!(all_text.iter().any(|l| l == "at b.test.ts:9:3"));
+    }
+
+    #[test]
+    fn build_failure_text_single_error_is_not_numbered() {
+        let failure = make_failure(None, None);
+        let text = build_failure_text(std::slice::from_ref(&failure), "t", &Theme::default());
+        let all_text: Vec<String> = text.lines.iter().map(line_text).collect();
+        assert!(!all_text.iter().any(|l| l.contains("Error 1/1")));
+    }
+
+    #[test]
+    fn build_failure_text_lists_attachments() {
+        let mut failure = make_failure(None, None);
+        failure.attachments = vec![crate::models::Attachment {
+            name: "screenshot".into(),
+            content_type: "image/png".into(),
+            path: "test-results/login/failed.png".into(),
+        }];
+        let failures = [failure];
+        let text = build_failure_text(&failures, "logs in", &Theme::default());
+        let rendered: Vec<String> = text.lines.iter().map(line_text).collect();
+        assert!(rendered.contains(&"  Attachments:".to_string()));
+        assert!(rendered.contains(&"    screenshot  test-results/login/failed.png".to_string()));
+    }
+
+    #[test]
+    fn build_failure_text_renders_source_snippet() {
+        let mut failure = make_failure(None, None);
+        failure.source_snippet =
+            Some("  1 | it('x', () => {\n> 2 |   expect(1).toBe(2);\n    |             ^".into());
+        let text = build_failure_text(std::slice::from_ref(&failure), "t", &Theme::default());
+        let all_text: Vec<String> = text.lines.iter().map(line_text).collect();
+        assert!(all_text.contains(&"  > 2 │   expect(1).toBe(2);".to_string()));
+        assert!(all_text.contains(&"      │             ^".to_string()));
+    }
+
     // ── push_json_value_lines — array branch ────────────────────────────────
 
     #[test]
@@ -1280,4 +1571,40 @@ mod tests {
             .expect("absent key must appear");
         assert!(absent_line.contains("(absent)"));
     }
+
+    // ── search ──────────────────────────────────────────────────────────────
+
+    #[test]
+    fn search_highlights_matches_across_spans() {
+        let theme = Theme::default();
+        let content = Text::from(vec![
+            Line::from(vec![Span::raw("expected "), Span::raw("value")]),
+            Line::from("no hit here"),
+            Line::from("a VALUE and a value"),
+        ]);
+        assert_eq!(find_hits(&content, "d va"), [(0, 7..11)]);
+
+        let hits = find_hits(&content, "value");
+        assert_eq!(
+            hits.iter().map(|(line, _)| *line).collect::<Vec<_>>(),
+            [0, 2, 2]
+        );
+        let content = highlight_hits(content, &hits, 1, &theme);
+        assert_eq!(line_text(&content.lines[2]), "a VALUE and a value");
+        let styled: Vec<(&str, Option<Color>)> = content.lines[2]
+            .spans
+            .iter()
+            .map(|s| (s.content.as_ref(), s.style.bg))
+            .collect();
+        assert_eq!(
+            styled,
+            [
+                ("a ", None),
+                ("VALUE", Some(theme.peach)),
+                (" and a ", None),
+                ("value", Some(theme.yellow)),
+            ]
+        );
+        assert_eq!(content.lines[1].spans.len(), 1);
+    }
 }
@@ -0,0 +1,107 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+};
+
+use super::ansi;
+use crate::app::App;
+use crate::app::runner_log::{LogKind, LogLine};
+
+/// Width of the `HH:MM:SS.mmm ` timestamp column.
+const STAMP_WIDTH: usize = 13;
+
+pub fn draw(frame: &mut Frame, app: &App) {
+    let theme = &app.theme;
+    let area = frame.area();
+    let width = (area.width * 9 / 10).max(40).min(area.width);
+    let height = (area.height * 4 / 5).max(8).min(area.height);
+    let popup = Rect {
+        x: area.x + area.width.saturating_sub(width) / 2,
+        y: area.y + area.height.saturating_sub(height) / 2,
+        width,
+        height,
+    };
+
+    frame.render_widget(Clear, popup);
+
+    let log = &app.runner_log;
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.blue))
+        .style(Style::default().bg(theme.mantle))
+        .title(Span::styled(
+            format!(" Runner log ({}) ", log.len()),
+            Style::default().fg(theme.blue).bold(),
+        ))
+        .title_bottom(Span::styled(
+            " j/k scroll · g/G oldest/newest · Esc close ",
+            Style::default().fg(theme.overlay0),
+        ))
+        .title_alignment(Alignment::Center);
+    let inner = block.inner(popup);
+    frame.render_widget(block, popup);
+
+    if log.is_empty() {
+        frame.render_widget(
+            Line::styled(" Nothing logged yet.", Style::default().fg(theme.subtext0)),
+            inner,
+        );
+        return;
+    }
+
+    // Walk back from the newest line shown until the panel is full, so the
+    // newest line sits at the bottom.
+    let viewport = inner.height as usize;
+    let text_width = (inner.width as usize).max(1);
+    let mut lines: Vec<Line> = Vec::new();
+    let mut rows = 0;
+    for entry in log.lines().rev().skip(app.runner_log_scroll) {
+        let entry_lines = entry_lines(entry, app);
+        rows += entry_lines
+            .iter()
+            .map(|line| line.width().div_ceil(text_width).max(1))
+            .sum::<usize>();
+        lines.splice(0..0, entry_lines);
+        if rows >= viewport {
+            break;
+        }
+    }
+
+    let paragraph = Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .scroll((rows.saturating_sub(viewport) as u16, 0));
+    frame.render_widget(paragraph, inner);
+}
+
+/// A log entry's lines: the timestamp, then the text styled by its kind.
+/// Output keeps the runner's own ANSI colors.
+fn entry_lines(entry: &LogLine, app: &App) -> Vec<Line<'static>> {
+    let theme = &app.theme;
+    let (prefix, base) = match entry.kind {
+        LogKind::Command => ("$ ", Style::default().fg(theme.teal)),
+        LogKind::Output => ("", Style::default().fg(theme.text)),
+        LogKind::Error => ("", Style::default().fg(theme.red)),
+        LogKind::Info => ("", Style::default().fg(theme.subtext0)),
+    };
+    entry
+        .text
+        .lines()
+        .enumerate()
+        .map(|(i, text)| {
+            let stamp = if i == 0 {
+                format!("{} ", entry.clock)
+            } else {
+                " ".repeat(STAMP_WIDTH)
+            };
+            let mut spans = vec![Span::styled(stamp, Style::default().fg(theme.overlay0))];
+            if i == 0 && !prefix.is_empty() {
+                spans.push(Span::styled(
+                    prefix,
+                    Style::default().fg(theme.mauve).bold(),
+                ));
+            }
+            spans.extend(ansi::spans(text, base));
+            Line::from(spans)
+        })
+        .collect()
+}
@@ -3,19 +3,30 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph},
 };
 
-use super::theme;
+use super::theme::Theme;
 
-pub fn draw(frame: &mut Frame, input: &tui_input::Input, active: bool, area: Rect) {
+pub fn draw(
+    frame: &mut Frame,
+    input: &tui_input::Input,
+    active: bool,
+    flat: bool,
+    theme: &Theme,
+    area: Rect,
+) {
     let (border_color, text_style) = if active {
-        (theme::BLUE, Style::default().fg(theme::TEXT))
+        (theme.blue, Style::default().fg(theme.text))
     } else {
-        (theme::SURFACE2, Style::default().fg(theme::OVERLAY0))
+        (theme.surface2, Style::default().fg(theme.overlay0))
     };
 
     let block = Block::default()
         .borders(Borders::ALL)
         .border_style(Style::default().fg(border_color))
-        .title(" Filter ");
+        .title(if flat {
+            " Filter · ranked "
+        } else {
+            " Filter "
+        });
 
     let paragraph = Paragraph::new(format!("/ {}", input.value()))
         .style(text_style)
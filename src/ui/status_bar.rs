@@ -1,35 +1,45 @@
 use ratatui::{prelude::*, widgets::Paragraph};
 
-use super::theme;
-use crate::{app::App, models::NodeKind};
+use super::theme::Theme;
+use crate::{
+    app::{Action, App},
+    models::{NodeKind, RunSummary},
+};
 
 const SPINNER_FRAMES: &[&str] = &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
 
 /// Keybinding hints shown permanently in the status bar.
 /// Kept intentionally short — full reference is in the help overlay ([?]).
-fn primary_hints(filter_active: bool) -> Vec<Span<'static>> {
-    if filter_active {
+fn primary_hints(app: &App) -> Vec<Span<'static>> {
+    let theme = &app.theme;
+    if app.filter_active || app.output_search.active {
         return vec![
-            Span::styled("[enter] ", Style::default().fg(theme::BLUE)),
+            Span::styled("[enter] ", Style::default().fg(theme.blue)),
             Span::raw("apply  "),
-            Span::styled("[esc] ", Style::default().fg(theme::BLUE)),
+            Span::styled("[esc] ", Style::default().fg(theme.blue)),
             Span::raw("clear  "),
         ];
     }
 
-    vec![
-        Span::styled("[a] ", Style::default().fg(theme::BLUE)),
-        Span::raw("run all  "),
-        Span::styled("[r] ", Style::default().fg(theme::BLUE)),
-        Span::raw("rerun  "),
-        Span::styled("[w] ", Style::default().fg(theme::BLUE)),
-        Span::raw("watch  "),
-        Span::styled("[?] ", Style::default().fg(theme::BLUE)),
-        Span::raw("help  "),
+    [
+        (Action::RunFiltered, "run all  "),
+        (Action::RerunFailed, "rerun  "),
+        (Action::ToggleWatch, "watch  "),
+        (Action::ToggleHelp, "help  "),
     ]
+    .into_iter()
+    .filter_map(|(action, hint)| Some((app.keymap.key_for(&action)?, hint)))
+    .flat_map(|(key, hint)| {
+        [
+            Span::styled(format!("[{key}] "), Style::default().fg(theme.blue)),
+            Span::raw(hint),
+        ]
+    })
+    .collect()
 }
 
 pub fn draw(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = &app.theme;
     let left = get_help(app);
     let right = get_summary(app);
 
@@ -37,9 +47,9 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect) {
         Layout::horizontal([Constraint::Min(1), Constraint::Length(right.width() as u16)])
             .areas(area);
 
-    let left_paragraph = Paragraph::new(left).style(Style::default().bg(theme::SURFACE0));
+    let left_paragraph = Paragraph::new(left).style(Style::default().bg(theme.surface0));
     let right_paragraph = Paragraph::new(right)
-        .style(Style::default().bg(theme::SURFACE0))
+        .style(Style::default().bg(theme.surface0))
         .alignment(Alignment::Right);
 
     frame.render_widget(left_paragraph, left_area);
@@ -47,43 +57,70 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect) {
 }
 
 fn get_help(app: &App) -> Line<'_> {
+    let theme = &app.theme;
     let watch_indicator = if app.watch_mode { " [watch] " } else { "" };
 
     if app.discovering {
         let spinner = SPINNER_FRAMES[app.spinner_tick % SPINNER_FRAMES.len()];
         Line::from(vec![Span::styled(
             format!(" {} Discovering tests...", spinner),
-            Style::default().fg(theme::YELLOW),
+            Style::default().fg(theme.yellow),
         )])
     } else {
-        let mut spans = primary_hints(app.filter_active);
+        let mut spans = primary_hints(app);
+        if let Some(pending) = app.keymap.pending_label() {
+            spans.push(Span::styled(
+                format!(" {pending}… "),
+                Style::default().fg(theme.yellow),
+            ));
+        }
         spans.push(Span::styled(
             watch_indicator,
-            Style::default().fg(theme::TEAL),
+            Style::default().fg(theme.teal),
         ));
         Line::from(spans)
     }
 }
 
+/// Snapshot activity from the last run; empty when nothing changed.
+fn snapshot_counts(summary: &RunSummary, theme: &Theme) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    let written = summary.snapshots_written + summary.snapshots_updated;
+    if written > 0 {
+        spans.push(Span::styled(
+            format!(" 📸 {written} written "),
+            Style::default().fg(theme.peach),
+        ));
+    }
+    if summary.snapshots_obsolete > 0 {
+        spans.push(Span::styled(
+            format!(" {} obsolete ", summary.snapshots_obsolete),
+            Style::default().fg(theme.overlay0),
+        ));
+    }
+    spans
+}
+
 fn get_summary(app: &App) -> Line<'_> {
+    let theme = &app.theme;
     let file_count = app.tree.count_kind(NodeKind::File);
     let test_count = app.tree.count_kind(NodeKind::Test);
     let counts: Vec<Span> = if file_count > 0 || test_count > 0 {
         let mut counts = vec![
             Span::styled(
                 format!("{}", file_count),
-                Style::default().fg(theme::OVERLAY0),
+                Style::default().fg(theme.overlay0),
             ),
-            Span::styled(" files  ", Style::default().fg(theme::OVERLAY0)),
+            Span::styled(" files  ", Style::default().fg(theme.overlay0)),
         ];
 
         if test_count > 0 {
             counts.extend([
                 Span::styled(
                     format!("{}", test_count),
-                    Style::default().fg(theme::OVERLAY0),
+                    Style::default().fg(theme.overlay0),
                 ),
-                Span::styled(" tests  ", Style::default().fg(theme::OVERLAY0)),
+                Span::styled(" tests  ", Style::default().fg(theme.overlay0)),
             ]);
         }
 
@@ -97,7 +134,7 @@ fn get_summary(app: &App) -> Line<'_> {
         let mut spans = counts;
         spans.push(Span::styled(
             format!("{} running... ", spinner),
-            Style::default().fg(theme::YELLOW),
+            Style::default().fg(theme.yellow),
         ));
         Line::from(spans)
     } else if !app.discovering {
@@ -106,19 +143,28 @@ fn get_summary(app: &App) -> Line<'_> {
 
             if passed + failed + skipped > 0 {
                 let mut spans = counts;
+                if app.summary_restored {
+                    spans.push(Span::styled(
+                        "last run  ",
+                        Style::default()
+                            .fg(theme.overlay0)
+                            .add_modifier(Modifier::ITALIC),
+                    ));
+                }
                 spans.extend([
-                    Span::styled("✔ ", Style::default().fg(theme::GREEN)),
-                    Span::styled(format!("{}", passed), Style::default().fg(theme::GREEN)),
-                    Span::styled("  ✘ ", Style::default().fg(theme::RED)),
-                    Span::styled(format!("{}", failed), Style::default().fg(theme::RED)),
-                    Span::styled("  ⊘ ", Style::default().fg(theme::TEAL)),
-                    Span::styled(format!("{}", skipped), Style::default().fg(theme::TEAL)),
-                    Span::styled("  ⏲ ", Style::default().fg(theme::MAUVE)),
+                    Span::styled("✔ ", Style::default().fg(theme.green)),
+                    Span::styled(format!("{}", passed), Style::default().fg(theme.green)),
+                    Span::styled("  ✘ ", Style::default().fg(theme.red)),
+                    Span::styled(format!("{}", failed), Style::default().fg(theme.red)),
+                    Span::styled("  ⊘ ", Style::default().fg(theme.teal)),
+                    Span::styled(format!("{}", skipped), Style::default().fg(theme.teal)),
+                    Span::styled("  ⏲ ", Style::default().fg(theme.mauve)),
                     Span::styled(
                         format!("{:.1}s ", summary.duration as f64 / 1000.0),
-                        Style::default().fg(theme::MAUVE),
+                        Style::default().fg(theme.mauve),
                     ),
                 ]);
+                spans.extend(snapshot_counts(summary, theme));
                 Line::from(spans)
             } else {
                 Line::from(counts)
@@ -0,0 +1,173 @@
+use ratatui::prelude::*;
+
+use super::theme::Theme;
+
+const KEYWORDS: &[&str] = &[
+    "as",
+    "async",
+    "await",
+    "break",
+    "case",
+    "catch",
+    "class",
+    "const",
+    "continue",
+    "default",
+    "delete",
+    "do",
+    "else",
+    "enum",
+    "export",
+    "extends",
+    "false",
+    "finally",
+    "for",
+    "from",
+    "function",
+    "if",
+    "implements",
+    "import",
+    "in",
+    "instanceof",
+    "interface",
+    "let",
+    "new",
+    "null",
+    "of",
+    "private",
+    "public",
+    "readonly",
+    "return",
+    "static",
+    "super",
+    "switch",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "type",
+    "typeof",
+    "undefined",
+    "var",
+    "void",
+    "while",
+    "yield",
+];
+
+/// Highlight one line of JavaScript/TypeScript. This is a lexical pass only —
+/// strings, comments, numbers, keywords and calls — good enough for code frames.
+/// Multi-line strings and block comments are coloured per line.
+pub fn highlight(code: &str, theme: &Theme) -> Vec<Span<'static>> {
+    let chars: Vec<char> = code.chars().collect();
+    let mut spans: Vec<Span<'static>> = Vec::new();
+    let mut plain = String::new();
+    let mut i = 0;
+
+    let flush = |plain: &mut String, spans: &mut Vec<Span<'static>>| {
+        if !plain.is_empty() {
+            spans.push(Span::styled(
+                std::mem::take(plain),
+                Style::default().fg(theme.text),
+            ));
+        }
+    };
+
+    while i < chars.len() {
+        let c = chars[i];
+        let start = i;
+        let color = if c == '/' && chars.get(i + 1) == Some(&'/') {
+            i = chars.len();
+            theme.overlay0
+        } else if c == '/' && chars.get(i + 1) == Some(&'*') {
+            i += 2;
+            while i < chars.len() && !(chars[i - 1] == '*' && chars[i] == '/') {
+                i += 1;
+            }
+            i = (i + 1).min(chars.len());
+            theme.overlay0
+        } else if matches!(c, '"' | '\'' | '`') {
+            i += 1;
+            while i < chars.len() && chars[i] != c {
+                if chars[i] == '\\' {
+                    i += 1;
+                }
+                i += 1;
+            }
+            i = (i + 1).min(chars.len());
+            theme.green
+        } else if c.is_ascii_digit() {
+            while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '.') {
+                i += 1;
+            }
+            theme.peach
+        } else if c.is_alphabetic() || c == '_' || c == '$' {
+            while i < chars.len()
+                && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '$')
+            {
+                i += 1;
+            }
+            let word: String = chars[start..i].iter().collect();
+            if KEYWORDS.contains(&word.as_str()) {
+                theme.mauve
+            } else if chars[i..].iter().find(|c| !c.is_whitespace()) == Some(&'(') {
+                theme.blue
+            } else {
+                plain.push_str(&word);
+                continue;
+            }
+        } else {
+            plain.push(c);
+            i += 1;
+            continue;
+        };
+
+        flush(&mut plain, &mut spans);
+        spans.push(Span::styled(
+            chars[start..i].iter().collect::<String>(),
+            Style::default().fg(color),
+        ));
+    }
+    flush(&mut plain, &mut spans);
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn colored(spans: &[Span]) -> Vec<(String, Option<Color>)> {
+        spans
+            .iter()
+            .map(|s| (s.content.to_string(), s.style.fg))
+            .collect()
+    }
+
+    #[test]
+    fn highlights_keywords_strings_numbers_and_calls() {
+        let theme = Theme::default();
+        let spans = highlight("const x = expect('a').toBe(42); // done", &theme);
+        let spans = colored(&spans);
+        assert!(spans.contains(&("const".into(), Some(theme.mauve))));
+        assert!(spans.contains(&("expect".into(), Some(theme.blue))));
+        assert!(spans.contains(&("'a'".into(), Some(theme.green))));
+        assert!(spans.contains(&("42".into(), Some(theme.peach))));
+        assert!(spans.contains(&("// done".into(), Some(theme.overlay0))));
+    }
+
+    #[test]
+    fn preserves_text() {
+        let code = "  it(\"escaped \\\" quote\", () => { /* c */ a.b });";
+        let text: String = highlight(code, &Theme::default())
+            .iter()
+            .map(|s| s.content.as_ref())
+            .collect();
+        assert_eq!(text, code);
+    }
+
+    #[test]
+    fn unterminated_string_runs_to_end_of_line() {
+        let theme = Theme::default();
+        let spans = colored(&highlight("x = `multi", &theme));
+        assert_eq!(spans.last(), Some(&("`multi".into(), Some(theme.green))));
+    }
+}
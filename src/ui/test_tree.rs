@@ -3,21 +3,29 @@ use ratatui::{
     widgets::{Block, Borders, List, ListItem},
 };
 
-use super::theme;
+use super::theme::Theme;
 use crate::{
-    app::{App, Panel},
-    models::{NodeKind, TestNode, TestStatus},
+    app::{App, Panel, history::test_key},
+    models::{NodeKind, Query, TestNode, TestStatus, TestTree},
 };
 
 pub fn draw(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = &app.theme;
     let focused = app.active_panel == Panel::TestTree;
     let border_style = if focused {
-        Style::default().fg(theme::BLUE)
+        Style::default().fg(theme.blue)
     } else {
-        Style::default().fg(theme::SURFACE2)
+        Style::default().fg(theme.surface2)
     };
 
+    let query = Query::parse(app.filter.value());
+    let flat = app.filter_flat && !query.is_empty();
+
+    let visible = app.visible_tree_nodes();
+    let n = visible.len();
+
     let title = match &app.project_name {
+        _ if flat => format!(" Matches ({n}) "),
         Some(name) => format!(" Tests — {} ", name),
         None => " Tests ".to_string(),
     };
@@ -30,9 +38,6 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect) {
     let available_width = block.inner(area).width as usize;
     let inner_height = app.tree_viewport_height;
 
-    let visible = app.visible_tree_nodes();
-    let n = visible.len();
-
     // Precompute is_last: a node is last if no later visible node appears at the same or
     // shallower depth (meaning no more siblings at this level follow).
     let is_last: Vec<bool> = (0..n)
@@ -58,24 +63,27 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect) {
             let selected = abs_i == app.selected_tree_index && focused;
 
             let branch = branch_prefix(depth, is_last[abs_i]);
-            let (icon, icon_color) = node_icon(node, app.spinner_tick);
+            let (icon, icon_color) = node_icon(node, app.spinner_tick, theme);
             let show_folder = matches!(node.kind, NodeKind::Workspace | NodeKind::Project);
-            let name = node_display_name(app.project_name.as_ref(), node);
+            let name = node_display_name(app, node_id, node, flat);
+            let highlighted = highlight_positions(&app.tree, &query, node, &name, flat);
             let name_color = node_name_color(node, app);
 
             // Build left spans first so we can measure them via Span::width()
             let mut left_spans: Vec<Span> = Vec::new();
-            left_spans.push(Span::styled(branch, Style::default().fg(theme::SURFACE2)));
+            left_spans.push(Span::styled(branch, Style::default().fg(theme.surface2)));
             if !icon.is_empty() {
                 left_spans.push(Span::styled(icon, Style::default().fg(icon_color)));
             }
             if show_folder {
-                left_spans.push(Span::styled("📁 ", Style::default().fg(theme::PEACH)));
+                left_spans.push(Span::styled("📁 ", Style::default().fg(theme.peach)));
+            }
+            // Results restored from the previous session are shown dimmed until re-run.
+            let mut name_style = Style::default().fg(name_color);
+            if node.restored {
+                name_style = name_style.add_modifier(Modifier::DIM | Modifier::ITALIC);
             }
-            left_spans.push(Span::styled(
-                name.to_string(),
-                Style::default().fg(name_color),
-            ));
+            left_spans.extend(highlight_spans(&name, &highlighted, name_style, theme));
 
             let left_w: usize = left_spans.iter().map(|s| s.width()).sum();
             let right_spans = right_content(node, app, node_id);
@@ -90,7 +98,7 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect) {
             let content = Line::from(spans);
             let item = ListItem::new(content);
             if selected {
-                item.style(Style::default().bg(theme::SURFACE1))
+                item.style(Style::default().bg(theme.surface1))
             } else {
                 item
             }
@@ -117,12 +125,12 @@ fn branch_prefix(depth: usize, is_last: bool) -> String {
 }
 
 /// Returns (icon_string_with_trailing_space, icon_color).
-fn node_icon(node: &TestNode, spinner_tick: usize) -> (String, Color) {
+fn node_icon(node: &TestNode, spinner_tick: usize, theme: &Theme) -> (String, Color) {
     match node.kind {
-        NodeKind::Workspace => (String::new(), theme::SUBTEXT0),
+        NodeKind::Workspace => (String::new(), theme.subtext0),
         NodeKind::Project | NodeKind::File | NodeKind::Suite => {
             let icon = if node.expanded { "▼ " } else { "▶ " };
-            (icon.to_string(), theme::SUBTEXT0)
+            (icon.to_string(), theme.subtext0)
         }
         NodeKind::Test => match node.status {
             TestStatus::Running => {
@@ -130,71 +138,192 @@ fn node_icon(node: &TestNode, spinner_tick: usize) -> (String, Color) {
                     &["⠋ ", "⠙ ", "⠹ ", "⠸ ", "⠼ ", "⠴ ", "⠦ ", "⠧ ", "⠇ ", "⠏ "];
                 (
                     FRAMES[spinner_tick % FRAMES.len()].to_string(),
-                    theme::YELLOW,
+                    theme.yellow,
                 )
             }
-            _ => ("■ ".to_string(), node.status.color()),
+            _ => ("■ ".to_string(), node.status.color(theme)),
         },
     }
 }
 
 fn node_name_color(node: &TestNode, app: &App) -> Color {
+    let theme = &app.theme;
     if app.watched_ids.contains(&node.id) {
-        return theme::TEAL;
+        return theme.teal;
     }
     match node.kind {
         NodeKind::Test => match node.status {
-            TestStatus::Failed => theme::RED,
-            TestStatus::Passed => theme::SUBTEXT0,
-            TestStatus::Skipped => theme::OVERLAY0,
-            _ => theme::TEXT,
+            TestStatus::Failed => theme.red,
+            TestStatus::Passed => theme.subtext0,
+            TestStatus::Skipped => theme.overlay0,
+            _ => theme.text,
         },
-        _ => theme::TEXT,
+        _ => theme.text,
+    }
+}
+
+/// Line coverage of the sources exercised by the tests under a node, shown
+/// ahead of the pass counts while coverage is on.
+fn coverage_span(node: &TestNode, app: &App, node_id: usize) -> Option<Span<'static>> {
+    let theme = &app.theme;
+    if !app.coverage_enabled || app.coverage.is_empty() || node.kind == NodeKind::Test {
+        return None;
+    }
+    let lines = app
+        .coverage
+        .lines_for_tests(app.tree.subtree_file_paths(node_id).into_iter())?;
+    let pct = lines.pct();
+    Some(Span::styled(
+        format!("{pct:>3.0}% "),
+        Style::default().fg(coverage_color(pct, theme)),
+    ))
+}
+
+pub(super) fn coverage_color(pct: f64, theme: &Theme) -> Color {
+    if pct >= 80.0 {
+        theme.green
+    } else if pct >= 50.0 {
+        theme.yellow
+    } else {
+        theme.red
     }
 }
 
 /// Build right-aligned spans. Width is measured by the caller via Span::width().
 fn right_content(node: &TestNode, app: &App, node_id: usize) -> Vec<Span<'static>> {
+    let mut spans: Vec<Span<'static>> = coverage_span(node, app, node_id).into_iter().collect();
+    spans.extend(status_content(node, app, node_id));
+    spans
+}
+
+fn status_content(node: &TestNode, app: &App, node_id: usize) -> Vec<Span<'static>> {
+    let theme = &app.theme;
     match node.kind {
         NodeKind::Workspace => {
             let (_, _, total_tests) = app.tree.subtree_test_counts(node_id);
             let file_count = app.tree.subtree_file_count(node_id);
             let s = format!("{} files · {} tests", file_count, total_tests);
-            vec![Span::styled(s, Style::default().fg(theme::SUBTEXT0))]
+            vec![Span::styled(s, Style::default().fg(theme.subtext0))]
         }
         NodeKind::Project | NodeKind::File | NodeKind::Suite => {
             let (passed, failed, total) = app.tree.subtree_test_counts(node_id);
             if total == 0 {
                 return vec![];
             }
-            build_gauge_spans(passed, failed, total)
+            build_gauge_spans(passed, failed, total, theme)
         }
         NodeKind::Test => {
+            let mut spans = Vec::new();
+            if let Some(flakiness) =
+                app.tree
+                    .file_and_full_name(node_id)
+                    .and_then(|(file, name)| {
+                        app.history
+                            .flakiness(&test_key(&file.to_string_lossy(), &name))
+                    })
+            {
+                spans.push(Span::styled(
+                    format!("⚡{:.0}% ", flakiness.score() * 100.0),
+                    Style::default().fg(theme.yellow),
+                ));
+            }
             if let Some(ms) = node.result.as_ref().and_then(|r| r.duration_ms) {
                 let s = format!("{}ms", ms);
-                vec![Span::styled(s, Style::default().fg(theme::SUBTEXT0))]
-            } else {
-                vec![]
+                spans.push(Span::styled(s, Style::default().fg(theme.subtext0)));
             }
+            spans
         }
     }
 }
 
 /// Count indicator: "passed/total" with passed coloured by status.
-fn build_gauge_spans(passed: usize, _failed: usize, total: usize) -> Vec<Span<'static>> {
+fn build_gauge_spans(
+    passed: usize,
+    _failed: usize,
+    total: usize,
+    theme: &Theme,
+) -> Vec<Span<'static>> {
     let passed_color = if passed == total {
-        theme::GREEN
+        theme.green
     } else if passed == 0 {
-        theme::RED
+        theme.red
     } else {
-        theme::YELLOW
+        theme.yellow
     };
     vec![
         Span::styled(passed.to_string(), Style::default().fg(passed_color)),
-        Span::styled(format!("/{}", total), Style::default().fg(theme::SUBTEXT0)),
+        Span::styled(format!("/{}", total), Style::default().fg(theme.subtext0)),
     ]
 }
 
-fn node_display_name<'a>(_project: Option<&'a String>, node: &'a TestNode) -> &'a str {
-    &node.name
+/// The node's name, or in flat mode its full context (`path > suite > test`),
+/// since there is no tree around it.
+fn node_display_name(app: &App, node_id: usize, node: &TestNode, flat: bool) -> String {
+    if !flat {
+        return node.name.clone();
+    }
+    match app.tree.file_and_full_name(node_id) {
+        Some((path, name)) if name.is_empty() => path.to_string_lossy().to_string(),
+        Some((path, name)) => format!("{} > {}", path.to_string_lossy(), name),
+        None => node.name.clone(),
+    }
+}
+
+/// Char indices of `name` matched by the filter's text terms. File names in the
+/// tree are highlighted where the query matched their full path.
+fn highlight_positions(
+    tree: &TestTree,
+    query: &Query,
+    node: &TestNode,
+    name: &str,
+    flat: bool,
+) -> Vec<usize> {
+    if query.is_empty() {
+        return Vec::new();
+    }
+    if !flat
+        && node.kind == NodeKind::File
+        && let Some(path) = node.path.as_deref().and_then(|p| p.to_str())
+        && path.ends_with(name)
+    {
+        let offset = path.chars().count() - name.chars().count();
+        return tree
+            .highlight(query, path)
+            .into_iter()
+            .filter_map(|i| i.checked_sub(offset))
+            .collect();
+    }
+    tree.highlight(query, name)
+}
+
+/// Split `text` into spans, styling the chars at `positions` as matches.
+fn highlight_spans(
+    text: &str,
+    positions: &[usize],
+    style: Style,
+    theme: &Theme,
+) -> Vec<Span<'static>> {
+    if positions.is_empty() {
+        return vec![Span::styled(text.to_string(), style)];
+    }
+    let match_style = style.fg(theme.yellow).add_modifier(Modifier::BOLD);
+    let mut spans: Vec<Span<'static>> = Vec::new();
+    let mut run = String::new();
+    let mut run_matched = false;
+    for (i, c) in text.chars().enumerate() {
+        let matched = positions.binary_search(&i).is_ok();
+        if matched != run_matched && !run.is_empty() {
+            let style = if run_matched { match_style } else { style };
+            spans.push(Span::styled(std::mem::take(&mut run), style));
+        }
+        run_matched = matched;
+        run.push(c);
+    }
+    if !run.is_empty() {
+        spans.push(Span::styled(
+            run,
+            if run_matched { match_style } else { style },
+        ));
+    }
+    spans
 }
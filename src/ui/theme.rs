@@ -1,21 +1,314 @@
-#![allow(dead_code)]
 use ratatui::prelude::Color;
 
-// Catppuccin Mocha palette
-pub const BASE: Color = Color::Rgb(30, 30, 46);
-pub const SURFACE0: Color = Color::Rgb(49, 50, 68);
-pub const SURFACE1: Color = Color::Rgb(69, 71, 90);
-pub const SURFACE2: Color = Color::Rgb(88, 91, 112);
-pub const TEXT: Color = Color::Rgb(205, 214, 244);
-pub const SUBTEXT0: Color = Color::Rgb(166, 173, 200);
-pub const SUBTEXT1: Color = Color::Rgb(186, 194, 222);
-pub const OVERLAY0: Color = Color::Rgb(108, 112, 134);
-pub const RED: Color = Color::Rgb(243, 139, 168);
-pub const GREEN: Color = Color::Rgb(166, 227, 161);
-pub const YELLOW: Color = Color::Rgb(249, 226, 175);
-pub const BLUE: Color = Color::Rgb(137, 180, 250);
-pub const TEAL: Color = Color::Rgb(148, 226, 213);
-pub const MAUVE: Color = Color::Rgb(203, 166, 247);
-pub const PEACH: Color = Color::Rgb(250, 179, 135);
-pub const CRUST: Color = Color::Rgb(17, 17, 27);
-pub const MANTLE: Color = Color::Rgb(24, 24, 37);
+/// The colors the UI draws with. Roles are named after the Catppuccin palette:
+/// `base`/`mantle`/`crust` are backgrounds, `surface*` and `overlay0` are
+/// selection, bars and borders, `text`/`subtext*` are foregrounds.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Theme {
+    pub base: Color,
+    pub mantle: Color,
+    pub crust: Color,
+    pub surface0: Color,
+    pub surface1: Color,
+    pub surface2: Color,
+    pub overlay0: Color,
+    pub text: Color,
+    pub subtext0: Color,
+    pub subtext1: Color,
+    pub red: Color,
+    pub green: Color,
+    pub yellow: Color,
+    pub blue: Color,
+    pub teal: Color,
+    pub mauve: Color,
+    pub peach: Color,
+}
+
+/// Built-in themes selectable with `name` in `[theme]`.
+pub const THEME_NAMES: &[&str] = &[
+    "mocha",
+    "latte",
+    "solarized-dark",
+    "solarized-light",
+    "ansi",
+];
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::mocha()
+    }
+}
+
+impl Theme {
+    /// Catppuccin Mocha (dark).
+    pub fn mocha() -> Self {
+        Self {
+            base: Color::Rgb(30, 30, 46),
+            mantle: Color::Rgb(24, 24, 37),
+            crust: Color::Rgb(17, 17, 27),
+            surface0: Color::Rgb(49, 50, 68),
+            surface1: Color::Rgb(69, 71, 90),
+            surface2: Color::Rgb(88, 91, 112),
+            overlay0: Color::Rgb(108, 112, 134),
+            text: Color::Rgb(205, 214, 244),
+            subtext0: Color::Rgb(166, 173, 200),
+            subtext1: Color::Rgb(186, 194, 222),
+            red: Color::Rgb(243, 139, 168),
+            green: Color::Rgb(166, 227, 161),
+            yellow: Color::Rgb(249, 226, 175),
+            blue: Color::Rgb(137, 180, 250),
+            teal: Color::Rgb(148, 226, 213),
+            mauve: Color::Rgb(203, 166, 247),
+            peach: Color::Rgb(250, 179, 135),
+        }
+    }
+
+    /// Catppuccin Latte (light).
+    pub fn latte() -> Self {
+        Self {
+            base: Color::Rgb(239, 241, 245),
+            mantle: Color::Rgb(230, 233, 239),
+            crust: Color::Rgb(220, 224, 232),
+            surface0: Color::Rgb(204, 208, 218),
+            surface1: Color::Rgb(188, 192, 204),
+            surface2: Color::Rgb(172, 176, 190),
+            overlay0: Color::Rgb(156, 160, 176),
+            text: Color::Rgb(76, 79, 105),
+            subtext0: Color::Rgb(108, 111, 133),
+            subtext1: Color::Rgb(92, 95, 119),
+            red: Color::Rgb(210, 15, 57),
+            green: Color::Rgb(64, 160, 43),
+            yellow: Color::Rgb(223, 142, 29),
+            blue: Color::Rgb(30, 102, 245),
+            teal: Color::Rgb(23, 146, 153),
+            mauve: Color::Rgb(136, 57, 239),
+            peach: Color::Rgb(254, 100, 11),
+        }
+    }
+
+    /// Solarized dark: base03 background, base1 text.
+    pub fn solarized_dark() -> Self {
+        Self {
+            base: Color::Rgb(0, 43, 54),
+            mantle: Color::Rgb(0, 43, 54),
+            crust: Color::Rgb(0, 43, 54),
+            surface0: Color::Rgb(7, 54, 66),
+            surface1: Color::Rgb(7, 54, 66),
+            surface2: Color::Rgb(88, 110, 117),
+            overlay0: Color::Rgb(88, 110, 117),
+            text: Color::Rgb(147, 161, 161),
+            subtext0: Color::Rgb(101, 123, 131),
+            subtext1: Color::Rgb(131, 148, 150),
+            ..Self::solarized_accents()
+        }
+    }
+
+    /// Solarized light: base3 background, base01 text.
+    pub fn solarized_light() -> Self {
+        Self {
+            base: Color::Rgb(253, 246, 227),
+            mantle: Color::Rgb(253, 246, 227),
+            crust: Color::Rgb(253, 246, 227),
+            surface0: Color::Rgb(238, 232, 213),
+            surface1: Color::Rgb(238, 232, 213),
+            surface2: Color::Rgb(147, 161, 161),
+            overlay0: Color::Rgb(147, 161, 161),
+            text: Color::Rgb(88, 110, 117),
+            subtext0: Color::Rgb(131, 148, 150),
+            subtext1: Color::Rgb(101, 123, 131),
+            ..Self::solarized_accents()
+        }
+    }
+
+    fn solarized_accents() -> Self {
+        Self {
+            red: Color::Rgb(220, 50, 47),
+            green: Color::Rgb(133, 153, 0),
+            yellow: Color::Rgb(181, 137, 0),
+            blue: Color::Rgb(38, 139, 210),
+            teal: Color::Rgb(42, 161, 152),
+            mauve: Color::Rgb(108, 113, 196),
+            peach: Color::Rgb(203, 75, 22),
+            ..Self::mocha()
+        }
+    }
+
+    /// The 16 standard terminal colors, for terminals without truecolor. The
+    /// terminal's own foreground and background are kept, so it suits light
+    /// and dark terminals alike.
+    pub fn ansi() -> Self {
+        Self {
+            base: Color::Reset,
+            mantle: Color::Reset,
+            crust: Color::Reset,
+            surface0: Color::Reset,
+            surface1: Color::DarkGray,
+            surface2: Color::DarkGray,
+            overlay0: Color::DarkGray,
+            text: Color::Reset,
+            subtext0: Color::Gray,
+            subtext1: Color::Gray,
+            red: Color::Red,
+            green: Color::Green,
+            yellow: Color::Yellow,
+            blue: Color::Blue,
+            teal: Color::Cyan,
+            mauve: Color::Magenta,
+            peach: Color::LightRed,
+        }
+    }
+
+    pub fn named(name: &str) -> Option<Self> {
+        Some(match name {
+            "mocha" => Self::mocha(),
+            "latte" => Self::latte(),
+            "solarized-dark" | "solarized" => Self::solarized_dark(),
+            "solarized-light" => Self::solarized_light(),
+            "ansi" => Self::ansi(),
+            _ => return None,
+        })
+    }
+
+    /// The theme used when none is configured: Mocha, or the ANSI colors in a
+    /// terminal known to lack truecolor support.
+    pub fn detect() -> Self {
+        let var = |name| std::env::var(name).ok();
+        if lacks_truecolor(
+            var("COLORTERM").as_deref(),
+            var("TERM").as_deref(),
+            var("TERM_PROGRAM").as_deref(),
+        ) {
+            Self::ansi()
+        } else {
+            Self::mocha()
+        }
+    }
+
+    /// Build the theme from `[theme]`: `name` picks a built-in theme (detected
+    /// when absent) and any other key overrides that color, e.g. `red = "#ff5555"`,
+    /// `blue = "lightblue"` or `text = "15"`. Problems are returned as messages
+    /// and the offending entries skipped.
+    pub fn from_config(table: &toml::Table) -> (Self, Vec<String>) {
+        let mut errors = Vec::new();
+        let mut theme = match table.get("name") {
+            None => Self::detect(),
+            Some(toml::Value::String(name)) => Self::named(name).unwrap_or_else(|| {
+                errors.push(format!(
+                    "unknown theme '{name}' (expected one of {})",
+                    THEME_NAMES.join(", ")
+                ));
+                Self::detect()
+            }),
+            Some(_) => {
+                errors.push("name: expected a theme name".to_string());
+                Self::detect()
+            }
+        };
+
+        for (key, value) in table.iter().filter(|(key, _)| *key != "name") {
+            let Some(slot) = theme.color_mut(key) else {
+                errors.push(format!("unknown color '{key}'"));
+                continue;
+            };
+            match value.as_str().map(str::parse::<Color>) {
+                Some(Ok(color)) => *slot = color,
+                _ => errors.push(format!(
+                    "{key}: expected a color like \"#rrggbb\" or \"red\""
+                )),
+            }
+        }
+        (theme, errors)
+    }
+
+    fn color_mut(&mut self, name: &str) -> Option<&mut Color> {
+        Some(match name {
+            "base" => &mut self.base,
+            "mantle" => &mut self.mantle,
+            "crust" => &mut self.crust,
+            "surface0" => &mut self.surface0,
+            "surface1" => &mut self.surface1,
+            "surface2" => &mut self.surface2,
+            "overlay0" => &mut self.overlay0,
+            "text" => &mut self.text,
+            "subtext0" => &mut self.subtext0,
+            "subtext1" => &mut self.subtext1,
+            "red" => &mut self.red,
+            "green" => &mut self.green,
+            "yellow" => &mut self.yellow,
+            "blue" => &mut self.blue,
+            "teal" => &mut self.teal,
+            "mauve" => &mut self.mauve,
+            "peach" => &mut self.peach,
+            _ => return None,
+        })
+    }
+}
+
+/// Whether the terminal described by `$COLORTERM`, `$TERM` and
+/// `$TERM_PROGRAM` is known to lack truecolor: the Linux console, a terminal
+/// limited to 8 or 16 colors, or macOS's Terminal. Most terminals that leave
+/// `$COLORTERM` unset, as over SSH or in tmux, have it.
+fn lacks_truecolor(
+    colorterm: Option<&str>,
+    term: Option<&str>,
+    term_program: Option<&str>,
+) -> bool {
+    if colorterm.is_some_and(|v| matches!(v, "truecolor" | "24bit")) {
+        return false;
+    }
+    term.is_some_and(|t| {
+        matches!(t, "linux" | "dumb") || t.ends_with("-8color") || t.ends_with("-16color")
+    }) || term_program == Some("Apple_Terminal")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn theme(config: &str) -> (Theme, Vec<String>) {
+        Theme::from_config(&toml::from_str(config).unwrap())
+    }
+
+    #[test]
+    fn only_terminals_known_to_lack_truecolor_get_ansi() {
+        assert!(!lacks_truecolor(None, Some("xterm-256color"), None));
+        assert!(!lacks_truecolor(None, Some("tmux-256color"), None));
+        assert!(lacks_truecolor(None, Some("linux"), None));
+        assert!(lacks_truecolor(None, Some("xterm-16color"), None));
+        assert!(lacks_truecolor(
+            None,
+            Some("xterm-256color"),
+            Some("Apple_Terminal")
+        ));
+        assert!(!lacks_truecolor(
+            Some("truecolor"),
+            Some("xterm-256color"),
+            Some("Apple_Terminal")
+        ));
+    }
+
+    #[test]
+    fn every_listed_name_is_a_builtin() {
+        for name in THEME_NAMES {
+            assert!(Theme::named(name).is_some(), "{name}");
+        }
+    }
+
+    #[test]
+    fn overrides_apply_on_top_of_the_named_theme() {
+        let (theme, errors) = theme("name = \"latte\"\nred = \"#ff0000\"\nblue = \"lightblue\"\n");
+        assert!(errors.is_empty(), "{errors:?}");
+        assert_eq!(theme.red, Color::Rgb(255, 0, 0));
+        assert_eq!(theme.blue, Color::LightBlue);
+        assert_eq!(theme.green, Theme::latte().green);
+    }
+
+    #[test]
+    fn reports_unknown_names_and_bad_colors() {
+        let (theme, errors) =
+            theme("name = \"dracula\"\npink = \"red\"\nred = \"nope\"\ngreen = \"2\"\n");
+        assert_eq!(errors.len(), 3, "{errors:?}");
+        assert!(errors[0].contains("unknown theme 'dracula'"));
+        assert_eq!(theme.green, Color::Indexed(2));
+    }
+}